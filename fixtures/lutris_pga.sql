@@ -0,0 +1,38 @@
+-- Trimmed down copy of the games table Lutris creates in pga.db
+CREATE TABLE games (
+    id INTEGER PRIMARY KEY,
+    name TEXT,
+    slug TEXT,
+    installer_slug TEXT,
+    parent_slug TEXT,
+    platform TEXT,
+    runner TEXT,
+    executable TEXT,
+    directory TEXT,
+    updated DATETIME,
+    lastplayed INTEGER,
+    installed INTEGER,
+    installed_at INTEGER,
+    year INTEGER,
+    configpath TEXT,
+    has_custom_banner INTEGER,
+    has_custom_icon INTEGER,
+    has_custom_coverart_big INTEGER,
+    playtime REAL,
+    hidden INTEGER,
+    service TEXT,
+    service_id TEXT,
+    discord_id TEXT
+);
+
+INSERT INTO games (name, slug, platform, runner, lastplayed, installed, playtime, service)
+VALUES ('Celeste', 'celeste', 'Linux', 'linux', 1700000000, 1, 2.5, NULL);
+
+INSERT INTO games (name, slug, platform, runner, lastplayed, installed, playtime, service)
+VALUES ('Disco Elysium', 'disco-elysium', NULL, 'wine', 0, 1, 0, 'gog');
+
+INSERT INTO games (name, slug, platform, runner, lastplayed, installed, playtime, service)
+VALUES ('Super Metroid', 'super-metroid', 'Nintendo SNES', 'snes9x', NULL, 0, 0.75, NULL);
+
+INSERT INTO games (name, slug, platform, runner, lastplayed, installed, playtime, service)
+VALUES ('Never Installed', 'never-installed', 'Linux', 'linux', 0, 0, 0, 'egs');
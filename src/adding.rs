@@ -1,6 +1,7 @@
-use eframe::egui::{Button, Checkbox, Color32, Label, RichText, TextEdit, Vec2};
+use std::time::Duration;
+use eframe::egui::{Button, DragValue, Checkbox, Color32, Label, RichText, TextEdit, Vec2};
 
-use crate::{app_setup::GameLog, clock::get_date, egui::Ui, enums::Rating, json_file_operations::{save_to_file, search_for_game, Game}};
+use crate::{app_setup::GameLog, clock::get_date, covers::{cover_picker, remove_cover_file}, egui::Ui, json_file_operations::{search_for_game, Playthrough}, notes::notes_editor, platforms::platform_picker, star_rating::star_rating_input, status::status_picker, tags::{all_tags, tag_editor}, validation::{field_error_label, validate, GameDraft, GameField, MAX_NAME_LENGTH}};
 
 // "Adding" Window GUI Coded        
 
@@ -10,6 +11,7 @@ impl GameLog{
     {
         let label_size= Vec2::new(100.0, 20.0);
         let input_box_size = Vec2::new(150.0, 20.0);
+        let known_tags = all_tags(&self.game_file_contents); // For autocompleting tags
         
         ui.add_space(5.0);
 
@@ -22,8 +24,11 @@ impl GameLog{
 
                 ui.add_sized(input_box_size, TextEdit::singleline(&mut self.add_game_name)
                     .hint_text("Game Name (< 50 Char)")
-                    .char_limit(50));
+                    .char_limit(MAX_NAME_LENGTH));
             });
+            field_error_label(ui, &self.adding_field_errors, GameField::Name);
+
+            self.metadata_suggestions(ui); // "Fill from" results for the name as it's typed
 
             ui.add_space(2.0);
 
@@ -32,22 +37,92 @@ impl GameLog{
                     Label::new(RichText::new("Game Rating").strong())
                 );
 
-                ui.add_sized(input_box_size, TextEdit::singleline(&mut self.add_game_rating)
-                    .hint_text("Number between 1-5")
-                    .char_limit(1)
+                star_rating_input(ui, &mut self.add_game_rating, self.settings.rating_scale);
+            });
+            field_error_label(ui, &self.adding_field_errors, GameField::Rating);
+
+            ui.add_space(2.0);
+
+            ui.horizontal_top(|ui| {
+                ui.add_sized(label_size,
+                    Label::new(RichText::new("Game Notes").strong())
                 );
+
+                notes_editor(ui, &mut self.add_game_notes, &mut self.add_notes_preview, "This is optional", 250.0);
             });
 
             ui.add_space(2.0);
 
             ui.horizontal(|ui| {
                 ui.add_sized(label_size,
-                    Label::new(RichText::new("Game Notes").strong())
+                    Label::new(RichText::new("Platform").strong())
+                );
+
+                platform_picker(ui, "add_game_platform", &mut self.add_game_platform, &self.settings.platforms);
+            });
+
+            ui.add_space(2.0);
+
+            ui.horizontal(|ui| {
+                ui.add_sized(label_size,
+                    Label::new(RichText::new("Status").strong())
+                );
+
+                status_picker(ui, "add_game_status", &mut self.add_game_status);
+            });
+
+            ui.add_space(2.0);
+
+            ui.horizontal(|ui| {
+                ui.add_sized(label_size,
+                    Label::new(RichText::new("Storefront").strong())
+                );
+
+                ui.add_sized(input_box_size, TextEdit::singleline(&mut self.add_game_storefront)
+                    .hint_text("Steam, GOG, eShop... (Optional)")
+                    .char_limit(30)
+                );
+            });
+
+            ui.add_space(2.0);
+
+            ui.horizontal(|ui| {
+                ui.add_sized(label_size,
+                    Label::new(RichText::new("Play Time").strong())
+                );
+
+                ui.add(DragValue::new(&mut self.add_play_time_hours).range(0..=10_000).suffix(" hours")); // Optional, for games played before they were logged
+            });
+
+            ui.add_space(2.0);
+
+            ui.horizontal(|ui| {
+                ui.add_sized(label_size,
+                    Label::new(RichText::new("Game Tags").strong())
                 );
 
-                ui.add_sized(input_box_size, TextEdit::singleline(&mut self.add_game_notes)
-                    .hint_text("This is optional")
+                tag_editor(ui, &mut self.add_game_tags, &mut self.add_tag_input, &known_tags);
+            });
+            field_error_label(ui, &self.adding_field_errors, GameField::Tags);
+
+            ui.add_space(2.0);
+
+            ui.horizontal(|ui| {
+                ui.add_sized(label_size,
+                    Label::new(RichText::new("Cover").strong())
                 );
+
+                let previous_cover = self.add_game_cover.clone();
+
+                if let Some(error) = cover_picker(ui, &mut self.add_cover_path, &mut self.add_game_cover, &self.add_game_name) {
+                    self.adding_feedback_message = error;
+                    self.error_confirmation = true;
+                }
+
+                // The game isn't in the log yet, so a replaced cover isn't used by anything
+                if previous_cover != self.add_game_cover {
+                    remove_cover_file(&previous_cover);
+                }
             });
 
             ui.add_space(2.0);  
@@ -57,7 +132,7 @@ impl GameLog{
                 ui.add_sized(Vec2::new(200.0, 20.0),
                         Label::new(RichText::new(&self.adding_feedback_message)
                         .color(
-                            if self.error_confirmation == true {
+                            if self.error_confirmation {
                                 Color32::RED
                             }
                             else if self.dark_mode { // Better Color For Dark Mode
@@ -85,54 +160,84 @@ impl GameLog{
 
                             self.error_confirmation = true; // Reset text colour to red, will be changed back to green if the process is successful again 
 
-                            if !self.add_game_name.is_empty() && !self.add_game_rating.is_empty() // Making sure the user has inputted into the game name and ratings box (Notes is optional)
+                            self.adding_field_errors.clear();
+
+                            let date = get_date().to_string();
+
+                            let draft = GameDraft {
+                                name: self.add_game_name.clone(),
+                                rating: self.add_game_rating.clone(),
+                                rating_scale: self.settings.rating_scale,
+                                times_played: 1,
+                                last_playthrough: date.clone(),
+                                notes: self.add_game_notes.clone(),
+                                tags: self.add_game_tags.clone(),
+                                platform: self.add_game_platform.clone(),
+                                storefront: self.add_game_storefront.clone(),
+                                playthroughs: vec![Playthrough { // Adding a game counts as its first playthrough
+                                    date,
+                                    platform: self.add_game_platform.clone(),
+                                    storefront: self.add_game_storefront.clone(),
+                                    play_time: Duration::ZERO,
+                                    note: String::new(),
+                                }],
+                                play_time: Duration::from_secs(self.add_play_time_hours as u64 * 3600),
+                                executables: Vec::new(), // Linked from the edit window
+                                status: self.add_game_status,
+                                release_year: self.add_game_metadata.as_ref().and_then(|metadata| metadata.release_year),
+                                developer: self.add_game_metadata.as_ref().map(|metadata| metadata.developer.clone()).unwrap_or_default(),
+                                genres: self.add_game_metadata.as_ref().map(|metadata| metadata.genres.clone()).unwrap_or_default(),
+                                cover: self.add_game_cover.clone(),
+                            };
+
+                            match validate(&draft) // All the field rules (required boxes, name length, rating range) live in validation.rs
                             {
-                                match search_for_game(&self.game_file_contents, &self.add_game_name) // Make sure game isn't already in the log (Remind users who may have forgot)
+                                Ok(game) => match search_for_game(&self.game_file_contents, &game.name) // Make sure game isn't already in the log (Remind users who may have forgot)
                                 {
                                     Ok(index) => self.adding_feedback_message = format!{"Game is already in game log at index: {}", index},
 
-                                    Err(_) => match self.add_game_rating.trim().parse::<u8>() // Parsing into a number and dealing with potential errors
-                                            {
-                                        Ok(parsed) => {
-                                            if let Some(parsed_rating) = Rating::from_u8(parsed) { //Parsing into an enum
-
-                                                let date = get_date();
-
-                                                let game = Game {
-                                                    name: self.add_game_name.clone(),
-                                                    rating: parsed_rating,
-                                                    times_played: 1,
-                                                    last_playthrough: date.to_string(),
-                                                    notes: self.add_game_notes.clone(),
-                                                };
-
-                                                self.game_file_contents.push(game); // Add it to the vector so we can easily save it to the JSON file
-
-                                                match save_to_file(&self.game_file_contents)
-                                                {
-                                                    Ok(_) => {
-                                                        println!("CREATED"); // Game is added to the game log (Terminal Message)
-                                                        self.adding_feedback_message = format!("Game Added!"); // Remove any error messages previously acquired
-                                                        self.error_confirmation = false;
-                                                    },
-                                                    Err(_) => self.adding_feedback_message = format!("There was an error when adding the game to the file"),
-                                                };
-                                            }
-                                            else {
-                                                self.adding_feedback_message = format!("Invalid number entered!\nTry again buddy");
-                                            }
-                                        },
-                                        _ => self.adding_feedback_message = format!("Invalid rating entered!\nTry again buddy"), // Both this and the error on line 81 are because of a parse error, hence same error message
+                                    Err(_) => {
+                                        // No cover picked, so use the one from the metadata lookup (downloaded in the background)
+                                        let cover_url = self.add_game_metadata.as_ref().and_then(|metadata| metadata.cover_url.clone()).filter(|_| game.cover.is_empty());
+                                        let game_name = game.name.clone();
+
+                                        // Add it to the vector and save it to the JSON file
+                                        match self.apply_change(&format!("Add {}", game_name), |game_log| {
+                                            game_log.game_file_contents.push(game);
+                                            Ok(())
+                                        })
+                                        {
+                                            Ok(_) => {
+                                                println!("CREATED"); // Game is added to the game log (Terminal Message)
+                                                self.adding_feedback_message = String::from("Game Added!"); // Remove any error messages previously acquired
+                                                self.error_confirmation = false;
+
+                                                // Clear all input boxes once the game is in, leaving them filled on errors so users can fix their mistakes
+                                                self.add_game_name.clear();
+                                                self.add_game_rating.clear();
+                                                self.add_game_notes.clear();
+                                                self.add_game_tags.clear();
+                                                self.add_tag_input.clear();
+                                                self.add_play_time_hours = 0;
+                                                self.add_game_status = None;
+                                                self.add_game_metadata = None;
+                                                self.add_game_cover.clear(); // Belongs to the game now
+                                                self.add_cover_path.clear();
+
+                                                if let Some(url) = cover_url {
+                                                    self.fetch_cover(ui.ctx(), &game_name, &url);
+                                                }
+                                                self.add_game_storefront.clear(); // Platform is kept as people tend to add a few games from the same one
+                                            },
+                                            Err(_) => self.adding_feedback_message = String::from("There was an error when adding the game to the file"),
+                                        };
                                     }
+                                },
+                                Err(errors) => {
+                                    self.adding_feedback_message = String::from("Some boxes need fixing!\nTry again buddy");
+                                    self.adding_field_errors = errors; // Shown next to each box
                                 }
-                            } else {
-                                self.adding_feedback_message = format!("You have missed some required boxes") // If users didn't fill in required boxes
                             }
-
-                            // Clear all inout boxes once the button is clicked and operations have been executed
-                            self.add_game_name.clear();
-                            self.add_game_rating.clear();
-                            self.add_game_notes.clear();
                         };
                 });
             });
@@ -1,6 +1,10 @@
 use eframe::{egui::{self, CentralPanel, Context, FontId, Layout, RichText, TextEdit, TextureHandle, TopBottomPanel}, App, Frame};
+use std::path::Path;
+use std::time::Instant;
+use chrono::NaiveDateTime;
+use std::sync::{mpsc::{Receiver, Sender}, Arc};
 use image::GenericImageView;
-use crate::{enums::WindowOpened, json_file_operations::{reading_json, search_for_game, Game}};
+use crate::{enums::{EditingTab, GameStatus, Rating, ViewMode, WindowOpened}, journal::{journal_covers, read_journal, JournalEntry, JOURNAL_FILE}, game_views::{frame_width, list_entry_notes}, settings::save_settings, importers::{steam::find_steam_root, ImportCandidate, ImportPreview}, json_file_operations::{reading_trash, search_games, Game}, storage::{open_storage, Storage, UnavailableStorage}, changes::Snapshot, backups::{backup_covers, list_backups, Backup, BackupPreview, BACKUPS_DIR}, instance_lock::{InstanceLock, LOCK_FILE}, outside_changes::MergeConflict, settings::{load_settings, Settings}, platforms::played_on, playtime::{load_session, play_time_summary, Session}, process_watcher::{ProcessWatcher, RunningProcess}, tags::has_tag, validation::FieldError, covers::{remove_unused_covers, CoverCache, COVERS_DIR}, metadata::{details_line, provider_from_settings, GameMetadata, MetadataLookup, MetadataProvider}};
 
 
 /// Stores the application's state, including UI settings and user input.
@@ -10,17 +14,49 @@ pub struct GameLog {
     // General Settings/File Importing
     pub dark_mode: bool, 
     pub assets: Vec<egui::TextureHandle>,
-    pub game_file_contents: Vec<Game>, // Grabbing Gamelog details from the JSON file
+    pub game_file_contents: Vec<Game>, // Grabbing Gamelog details from the storage backend
+    pub settings: Settings, // Preferences saved between runs (Rating scale etc.)
+    pub storage: Box<dyn Storage>, // Where the log is saved (JSON file or SQLite database)
+    pub synced_games: Vec<Game>, // The log as the storage has it, which outside changes are merged against
+    pub outside_change: Option<Vec<Game>>, // Log changed outside the app, waiting to be reloaded or merged
+    pub merge_conflicts: Vec<MergeConflict>,
+    pub last_outside_check: Instant,
+    pub instance_lock: Option<InstanceLock>, // None when another copy of the app has the log, which makes this one read only
+    pub lock_error: String, // Who has the log, shown over the read only warning
+    pub storage_error: String, // Why the storage backend couldn't be opened (Also makes the app read only)
+    pub storage_overwrite: bool, // Let switching backends replace a log already saved in the other one
+    pub storage_feedback: String,
+
+    // Backups
+    pub saves_since_backup: u32,
+    pub last_backup: Option<NaiveDateTime>,
+    pub backups: Option<Vec<Backup>>, // Listed when the Backups window opens (None to list them again)
+    pub backup_preview: Option<BackupPreview>,
+    pub backup_feedback: String,
 
     // Search Game
     pub search_game: String,
     pub last_searched_term: String, // Stores last input of "search_game" so input feedback messages can linger after search_game is cleared
     pub invalid_search_message: String, // Display a message telling users their game isnt found. This shouldn't be updated each frame but needs to be global hence its a field
+    pub list_feedback_message: String, // Results of the right click menu and batch actions
+    pub list_feedback_error: bool,
     pub search_result: Option<Vec<Game>>, // Store search results for games
+    pub tag_filter: Option<String>, // Tag picked in the sidebar. Only games with this tag are listed
+    pub platform_filter: Option<String>, // Same as tag_filter but for platforms
+
+    // Session Timer
+    pub session: Option<Session>, // Running timer (Loaded on startup so it survives restarts)
+    pub timer_game_name: String,
+    pub timer_feedback_message: String,
+
+    // Automatic Play Time Detection
+    pub process_watcher: ProcessWatcher,
+    pub process_scans: Option<Receiver<Vec<RunningProcess>>>, // Scans coming in from the background thread (None when turned off)
+    pub running_processes: Vec<RunningProcess>, // Latest scan, used to suggest executables in the edit window
 
     pub(crate) // Opening External Windows
     open_window: bool, // When this is true, code will execute to open a new window in the app
-    current_window_opened: WindowOpened,
+    pub(crate) current_window_opened: WindowOpened,
 
     // Global Variables to be used all over the program 
     pub checked: bool, // Checkbox variable
@@ -31,8 +67,18 @@ pub struct GameLog {
     pub add_game_name: String,
     pub add_game_rating: String,
     pub add_game_notes: String,
+    pub add_notes_preview: bool, // Showing the rendered Markdown instead of the notes box
+    pub add_game_tags: Vec<String>,
+    pub add_tag_input: String, // Tag currently being typed (Before it's added to the list)
+    pub add_game_platform: String,
+    pub add_game_status: Option<GameStatus>,
+    pub add_game_metadata: Option<GameMetadata>, // Details picked from a metadata lookup
+    pub add_game_cover: String, // Cover file made for the game being added
+    pub add_cover_path: String,
+    pub add_game_storefront: String,
+    pub add_play_time_hours: u32,
     pub adding_feedback_message: String,
-
+    pub adding_field_errors: Vec<FieldError>, // Inline messages shown beside each box that failed validation
 
     // Removing
     pub remove_game_name: String,
@@ -42,13 +88,75 @@ pub struct GameLog {
     pub edit_game_name: String,
     pub edit_game_rating: String,
     pub edit_game_notes: String,
+    pub edit_notes_preview: bool,
+    pub edit_game_tags: Vec<String>,
+    pub edit_tag_input: String,
+    pub edit_game_platform: String,
+    pub edit_game_status: Option<GameStatus>,
+    pub edit_game_cover: String,
+    pub edit_cover_path: String,
+    pub edit_game_storefront: String,
+    pub edit_play_time_hours: u32,
+    pub edit_play_time_minutes: u32,
+    pub edit_play_time_taken_off: bool, // Take the entered time off rather than adding it, for correcting mistakes
+    pub edit_game_executables: Vec<String>,
+    pub edit_executable_input: String,
     pub increase_times_played: i32,
     pub editing_search_game_name: String,
     pub editing_search_feedback: String,
-    pub increment_times_played: u8,
+    pub increment_times_played: i64, // Added to (or taken from) the game's times played when the edit is confirmed
     pub editing_search_error_confirmation: bool,
     pub editing_selected_index: usize,
     pub editing_feedback_message: String,
+    pub editing_field_errors: Vec<FieldError>,
+    pub editing_tab: EditingTab,
+    pub journal: Vec<JournalEntry>, // Every recorded change, oldest first (Shown in the History tab)
+    pub history_feedback: String,
+
+    // Tags
+    pub rename_tag_from: String,
+    pub rename_tag_to: String,
+    pub tags_feedback_message: String,
+
+    // Settings
+    pub new_platform_name: String,
+
+    // Metadata Lookups
+    pub metadata_provider: Option<Arc<dyn MetadataProvider>>, // None while lookups aren't set up
+    pub metadata_lookup: MetadataLookup,
+
+    // Covers
+    pub cover_cache: CoverCache,
+    pub cover_downloads: Option<Receiver<(String, Result<String, String>)>>, // (Game name, new cover file) from background downloads
+    pub cover_download_sender: Option<Sender<(String, Result<String, String>)>>,
+    pub selected_games: Vec<String>, // Picked in the list/table/grid (By name, so it survives games being removed)
+    pub selection_anchor: Option<String>, // Last game clicked without Shift, where Shift-click ranges start from
+    pub batch_status: Option<GameStatus>, // Batch action bar inputs
+    pub batch_tag: String,
+    pub batch_platform: String,
+
+    // Importing
+    pub import_source: usize, // Index into all_importers()
+    pub import_source_path: String, // Folder/file the importer reads from
+    pub import_candidates: Vec<ImportCandidate>, // Games found, waiting for review
+    pub import_rating: String, // Rating given to imported games their source didn't rate
+    pub import_preview: Vec<ImportPreview>, // Dry run shown before anything is added (Empty when not previewing)
+    pub import_feedback_message: String,
+
+    // Trash and Undo
+    pub trash: Vec<Game>, // Games moved to the trash (Saved next to the log)
+    pub undo_stack: Vec<Snapshot>,
+    pub redo_stack: Vec<Snapshot>,
+
+    // Command Palette (Ctrl+K)
+    pub palette_open: bool,
+    pub palette_query: String,
+    pub palette_selected: usize, // Highlighted match, moved with the arrow keys
+    pub palette_log_game: Option<String>, // Game a playthrough is being logged for
+    pub palette_feedback: String,
+    pub log_run_date: String, // Date/note boxes for logging a playthrough (Right click menu and palette)
+    pub log_run_note: String,
+    pub log_run_platform: String, // Empty for the game's own platform
 }
 
 /// App settings on startup
@@ -61,12 +169,64 @@ impl GameLog {
     pub fn startup(ctx: &egui::Context)  -> Self {
         // General Settings/File Importing
         let assets = Self::load_assets_from_bytes(ctx);
-        let game_file_contents = reading_json(); // Grabbing Gamelog details from the JSON file
+        let settings = load_settings();
+        // Falling back to another backend would show (and save over) a different log, so a log that can't be opened is only reported
+        let (mut storage, storage_error): (Box<dyn Storage>, String) = match open_storage(settings.storage_backend) {
+            Ok(storage) => (storage, String::new()),
+            Err(error) => {
+                let error = format!("Error Opening the {} Game Log: {}", settings.storage_backend, error);
+                (Box::new(UnavailableStorage { error: error.clone() }), error)
+            },
+        };
+        let game_file_contents = storage.load().unwrap_or_else(|error| {
+            println!("Error Reading the Game Log: {}", error);
+            Vec::new() // Still opens with an empty list, like before
+        });
+        let synced_games = game_file_contents.clone();
+        let outside_change = None;
+        let merge_conflicts = Vec::new();
+        let last_outside_check = Instant::now();
+        let (instance_lock, lock_error) = match InstanceLock::acquire(LOCK_FILE) {
+            Ok(lock) => (Some(lock), String::new()),
+            Err(error) => (None, error.to_string()),
+        };
+        let storage_overwrite = false;
+        let storage_feedback = String::new();
+        let saves_since_backup = 0;
+        let last_backup = list_backups(Path::new(BACKUPS_DIR)).first().map(|backup| backup.created);
+        let backups = None;
+        let backup_preview = None;
+        let backup_feedback = String::new();
+        let trash = reading_trash();
+        let undo_stack = Vec::new();
+        let redo_stack = Vec::new();
+        let palette_open = false;
+        let palette_query = String::new();
+        let palette_selected = 0;
+        let palette_log_game = None;
+        let palette_feedback = String::new();
+        let log_run_date = String::new();
+        let log_run_note = String::new();
+        let log_run_platform = String::new();
 
         // Main Menu Searching
         let search_game: String = String::new(); 
         let last_searched_term: String = String::new(); 
         let invalid_search_message = String::new(); 
+        let list_feedback_message = String::new();
+        let list_feedback_error = true;
+        let tag_filter = None;
+        let platform_filter = None;
+
+        // Session Timer
+        let session = load_session();
+        let timer_game_name = session.as_ref().map(|session| session.game_name.clone()).unwrap_or_default();
+        let timer_feedback_message = String::new();
+
+        // Automatic Play Time Detection (Scanner is started at the bottom once the app exists)
+        let process_watcher = ProcessWatcher::default();
+        let process_scans = None;
+        let running_processes = Vec::new();
         
 
         // Opening External Windows
@@ -82,7 +242,18 @@ impl GameLog {
         let add_game_name = String::new();
         let add_game_rating = String::new();
         let add_game_notes = String::new();
+        let add_notes_preview = false;
+        let add_game_tags = Vec::new();
+        let add_tag_input = String::new();
+        let add_game_platform = String::new();
+        let add_game_status = None;
+        let add_game_metadata = None;
+        let add_game_cover = String::new();
+        let add_cover_path = String::new();
+        let add_game_storefront = String::new();
+        let add_play_time_hours = 0;
         let adding_feedback_message = String::new();
+        let adding_field_errors = Vec::new();
 
         // Removing
         let remove_game_name = String::new();
@@ -92,6 +263,19 @@ impl GameLog {
         let edit_game_name= String::new();
         let edit_game_rating= String::new();
         let edit_game_notes= String::new();
+        let edit_notes_preview = false;
+        let edit_game_tags = Vec::new();
+        let edit_tag_input = String::new();
+        let edit_game_platform = String::new();
+        let edit_game_status = None;
+        let edit_game_cover = String::new();
+        let edit_cover_path = String::new();
+        let edit_game_storefront = String::new();
+        let edit_play_time_hours = 0;
+        let edit_play_time_minutes = 0;
+        let edit_play_time_taken_off = false;
+        let edit_game_executables = Vec::new();
+        let edit_executable_input = String::new();
         let increase_times_played = 0;
         let editing_search_game_name = String::new();
         let editing_search_feedback = String::new();
@@ -99,14 +283,84 @@ impl GameLog {
         let editing_selected_index = 0;
         let editing_search_error_confirmation = true;
         let editing_feedback_message = String::new();
+        let editing_field_errors = Vec::new();
+        let editing_tab = EditingTab::default();
+        let journal = read_journal(Path::new(JOURNAL_FILE));
+        let history_feedback = String::new();
+
+        // Tags
+        let rename_tag_from = String::new();
+        let rename_tag_to = String::new();
+        let tags_feedback_message = String::new();
+
+        // Settings
+        let new_platform_name = String::new();
+
+        // Metadata Lookups
+        let metadata_provider = provider_from_settings(&settings);
+        let metadata_lookup = MetadataLookup::default();
+
+        // Covers
+        let cover_cache = CoverCache::default();
+        let cover_downloads = None;
+        let cover_download_sender = None;
+        let selected_games = Vec::new();
+        let selection_anchor = None;
+        let batch_status = None;
+        let batch_tag = String::new();
+        let batch_platform = String::new();
+
+        // Importing
+        let import_source = 0; // Steam
+        let import_source_path = find_steam_root().map(|root| root.display().to_string()).unwrap_or_default();
+        let import_candidates = Vec::new();
+        let import_rating = Rating::from_points(60).map(|rating| settings.rating_scale.format_value(rating)).unwrap_or_default(); // Middle of the scale
+        let import_preview = Vec::new();
+        let import_feedback_message = String::new();
+
+        // Covers are kept around for undo while the app is open, so any that ended up unused get tidied away here.
+        // Restoring a backup or reverting a change in the History tab can bring a cover back, so those count as in use too.
+        // Not while another copy is open though, as it may still need them (Or when the log couldn't be opened, as nothing would look in use)
+        if instance_lock.is_some() && storage_error.is_empty() {
+            let mut covers_in_use: Vec<String> = game_file_contents.iter().chain(trash.iter()).map(|game| game.cover.clone()).collect();
+            covers_in_use.extend(backup_covers(Path::new(BACKUPS_DIR)));
+            covers_in_use.extend(journal_covers(&journal));
+            remove_unused_covers(Path::new(COVERS_DIR), &covers_in_use.iter().map(String::as_str).collect::<Vec<&str>>());
+        }
         
-        Self { dark_mode: true, 
+        let mut game_log = Self { dark_mode: true, 
                 assets,
                 search_game,
                 last_searched_term,
                 invalid_search_message,
+                list_feedback_message,
+                list_feedback_error,
                 search_result: None,
+                tag_filter,
+                platform_filter,
+                session,
+                timer_game_name,
+                timer_feedback_message,
+                process_watcher,
+                process_scans,
+                running_processes,
                 game_file_contents,
+                settings,
+                storage,
+                synced_games,
+                outside_change,
+                merge_conflicts,
+                last_outside_check,
+                instance_lock,
+                lock_error,
+                storage_error,
+                storage_overwrite,
+                storage_feedback,
+                saves_since_backup,
+                last_backup,
+                backups,
+                backup_preview,
+                backup_feedback,
                 open_window,
                 current_window_opened,
                 checked,
@@ -114,21 +368,104 @@ impl GameLog {
                 add_game_name,
                 add_game_rating,
                 add_game_notes,
+                add_notes_preview,
+                add_game_tags,
+                add_tag_input,
+                add_game_platform,
+                add_game_status,
+                add_game_metadata,
+                add_game_cover,
+                add_cover_path,
+                add_game_storefront,
+                add_play_time_hours,
                 adding_feedback_message,
+                adding_field_errors,
                 error_confirmation,
                 remove_game_name,
                 removing_feedback_message,
                 edit_game_name,
                 edit_game_rating,
                 edit_game_notes,
+                edit_notes_preview,
+                edit_game_tags,
+                edit_tag_input,
+                edit_game_platform,
+                edit_game_status,
+                edit_game_cover,
+                edit_cover_path,
+                edit_game_storefront,
+                edit_play_time_hours,
+                edit_play_time_minutes,
+                edit_play_time_taken_off,
+                edit_game_executables,
+                edit_executable_input,
                 increase_times_played,
                 editing_search_game_name,
                 editing_search_feedback,
                 increment_times_played,
                 editing_selected_index,
                 editing_search_error_confirmation,
-                editing_feedback_message
-            }
+                editing_feedback_message,
+                editing_field_errors,
+                editing_tab,
+                journal,
+                history_feedback,
+                rename_tag_from,
+                rename_tag_to,
+                tags_feedback_message,
+                new_platform_name,
+                metadata_provider,
+                metadata_lookup,
+                cover_cache,
+                cover_downloads,
+                cover_download_sender,
+                selected_games,
+                selection_anchor,
+                batch_status,
+                batch_tag,
+                batch_platform,
+                trash,
+                undo_stack,
+                redo_stack,
+                palette_open,
+                palette_query,
+                palette_selected,
+                palette_log_game,
+                palette_feedback,
+                log_run_date,
+                log_run_note,
+                log_run_platform,
+                import_source,
+                import_source_path,
+                import_candidates,
+                import_rating,
+                import_preview,
+                import_feedback_message
+            };
+
+        // Start watching for running games if it was left turned on
+        game_log.set_auto_detect(game_log.settings.auto_detect_playtime);
+        game_log.startup_backup();
+
+        game_log
+    }
+
+    /// Whether a game passes the tag and platform filters picked in the sidebar
+    pub fn is_game_shown(&self, game: &Game) -> bool {
+        self.tag_filter.as_ref().is_none_or(|tag| has_tag(game, tag))
+            && self.platform_filter.as_ref().is_none_or(|platform| played_on(game, platform))
+    }
+
+    /// Left hand panel with the tag and platform filters
+    fn filter_sidebar(&mut self, ctx: &Context) {
+        egui::SidePanel::left("filter_panel").resizable(true).default_width(150.0).show(ctx, |ui| {
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                ui.add_space(10.0);
+                self.tag_filter_list(ui);
+                ui.add_space(15.0);
+                self.platform_filter_list(ui);
+            });
+        });
     }
 
     /// Load embedded image assets into the app context upon startup
@@ -167,6 +504,34 @@ impl GameLog {
 impl App for GameLog {
 
     fn update(&mut self, ctx: &Context, _frame: &mut Frame) {
+        self.poll_process_watcher(ctx); // Picks up games that have been started/closed since the last scan
+        self.poll_cover_downloads(ctx);
+        self.poll_outside_changes(ctx);
+        self.outside_change_window(ctx);
+        self.command_palette(ctx);
+
+        // Undo shortcuts, unless a text box is being typed in (Those have their own undo)
+        if !ctx.wants_keyboard_input() {
+            let redo_shortcut = egui::KeyboardShortcut::new(egui::Modifiers::COMMAND | egui::Modifiers::SHIFT, egui::Key::Z);
+            let undo_shortcut = egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::Z);
+
+            // Redo first, as Ctrl+Z would also match Ctrl+Shift+Z
+            if ctx.input_mut(|input| input.consume_shortcut(&redo_shortcut)) {
+                self.redo();
+            }
+            else if ctx.input_mut(|input| input.consume_shortcut(&undo_shortcut)) {
+                self.undo();
+            }
+
+            // Ctrl+A selects every game on screen, Escape lets go of them
+            if ctx.input_mut(|input| input.consume_key(egui::Modifiers::COMMAND, egui::Key::A)) {
+                self.selected_games = self.shown_games().into_iter().map(|i| self.game_file_contents[i].name.clone()).collect();
+            }
+            if ctx.input_mut(|input| input.consume_key(egui::Modifiers::NONE, egui::Key::Escape)) {
+                self.selected_games.clear();
+            }
+        }
+
         TopBottomPanel::top("top_panel").exact_height(40.0).show(ctx, |ui| {
             // Set the correct image depending on whether the appearance is currently light mode or dark mode
             let appearance_texture = if self.dark_mode {&self.assets[1]} else {&self.assets[0]};
@@ -206,6 +571,54 @@ impl App for GameLog {
                     self.current_window_opened = WindowOpened::Removing;
                 };
 
+                if ui.add_sized(appearance_size, egui::Button::new("Tags"))
+                .clicked() {
+                    self.open_window = true;
+                    self.current_window_opened = WindowOpened::Tags;
+                };
+
+                if ui.add_sized(appearance_size, egui::Button::new("Import"))
+                .clicked() {
+                    self.open_window = true;
+                    self.current_window_opened = WindowOpened::Import;
+                };
+
+                if ui.add_sized(appearance_size, egui::Button::new("Statistics"))
+                .clicked() {
+                    self.open_window = true;
+                    self.current_window_opened = WindowOpened::Statistics;
+                };
+
+                if ui.add_sized(appearance_size, egui::Button::new("Settings"))
+                .clicked() {
+                    self.open_window = true;
+                    self.current_window_opened = WindowOpened::Settings;
+                };
+
+                if ui.add_sized(appearance_size, egui::Button::new("Commands"))
+                .on_hover_text("Ctrl+K")
+                .clicked() {
+                    self.palette_open = true;
+                };
+
+
+                ui.separator();
+
+                // Undo/Redo (Ctrl+Z and Ctrl+Shift+Z also work)
+                let undo_hover = self.undo_stack.last().map(|change| format!("Undo: {}", change.description)).unwrap_or_default();
+                if ui.add_enabled(!self.undo_stack.is_empty(), egui::Button::new("Undo")).on_hover_text(undo_hover).clicked() {
+                    self.undo();
+                }
+
+                let redo_hover = self.redo_stack.last().map(|change| format!("Redo: {}", change.description)).unwrap_or_default();
+                if ui.add_enabled(!self.redo_stack.is_empty(), egui::Button::new("Redo")).on_hover_text(redo_hover).clicked() {
+                    self.redo();
+                }
+
+                ui.separator();
+
+                // Play session timer
+                self.session_timer_bar(ui, ctx);
 
                 // Dark/Light mode toggle
                 ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui|{
@@ -213,10 +626,26 @@ impl App for GameLog {
                     .clicked() {
                         self.dark_mode = !self.dark_mode;
                     }
+
+                    ui.separator();
+
+                    // List/Table/Grid toggle (Right to left, so added in reverse)
+                    let previous_view = self.settings.view_mode;
+                    for view_mode in ViewMode::ALL.into_iter().rev() {
+                        ui.selectable_value(&mut self.settings.view_mode, view_mode, view_mode.to_string());
+                    }
+                    if previous_view != self.settings.view_mode && save_settings(&self.settings).is_err() {
+                        println!("Error Saving Settings");
+                    }
+
+                    self.read_only_banner(ui);
                 });     
             });
         });
 
+        // Tags and platforms down the left hand side (Filters the list)
+        self.filter_sidebar(ctx);
+
         // Actual Contents of the window
         CentralPanel::default().show(ctx, |ui| {
 
@@ -228,7 +657,7 @@ impl App for GameLog {
             
 
             // Game Log Display Variables
-            let mut game_log_display = format!("The Game Log is empty :/"); // Message for when the game log is empty
+            let mut game_log_display = String::from("The Game Log is empty :/"); // Message for when the game log is empty
 
             ui.vertical_centered(|ui| {
                 ui.add_space(30.0);
@@ -241,12 +670,12 @@ impl App for GameLog {
                     ui.label("Search:"); // Affordance, telling users what the search bar is for
                     
                     let search_response = ui.add_sized(search_size, TextEdit::singleline(&mut self.search_game)// Save user's search input
-                        .hint_text("Enter the Game's Name")
+                        .hint_text("Enter the Game's Name or tag:name")
                         .char_limit(50) // Enforce a 50 character search limit so users can't break the layout :D 
                         .frame(true) // Frame appears upon cursor hover
                         .horizontal_align(egui::Align::Center)
                         .vertical_align(egui::Align::Center)
-                    ); 
+                    ).on_hover_text("Filters: tag:, platform:, status: and note: (Notes skip spoilers unless spoilers:show is added)"); 
 
                     // If Enter Key is pressed, execute a "Search File" function which will search the game log for the game name inputted.
                     // Right now i dont have this method so nothing really happens lol
@@ -255,16 +684,16 @@ impl App for GameLog {
 
                         self.last_searched_term = self.search_game.clone(); // Save the users input for message displaying 
 
-                        self.search_result = match search_for_game(&self.game_file_contents, &self.last_searched_term)
-                        {
-                            Ok(game_index) => {
-                                self.invalid_search_message.clear();
-                                Some(vec![self.game_file_contents[game_index].clone()]) // Save the game found to a field
-                            }
-                            Err(_) =>  {
-                                self.invalid_search_message = format!("Invalid Game"); // Display this message to tell users the game doesn't exist in the game log
-                                None // Contents of search_result is reset, displaying the whole list again
-                            }
+                        // Searches can be a game's name and/or "tag:" filters, so more than one game can be found
+                        let found = search_games(&self.game_file_contents, &self.last_searched_term);
+
+                        self.search_result = if found.is_empty() {
+                            self.invalid_search_message = String::from("Invalid Game"); // Display this message to tell users the game doesn't exist in the game log
+                            None // Contents of search_result is reset, displaying the whole list again
+                        }
+                        else {
+                            self.invalid_search_message.clear();
+                            Some(found.into_iter().map(|game_index| self.game_file_contents[game_index].clone()).collect()) // Save the games found to a field
                         };
                         
                         self.search_game.clear(); // Clear input, ready for next input
@@ -285,12 +714,32 @@ impl App for GameLog {
                         if !self.invalid_search_message.is_empty() {
                             ui.label(RichText::new(&self.invalid_search_message).color(egui::Color32::RED));
                         }
+
+                        // What happened after using a game's right click menu or the batch actions
+                        if !self.list_feedback_message.is_empty() {
+                            let colour = if self.list_feedback_error {
+                                egui::Color32::RED
+                            }
+                            else if self.dark_mode {
+                                egui::Color32::GREEN
+                            }
+                            else {
+                                egui::Color32::DARK_GREEN
+                            };
+                            ui.label(RichText::new(&self.list_feedback_message).color(colour));
+                        }
                     }
                 });
                 
 
                 ui.add_space(10.0);
 
+                // The list keeps the width the search layout is built around, while the table and grid use all the room the window has
+                let frame_width = frame_width(self.settings.view_mode, available_width, min_width_for_search);
+
+                // Status/tag/platform/trash actions for everything selected (Click, Ctrl-click and Shift-click games)
+                self.batch_action_bar(ui);
+
                 egui::ScrollArea::vertical().auto_shrink([false; 2]).show(ui, |ui| { // Allow horizontal & vertical growth
                     egui::Frame::default()
                         .fill(ui.visuals().extreme_bg_color)
@@ -299,49 +748,71 @@ impl App for GameLog {
                         .show(ui, |ui| {
 
                             // Content of frame. Will contain Game Log Info
-                            ui.set_width(600.0); // Set width so the frame doesnt take up the whole panel lol
+                            ui.set_width(frame_width); // Set width so the frame doesnt take up the whole panel lol
                             
-                            if !self.game_file_contents.is_empty() {
+                            if !self.game_file_contents.is_empty() && self.settings.view_mode == ViewMode::Table {
+                                let shown_games = self.shown_games();
+                                self.game_table(ui, &shown_games);
+                            }
+                            else if !self.game_file_contents.is_empty() && self.settings.view_mode == ViewMode::Grid {
+                                let shown_games = self.shown_games();
+                                self.game_grid(ui, &shown_games, frame_width);
+                            }
+                            else if !self.game_file_contents.is_empty() {
 
                                 // If there is a valid search result
-                                if !self.search_result.is_none() {
-
-                                    let game_found = self.search_result.as_ref().unwrap();
-                                    let game = &game_found[0];
-                                    game_log_display = format!(
-                                        "Name: {}\nRating: {}\nTimes Played: {}\nLast Playthrough: {}\nNotes: {}\n\n",
-                                        game.name,
-                                        game.rating,
-                                        game.times_played,
-                                        game.last_playthrough,
-                                        game.notes
-                                    );
-                                    // Display Search Results
-                                    ui.label(RichText::new(&game_log_display)
-                                            .size(20.0)
-                                            .strong()
-                                            .color(ui.visuals().text_color())
+                                if self.search_result.is_some() {
+
+                                    for index in self.shown_games() {
+                                        let game = &self.game_file_contents[index];
+                                        game_log_display = format!(
+                                            "Name: {}\nDetails: {}\nRating: {}\nStatus: {}\nTimes Played: {}\nPlay Time: {}\nLast Playthrough: {}\nPlatform: {}\nTags: {}",
+                                            game.name,
+                                            details_line(game.release_year, &game.developer, &game.genres),
+                                            self.settings.rating_scale.display(game.rating),
+                                            game.status.map(|status| status.to_string()).unwrap_or_default(),
+                                            game.times_played,
+                                            play_time_summary(game),
+                                            game.last_playthrough,
+                                            game.platform,
+                                            game.tags.join(", ")
                                         );
+                                        let notes = game.notes.clone();
+                                        // Display Search Results (Right click for things to do with the game)
+                                        let response = ui.add(egui::Label::new(self.list_entry_text(ui, &game_log_display, index)).sense(egui::Sense::click()));
+                                        self.game_interactions(response, index);
+                                        list_entry_notes(ui, &notes, index);
+                                    }
                                 }
 
                                 // Display whole list if no search result, and data is in JSON file
                                 else {
                                     // Create and display a label for every game in the game log in a structured and consistent manner
-                                    for (i, game) in self.game_file_contents.iter().enumerate() {
-                                        game_log_display = format!("Index: {} \nName: {} \nRating: {} \nTimes Played: {} \n Last Playthrough: {} \nNotes: {}\n\n",
+                                    for i in 0..self.game_file_contents.len() {
+                                        let game = &self.game_file_contents[i];
+
+                                        // Skip games that don't match the tag/platform picked in the sidebar
+                                        if !self.is_game_shown(game) {
+                                            continue;
+                                        }
+
+                                        game_log_display = format!("Index: {} \nName: {} \nDetails: {} \nRating: {} \nStatus: {} \nTimes Played: {} \nPlay Time: {} \n Last Playthrough: {} \nPlatform: {} \nTags: {}",
                                             i,
                                             game.name,
-                                            game.rating,
+                                            details_line(game.release_year, &game.developer, &game.genres),
+                                            self.settings.rating_scale.display(game.rating),
+                                            game.status.map(|status| status.to_string()).unwrap_or_default(),
                                             game.times_played,
+                                            play_time_summary(game),
                                             game.last_playthrough,
-                                            game.notes
+                                            game.platform,
+                                            game.tags.join(", ")
                                         );
+                                        let notes = game.notes.clone();
                                         // This is needed to print out every game in the list, as just using one outside the loop results in variable overwriting, so the label wont display every game
-                                        ui.label(RichText::new(&game_log_display)
-                                            .size(20.0)
-                                            .strong()
-                                            .color(ui.visuals().text_color())
-                                    );
+                                        let response = ui.add(egui::Label::new(self.list_entry_text(ui, &game_log_display, i)).sense(egui::Sense::click()));
+                                        self.game_interactions(response, i);
+                                        list_entry_notes(ui, &notes, i);
                                     }   
                                 }
                             }
@@ -387,6 +858,43 @@ impl App for GameLog {
                                 self.removing_gui(ui)
                             });
                     },
+                    WindowOpened::Tags => {
+                        egui::Window::new("Tags")
+                            .open(&mut open_window)
+                            .show(ctx, |ui| {
+                                self.tags_gui(ui)
+                            });
+                    },
+                    WindowOpened::Import => {
+                        egui::Window::new("Import Games")
+                            .min_width(400.0)
+                            .open(&mut open_window)
+                            .show(ctx, |ui| {
+                                self.import_gui(ui)
+                            });
+                    },
+                    WindowOpened::Statistics => {
+                        egui::Window::new("Statistics")
+                            .open(&mut open_window)
+                            .show(ctx, |ui| {
+                                self.statistics_gui(ui)
+                            });
+                    },
+                    WindowOpened::Settings => {
+                        egui::Window::new("Settings")
+                            .open(&mut open_window)
+                            .show(ctx, |ui| {
+                                self.settings_gui(ui)
+                            });
+                    },
+                    WindowOpened::Backups => {
+                        egui::Window::new("Backups")
+                            .min_width(400.0)
+                            .open(&mut open_window)
+                            .show(ctx, |ui| {
+                                self.backups_gui(ui)
+                            });
+                    },
                     WindowOpened::Default => { // This Will never be reached as it just exists as a default value
                             println!("All External Windows Closed")
                     },
@@ -403,8 +911,23 @@ impl App for GameLog {
                 self.adding_feedback_message.clear();
                 self.editing_feedback_message.clear();
                 self.removing_feedback_message.clear();
+                self.backups = None;
+                self.backup_preview = None;
+                self.backup_feedback.clear();
                 self.editing_search_feedback.clear();
+                self.adding_field_errors.clear();
+                self.editing_field_errors.clear();
+                self.editing_tab = EditingTab::Details;
+                self.history_feedback.clear();
+                self.tags_feedback_message.clear();
+                self.import_feedback_message.clear();
+                self.import_preview.clear();
             }
         });
     }
+
+    // Anything the process watcher is still timing is saved before the app closes
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        self.set_auto_detect(false);
+    }
 }
\ No newline at end of file
@@ -0,0 +1,407 @@
+// Crates/Imports
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+use chrono::{Local, NaiveDateTime};
+use eframe::egui::{self, DragValue, RichText, ScrollArea, Ui};
+use serde::de::IgnoredAny;
+
+use crate::{app_setup::GameLog, journal::JOURNAL_FILE, json_file_operations::Game, settings::save_settings};
+
+pub const BACKUPS_DIR: &str = "GameLogBackups"; // Snapshots of the log, next to GameLog.Json
+const FILE_PREFIX: &str = "GameLog-";
+const TIME_FORMAT: &str = "%Y-%m-%d_%H-%M-%S"; // Sorts in time order, and has nothing Windows won't allow in a file name
+const PREVIEW_NAMES: usize = 10; // Names listed under each part of a backup's differences
+
+/// A snapshot in the backups folder
+#[derive(Clone, Debug, PartialEq)]
+pub struct Backup {
+    pub path: PathBuf,
+    pub created: NaiveDateTime,
+    pub game_count: usize,
+}
+
+/// What restoring a backup would change, by game name
+#[derive(Default, Debug, PartialEq)]
+pub struct LogDiff {
+    pub added: Vec<String>, // In the backup but not the log, so they'd come back
+    pub removed: Vec<String>, // In the log but not the backup
+    pub changed: Vec<String>,
+}
+
+impl LogDiff {
+    pub fn is_empty(&self) -> bool
+    {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// A backup picked in the Backups window, read in so it can be compared and restored
+pub struct BackupPreview {
+    pub backup: Backup,
+    pub games: Vec<Game>,
+    pub diff: LogDiff,
+}
+
+/// Compare the log with a backup of it
+pub fn diff_logs(current: &[Game], backup: &[Game]) -> LogDiff
+{
+    let current_by_name: HashMap<&str, &Game> = current.iter().map(|game| (game.name.as_str(), game)).collect();
+    let backup_by_name: HashMap<&str, &Game> = backup.iter().map(|game| (game.name.as_str(), game)).collect();
+    let mut diff = LogDiff::default();
+
+    for game in backup {
+        match current_by_name.get(game.name.as_str()) {
+            None => diff.added.push(game.name.clone()),
+            Some(current_game) if *current_game != game => diff.changed.push(game.name.clone()),
+            Some(_) => (),
+        }
+    }
+    diff.removed = current.iter().filter(|game| !backup_by_name.contains_key(game.name.as_str())).map(|game| game.name.clone()).collect();
+
+    diff
+}
+
+/// The copy of the journal kept with a backup (GameLog-<time>.journal.jsonl), so the history can be restored with it
+pub fn backup_journal_path(backup: &Path) -> PathBuf
+{
+    backup.with_extension("journal.jsonl")
+}
+
+/// Write the log into the backups folder, named after when it was taken, with a copy of the `journal` file if there is one.
+///
+/// A second backup in the same second (e.g. Back Up Now straight after an automatic one) is numbered rather than written over the first
+pub fn write_backup(dir: &Path, games: &[Game], journal: &Path, now: NaiveDateTime) -> Result<PathBuf, anyhow::Error>
+{
+    fs::create_dir_all(dir)?;
+
+    let stamp = now.format(TIME_FORMAT);
+    let path = (1..)
+        .map(|number| match number {
+            1 => dir.join(format!("{}{}.Json", FILE_PREFIX, stamp)),
+            number => dir.join(format!("{}{} ({}).Json", FILE_PREFIX, stamp, number)),
+        })
+        .find(|path| !path.exists())
+        .expect("Some number is free");
+    fs::write(&path, serde_json::to_string_pretty(games)?)?;
+
+    if journal.exists() {
+        fs::copy(journal, backup_journal_path(&path))?;
+    }
+
+    Ok(path)
+}
+
+// When a backup was taken and which one it was that second (1 for the first), from its file name (None for anything else in the folder)
+fn backup_time(path: &Path) -> Option<(NaiveDateTime, u32)>
+{
+    let stem = path.file_stem()?.to_str()?.strip_prefix(FILE_PREFIX)?;
+
+    let (time, number) = match stem.strip_suffix(')').and_then(|stem| stem.rsplit_once(" (")) {
+        Some((time, number)) => (time, number.parse().ok()?),
+        None => (stem, 1),
+    };
+
+    Some((NaiveDateTime::parse_from_str(time, TIME_FORMAT).ok()?, number))
+}
+
+/// Every backup in the folder, newest first
+pub fn list_backups(dir: &Path) -> Vec<Backup>
+{
+    let Ok(entries) = fs::read_dir(dir) else { return Vec::new() };
+
+    let mut backups: Vec<Backup> = entries.flatten()
+        .filter_map(|entry| {
+            let path = entry.path();
+            let (created, _) = backup_time(&path)?;
+
+            // Only counted, so the games themselves aren't read in
+            let file = File::open(&path).ok()?;
+            let games: Vec<IgnoredAny> = serde_json::from_reader(BufReader::new(file)).ok()?;
+
+            Some(Backup { path, created, game_count: games.len() })
+        })
+        .collect();
+
+    backups.sort_by_key(|backup| std::cmp::Reverse(backup_time(&backup.path)));
+    backups
+}
+
+pub fn read_backup(path: &Path) -> Result<Vec<Game>, anyhow::Error>
+{
+    Ok(serde_json::from_reader(BufReader::new(File::open(path)?))?)
+}
+
+/// Every cover file used by a game in any backup, so restoring one never brings back a game whose cover was deleted
+pub fn backup_covers(dir: &Path) -> Vec<String>
+{
+    list_backups(dir).iter()
+        .filter_map(|backup| read_backup(&backup.path).ok())
+        .flatten()
+        .map(|game| game.cover)
+        .filter(|cover| !cover.is_empty())
+        .collect()
+}
+
+/// Delete backups beyond the newest `keep` (0 for no limit) and ones older than `max_age_days` (0 for any age).
+/// The newest backup is always kept. Returns how many were deleted
+pub fn prune_backups(dir: &Path, keep: usize, max_age_days: u32, now: NaiveDateTime) -> usize
+{
+    let backups = list_backups(dir);
+    let mut removed = 0;
+
+    for (i, backup) in backups.iter().enumerate().skip(1) {
+        let too_many = keep > 0 && i >= keep;
+        let too_old = max_age_days > 0 && (now - backup.created).num_days() >= max_age_days as i64;
+
+        if (too_many || too_old) && fs::remove_file(&backup.path).is_ok() {
+            let _ = fs::remove_file(backup_journal_path(&backup.path)); // Older backups won't have one
+            removed += 1;
+        }
+    }
+
+    removed
+}
+
+// Backups GUI Code
+impl GameLog {
+    /// Back the log up now, then tidy away backups the retention settings don't keep
+    pub fn make_backup(&mut self) -> Result<PathBuf, String> {
+        let now = Local::now().naive_local();
+        let path = write_backup(Path::new(BACKUPS_DIR), &self.game_file_contents, Path::new(JOURNAL_FILE), now).map_err(|error| format!("Error Backing Up the Log: {}", error))?;
+
+        prune_backups(Path::new(BACKUPS_DIR), self.settings.backups_kept, self.settings.backup_max_age_days, now);
+        self.saves_since_backup = 0;
+        self.last_backup = Some(now);
+        self.backups = None; // Listed again next time the window shows them
+
+        Ok(path)
+    }
+
+    // Whether today still needs its daily backup
+    fn daily_backup_due(&self) -> bool {
+        self.settings.backup_daily && self.last_backup.is_none_or(|last| last.date() < Local::now().date_naive())
+    }
+
+    /// Startup and daily backups, taken when the app opens
+    pub fn startup_backup(&mut self) {
+        if self.read_only() || self.game_file_contents.is_empty() {
+            return;
+        }
+
+        if (self.settings.backup_on_startup || self.daily_backup_due())
+            && let Err(error) = self.make_backup() {
+            println!("{}", error);
+        }
+    }
+
+    /// Called after every save, for the every N saves and daily backups
+    pub fn count_save_for_backup(&mut self) {
+        self.saves_since_backup += 1;
+
+        let every_saves = self.settings.backup_every_saves;
+        if ((every_saves > 0 && self.saves_since_backup >= every_saves) || self.daily_backup_due())
+            && let Err(error) = self.make_backup() {
+            println!("{}", error);
+        }
+    }
+
+    // Automatic backup options (Shown in the Backups window)
+    fn backup_settings(&mut self, ui: &mut Ui) {
+        let mut changed = false;
+
+        ui.label(RichText::new("Automatic Backups").strong());
+        changed |= ui.checkbox(&mut self.settings.backup_on_startup, "Every time the app opens").changed();
+        changed |= ui.checkbox(&mut self.settings.backup_daily, "Once a day").changed();
+
+        ui.horizontal(|ui| {
+            ui.label("Every");
+            changed |= ui.add(DragValue::new(&mut self.settings.backup_every_saves).range(0..=1000)).changed();
+            ui.label("saves (0 for never)");
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("Keep the newest");
+            changed |= ui.add(DragValue::new(&mut self.settings.backups_kept).range(0..=1000)).changed();
+            ui.label("(0 for all)");
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("Delete backups older than");
+            changed |= ui.add(DragValue::new(&mut self.settings.backup_max_age_days).range(0..=3650)).changed();
+            ui.label("days (0 for never)");
+        });
+
+        if changed && save_settings(&self.settings).is_err() {
+            println!("Error Saving Settings");
+        }
+    }
+
+    pub fn backups_gui(&mut self, ui: &mut Ui) {
+        self.backup_settings(ui);
+
+        ui.separator();
+
+        if ui.add_enabled(!self.read_only(), egui::Button::new("Back Up Now")).clicked() {
+            self.backup_feedback = match self.make_backup() {
+                Ok(_) => String::from("Backed up"),
+                Err(error) => error,
+            };
+        }
+
+        let backups = self.backups.get_or_insert_with(|| list_backups(Path::new(BACKUPS_DIR))).clone();
+        let mut picked = None;
+
+        if backups.is_empty() {
+            ui.label(RichText::new("No backups yet").italics());
+        }
+
+        ScrollArea::vertical().id_salt("backups_list").max_height(200.0).show(ui, |ui| {
+            for backup in &backups {
+                let selected = self.backup_preview.as_ref().is_some_and(|preview| preview.backup.path == backup.path);
+                let label = format!("{}   {} game(s)", backup.created.format("%Y-%m-%d %H:%M:%S"), backup.game_count);
+
+                if ui.selectable_label(selected, label).clicked() {
+                    picked = Some(backup.clone());
+                }
+            }
+        });
+
+        if let Some(backup) = picked {
+            match read_backup(&backup.path) {
+                Ok(games) => {
+                    let diff = diff_logs(&self.game_file_contents, &games);
+                    self.backup_preview = Some(BackupPreview { backup, games, diff });
+                    self.backup_feedback.clear();
+                },
+                Err(error) => self.backup_feedback = format!("Error Reading the Backup: {}", error),
+            }
+        }
+
+        let mut restore = false;
+
+        if let Some(preview) = &self.backup_preview {
+            ui.separator();
+            ui.label(RichText::new(format!("Backup from {}", preview.backup.created.format("%Y-%m-%d %H:%M:%S"))).strong());
+
+            if preview.diff.is_empty() {
+                ui.label("Same as the log");
+            }
+            else {
+                ui.label("Restoring it would:");
+                for (names, what) in [(&preview.diff.added, "Bring back"), (&preview.diff.removed, "Remove"), (&preview.diff.changed, "Change")] {
+                    if names.is_empty() {
+                        continue;
+                    }
+
+                    let mut shown = names.iter().take(PREVIEW_NAMES).cloned().collect::<Vec<String>>().join(", ");
+                    if names.len() > PREVIEW_NAMES {
+                        shown.push_str(&format!(" and {} more", names.len() - PREVIEW_NAMES));
+                    }
+                    ui.label(format!("{} {} game(s): {}", what, names.len(), shown));
+                }
+            }
+
+            restore = ui.add_enabled(!self.read_only() && !preview.diff.is_empty(), egui::Button::new("Restore")).clicked();
+        }
+
+        if restore && let Some(preview) = self.backup_preview.take() {
+            let description = format!("Restore the backup from {}", preview.backup.created.format("%Y-%m-%d %H:%M"));
+
+            self.backup_feedback = match self.apply_change(&description, |game_log| {
+                game_log.game_file_contents = preview.games;
+                Ok(())
+            }) {
+                Ok(_) => match self.restore_journal(&backup_journal_path(&preview.backup.path)) {
+                    Ok(_) => String::from("Restored (Undo to put the log back)"),
+                    Err(error) => format!("Error Restoring the Change History: {} (The log was restored)", error),
+                },
+                Err(error) => error,
+            };
+        }
+
+        if !self.backup_feedback.is_empty() {
+            let color = if self.backup_feedback.starts_with("Error") || self.backup_feedback.starts_with("Read only") {
+                egui::Color32::RED
+            }
+            else if self.dark_mode {
+                egui::Color32::GREEN
+            }
+            else {
+                egui::Color32::DARK_GREEN
+            };
+            ui.label(RichText::new(&self.backup_feedback).color(color));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn game(name: &str, points: u8) -> Game {
+        serde_json::from_str(&format!(r#"{{"name": "{}", "rating": {}, "times_played": 1, "last_playthrough": "", "notes": ""}}"#, name, points)).unwrap()
+    }
+
+    #[test]
+    fn backups_are_listed_and_pruned() {
+        let dir = std::env::temp_dir().join(format!("gamelog-backups-test-{}", std::process::id()));
+        let day = |d| NaiveDate::from_ymd_opt(2025, 1, d).unwrap().and_hms_opt(12, 0, 0).unwrap();
+        let games = [game("Celeste", 80), game("Hades", 100)];
+
+        for d in 1..=5 {
+            write_backup(&dir, &games[..(d as usize % 2) + 1], &dir.join("no-journal"), day(d)).unwrap();
+        }
+        fs::write(dir.join("notes.txt"), "not a backup").unwrap();
+
+        let backups = list_backups(&dir);
+        assert_eq!(backups.len(), 5);
+        assert_eq!(backups[0].created, day(5));
+        assert_eq!(backups[0].game_count, 2);
+
+        assert_eq!(prune_backups(&dir, 3, 0, day(5)), 2); // Only the newest three are kept
+        assert_eq!(prune_backups(&dir, 0, 1, day(10)), 2); // All too old, but the newest stays
+        assert_eq!(list_backups(&dir).len(), 1);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn backups_in_the_same_second_are_all_kept() {
+        let dir = std::env::temp_dir().join(format!("gamelog-backups-same-second-{}", std::process::id()));
+        let now = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap().and_hms_opt(12, 0, 0).unwrap();
+        let mut celeste = game("Celeste", 80);
+        celeste.cover = String::from("celeste-1.png");
+
+        fs::create_dir_all(&dir).unwrap();
+        let journal = dir.join("journal.jsonl");
+        fs::write(&journal, "{}\n").unwrap();
+
+        let first = write_backup(&dir, &[game("Hades", 100)], &journal, now).unwrap();
+        let second = write_backup(&dir, std::slice::from_ref(&celeste), &journal, now).unwrap();
+        let third = write_backup(&dir, &[], &journal, now).unwrap();
+
+        assert_ne!(first, second);
+        assert_eq!(second.file_name().unwrap(), "GameLog-2025-01-01_12-00-00 (2).Json");
+        assert_eq!(fs::read_to_string(backup_journal_path(&second)).unwrap(), "{}\n"); // The journal goes with each backup (And isn't listed as one)
+        assert_eq!(list_backups(&dir).iter().map(|backup| backup.path.clone()).collect::<Vec<PathBuf>>(), vec![third, second, first]); // Newest first
+        assert_eq!(backup_covers(&dir), vec![String::from("celeste-1.png")]);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn diffs_by_name() {
+        let current = vec![game("Celeste", 80), game("Hades", 100)];
+        let backup = vec![game("Celeste", 60), game("Tunic", 80)];
+
+        assert_eq!(diff_logs(&current, &backup), LogDiff {
+            added: vec![String::from("Tunic")],
+            removed: vec![String::from("Hades")],
+            changed: vec![String::from("Celeste")],
+        });
+        assert!(diff_logs(&current, &current).is_empty());
+    }
+}
@@ -0,0 +1,348 @@
+// Crates/Imports
+use std::time::Duration;
+use chrono::NaiveDate;
+
+use crate::{app_setup::GameLog, clock::get_date, enums::GameStatus, importers::parse_date, instance_lock::{InstanceLock, SAVE_LOCK_TIMEOUT, WRITE_LOCK_FILE}, json_file_operations::{save_trash, search_for_game, search_games, Game, Playthrough}, storage::save_changes, validation::MAX_NAME_LENGTH};
+
+const UNDO_LIMIT: usize = 50; // Every step holds a whole copy of the log, so only the latest ones are kept
+
+// "Name (Copy)", then "Name (Copy 2)" and so on until it's a name the log doesn't have
+fn copy_name(games: &[Game], name: &str) -> String
+{
+    (1..).map(|n| {
+            let suffix = if n == 1 { String::from(" (Copy)") } else { format!(" (Copy {})", n) };
+            let base: String = name.chars().take(MAX_NAME_LENGTH - suffix.chars().count()).collect();
+            format!("{}{}", base.trim_end(), suffix)
+        })
+        .find(|copy| search_for_game(games, copy).is_err())
+        .unwrap_or_default()
+}
+
+/// Read the date of a run being logged. Left empty it's today, and runs can't be logged for days that haven't happened yet
+pub fn parse_run_date(text: &str) -> Result<NaiveDate, String>
+{
+    if text.trim().is_empty() {
+        return Ok(get_date());
+    }
+
+    let date = parse_date(text)
+        .and_then(|date| NaiveDate::parse_from_str(&date, "%Y-%m-%d").ok())
+        .ok_or_else(|| format!("{} isn't a date (Use YYYY-MM-DD)", text.trim()))?;
+
+    if date > get_date() {
+        return Err(String::from("That date is in the future"));
+    }
+
+    Ok(date)
+}
+
+/// Record another run of a game on `date`, with an optional note about it and the platform it was played on (Empty for the game's own).
+///
+/// Backdated runs go in order with the others, and only move `last_playthrough` if they're the latest
+pub fn record_playthrough(game: &mut Game, date: NaiveDate, note: &str, platform: &str) -> Result<(), String>
+{
+    let date = date.to_string();
+
+    game.times_played = game.times_played.checked_add(1).ok_or_else(|| String::from("Times played is already at its limit"))?;
+    if date > game.last_playthrough {
+        game.last_playthrough = date.clone();
+    }
+
+    let position = game.playthroughs.partition_point(|playthrough| playthrough.date <= date);
+    game.playthroughs.insert(position, Playthrough {
+        date,
+        platform: if platform.trim().is_empty() { game.platform.clone() } else { platform.trim().to_string() },
+        storefront: game.storefront.clone(),
+        play_time: Duration::ZERO,
+        note: note.trim().to_string(),
+    });
+
+    Ok(())
+}
+
+/// The log (and trash) as they were before a change, so it can be undone
+pub struct Snapshot {
+    pub description: String,
+    games: Vec<Game>,
+    trash: Vec<Game>,
+}
+
+// Shared way of changing the log, so every window, menu and background job saves and undoes the same way
+impl GameLog {
+    fn snapshot(&self, description: &str) -> Snapshot {
+        Snapshot {
+            description: description.to_string(),
+            games: self.game_file_contents.clone(),
+            trash: self.trash.clone(),
+        }
+    }
+
+    /// Save the log, only writing the games that differ from what's stored (The trash is small enough to just rewrite).
+    ///
+    /// If something else has changed the stored log, the log is held in memory until that's been reloaded or merged
+    pub fn save_log(&mut self, trash_changed: bool) -> Result<(), Box<dyn std::error::Error>> {
+        // Commands can save while the window is open, so only one of them writes at a time
+        let _write_lock = InstanceLock::wait_for(WRITE_LOCK_FILE, SAVE_LOCK_TIMEOUT)?;
+
+        if trash_changed {
+            save_trash(&self.trash)?;
+        }
+
+        if self.outside_change.is_some() || self.check_outside_changes() {
+            return Ok(());
+        }
+
+        save_changes(self.storage.as_mut(), &self.synced_games, &self.game_file_contents)?;
+        self.synced_games = self.game_file_contents.clone();
+        self.count_save_for_backup();
+        Ok(())
+    }
+
+    // Search results are copies of the games, so they're searched again to show the change
+    pub fn refresh_search(&mut self) {
+        if self.search_result.is_some() {
+            let found = search_games(&self.game_file_contents, &self.last_searched_term);
+            self.search_result = if found.is_empty() {
+                None
+            }
+            else {
+                Some(found.into_iter().map(|i| self.game_file_contents[i].clone()).collect())
+            };
+        }
+
+        // The game being edited may have moved (or be gone after an undo)
+        if !self.editing_search_error_confirmation {
+            match search_for_game(&self.game_file_contents, &self.editing_search_game_name) {
+                Ok(index) => self.editing_selected_index = index,
+                Err(_) => {
+                    self.editing_search_error_confirmation = true;
+                    self.editing_search_feedback = String::from("Game Not Found!");
+                }
+            }
+        }
+    }
+
+    /// Make a change to the log and save it, so it can be undone later.
+    ///
+    /// If `change` returns an error, or the log can't be saved afterwards, everything it did is put back
+    pub fn apply_change<T>(&mut self, description: &str, change: impl FnOnce(&mut GameLog) -> Result<T, String>) -> Result<T, String> {
+        if self.read_only() {
+            return Err(String::from("Read only while another copy of the app has the log open"));
+        }
+
+        let before = self.snapshot(description);
+
+        let value = match change(self) {
+            Ok(value) => value,
+            Err(error) => {
+                self.game_file_contents = before.games;
+                self.trash = before.trash;
+                return Err(error);
+            }
+        };
+
+        // Nothing to save or undo
+        if self.game_file_contents == before.games && self.trash == before.trash {
+            return Ok(value);
+        }
+
+        if self.save_log(self.trash != before.trash).is_err() {
+            self.game_file_contents = before.games;
+            self.trash = before.trash;
+            return Err(String::from("Error Saving to File"));
+        }
+
+        self.record_journal(&before.games);
+        self.undo_stack.push(before);
+        if self.undo_stack.len() > UNDO_LIMIT {
+            self.undo_stack.remove(0);
+        }
+        self.redo_stack.clear();
+        self.refresh_search();
+
+        Ok(value)
+    }
+
+    // Swap the log for a snapshot, keeping the current state on the other stack
+    fn step_history(&mut self, undoing: bool) {
+        if self.read_only() {
+            return;
+        }
+
+        let snapshot = if undoing { self.undo_stack.pop() } else { self.redo_stack.pop() };
+        let Some(snapshot) = snapshot else { return };
+
+        let current = self.snapshot(&snapshot.description);
+        self.game_file_contents = snapshot.games;
+        self.trash = snapshot.trash;
+
+        // Put the log and the step back if it couldn't be saved, so what's on screen still matches the file
+        if self.save_log(self.trash != current.trash).is_err() {
+            let step = self.snapshot(&current.description);
+            self.game_file_contents = current.games;
+            self.trash = current.trash;
+
+            if undoing {
+                self.undo_stack.push(step);
+            }
+            else {
+                self.redo_stack.push(step);
+            }
+            println!("Error Saving to File");
+            return;
+        }
+        self.record_journal(&current.games); // Undoing is a change too, as far as the history goes
+
+        if undoing {
+            self.redo_stack.push(current);
+        }
+        else {
+            self.undo_stack.push(current);
+        }
+        self.refresh_search();
+    }
+
+    /// Put the log back how it was before the last change
+    pub fn undo(&mut self) {
+        self.step_history(true);
+    }
+
+    pub fn redo(&mut self) {
+        self.step_history(false);
+    }
+
+    /// Record another run of a game. An empty `date` means today, and an empty `platform` the game's own
+    pub fn log_playthrough(&mut self, index: usize, date: &str, note: &str, platform: &str) -> Result<(), String> {
+        let date = parse_run_date(date)?;
+        let description = format!("Log playthrough of {}", self.game_file_contents[index].name);
+
+        self.apply_change(&description, |game_log| record_playthrough(&mut game_log.game_file_contents[index], date, note, platform))
+    }
+
+    pub fn set_status(&mut self, index: usize, status: Option<GameStatus>) -> Result<(), String> {
+        let description = format!("Status of {}", self.game_file_contents[index].name);
+
+        self.apply_change(&description, |game_log| {
+            game_log.game_file_contents[index].status = status;
+            Ok(())
+        })
+    }
+
+    /// Copy a game into the log under a new name, returning the name
+    pub fn duplicate_game(&mut self, index: usize) -> Result<String, String> {
+        let description = format!("Duplicate {}", self.game_file_contents[index].name);
+
+        self.apply_change(&description, |game_log| {
+            let mut copy = game_log.game_file_contents[index].clone();
+            copy.name = copy_name(&game_log.game_file_contents, &copy.name);
+
+            let name = copy.name.clone();
+            game_log.game_file_contents.insert(index + 1, copy); // Right under the original
+            Ok(name)
+        })
+    }
+
+    pub fn move_to_trash(&mut self, index: usize) -> Result<(), String> {
+        let description = format!("Move {} to the trash", self.game_file_contents[index].name);
+
+        self.apply_change(&description, |game_log| {
+            let game = game_log.game_file_contents.remove(index);
+            game_log.trash.push(game);
+            Ok(())
+        })
+    }
+
+    /// Change several games at once (One undo step and one save for the lot). Returns how many games it went over
+    pub fn change_games(&mut self, description: &str, indexes: &[usize], mut change: impl FnMut(&mut Game)) -> Result<usize, String> {
+        self.apply_change(description, |game_log| {
+            for &i in indexes {
+                change(&mut game_log.game_file_contents[i]);
+            }
+            Ok(indexes.len())
+        })
+    }
+
+    /// Move several games to the trash as one change
+    pub fn trash_games(&mut self, indexes: &[usize]) -> Result<usize, String> {
+        let description = format!("Move {} game(s) to the trash", indexes.len());
+
+        self.apply_change(&description, |game_log| {
+            let start = game_log.trash.len();
+
+            // Back to front so removing one doesn't move the ones still to go
+            for &i in indexes.iter().rev() {
+                let game = game_log.game_file_contents.remove(i);
+                game_log.trash.push(game);
+            }
+            game_log.trash[start..].reverse(); // Back into log order
+
+            Ok(indexes.len())
+        })
+    }
+
+    /// Put a trashed game back at the end of the log
+    pub fn restore_from_trash(&mut self, trash_index: usize) -> Result<(), String> {
+        let description = format!("Restore {}", self.trash[trash_index].name);
+
+        self.apply_change(&description, |game_log| {
+            let game = game_log.trash.remove(trash_index);
+
+            if search_for_game(&game_log.game_file_contents, &game.name).is_ok() {
+                return Err(format!("{} is already in the log", game.name));
+            }
+
+            game_log.game_file_contents.push(game);
+            Ok(())
+        })
+    }
+
+    /// Delete a game from the trash. Can still be undone until the app is closed
+    pub fn delete_from_trash(&mut self, trash_index: usize) -> Result<(), String> {
+        let description = format!("Delete {}", self.trash[trash_index].name);
+
+        self.apply_change(&description, |game_log| {
+            game_log.trash.remove(trash_index);
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn copies_get_names_the_log_doesnt_have() {
+        let games: Vec<Game> = serde_json::from_str(r#"[
+            {"name": "Celeste", "rating": 80, "times_played": 1, "last_playthrough": "2025-01-01", "notes": ""},
+            {"name": "Celeste (Copy)", "rating": 80, "times_played": 1, "last_playthrough": "2025-01-01", "notes": ""}
+        ]"#).unwrap();
+
+        assert_eq!(copy_name(&games, "Celeste"), "Celeste (Copy 2)");
+        assert_eq!(copy_name(&games, "Hades"), "Hades (Copy)");
+
+        let long_name = "A".repeat(MAX_NAME_LENGTH);
+        assert_eq!(copy_name(&games, &long_name).chars().count(), MAX_NAME_LENGTH); // Still fits in the name box
+    }
+
+    #[test]
+    fn backdated_runs_keep_the_latest_date() {
+        let mut games: Vec<Game> = serde_json::from_str(r#"[
+            {"name": "Celeste", "rating": 80, "times_played": 1, "last_playthrough": "2025-03-01", "notes": "",
+             "playthroughs": [{"date": "2025-03-01"}]}
+        ]"#).unwrap();
+        let celeste = &mut games[0];
+
+        record_playthrough(celeste, NaiveDate::from_ymd_opt(2024, 12, 24).unwrap(), " Christmas run ", "Switch").unwrap();
+
+        assert_eq!(celeste.times_played, 2);
+        assert_eq!(celeste.last_playthrough, "2025-03-01");
+        assert_eq!(celeste.playthroughs[0].date, "2024-12-24");
+        assert_eq!(celeste.playthroughs[0].note, "Christmas run");
+        assert_eq!(celeste.playthroughs[0].platform, "Switch");
+
+        assert!(parse_run_date("").is_ok());
+        assert!(parse_run_date("2999-01-01").is_err());
+        assert!(parse_run_date("next week").is_err());
+    }
+}
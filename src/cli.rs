@@ -0,0 +1,162 @@
+// Crates/Imports
+use std::fs;
+use std::path::Path;
+
+use crate::{journal::{append_journal, journal_changes_now, merge_journal_file, read_journal, JOURNAL_FILE}, changes::{parse_run_date, record_playthrough}, instance_lock::{InstanceLock, CLI_LOCK_TIMEOUT, LOCK_FILE, WRITE_LOCK_FILE}, json_file_operations::search_for_game, settings::load_settings, storage::{open_storage, switch_backend, StorageBackend}};
+
+const USAGE: &str = "Usage:
+  my_gui_app                                                     Open the game log
+  my_gui_app log <game name> [--date YYYY-MM-DD] [--note TEXT]   Log another playthrough (Today if no date)
+                 [--platform NAME]                              Played on another platform than the game's own
+  my_gui_app convert <json|sqlite> [--overwrite]                 Move the log to another storage backend and use it from now on
+  my_gui_app export-history <file>                               Copy the change history (GameLogJournal.jsonl) to a file
+  my_gui_app import-history <file>                               Merge a copied change history into this one (e.g. from another computer)
+  my_gui_app help                                                Show this message";
+
+/// What `log` was asked to do
+#[derive(Debug, PartialEq)]
+pub struct LogArgs {
+    pub game: String,
+    pub date: String, // Empty for today
+    pub note: String,
+    pub platform: String, // Empty for the game's own
+}
+
+/// Read the arguments after `log`. Words that aren't options make up the game's name, so it doesn't have to be quoted
+pub fn parse_log_args(args: &[String]) -> Result<LogArgs, String>
+{
+    let mut name_words = Vec::new();
+    let mut date = String::new();
+    let mut note = String::new();
+    let mut platform = String::new();
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--date" => date = args.next().cloned().ok_or("--date needs a date after it")?,
+            "--note" => note = args.next().cloned().ok_or("--note needs some text after it")?,
+            "--platform" => platform = args.next().cloned().ok_or("--platform needs a platform after it")?,
+            _ => name_words.push(arg.as_str()),
+        }
+    }
+
+    if name_words.is_empty() {
+        return Err(String::from("Which game? e.g. my_gui_app log Celeste"));
+    }
+
+    Ok(LogArgs { game: name_words.join(" "), date, note, platform })
+}
+
+// Log a playthrough straight into the stored log. Works with the window open, which picks the change up like any other outside change
+fn log_playthrough(args: &LogArgs) -> Result<String, String>
+{
+    let _write_lock = InstanceLock::wait_for(WRITE_LOCK_FILE, CLI_LOCK_TIMEOUT).map_err(|error| error.to_string())?;
+    let mut storage = open_storage(load_settings().storage_backend).map_err(|error| format!("Error Opening the Game Log: {}", error))?;
+    let mut games = storage.load().map_err(|error| format!("Error Reading the Game Log: {}", error))?;
+    let index = search_for_game(&games, &args.game).map_err(|_| format!("{} isn't in the game log", args.game))?;
+    let date = parse_run_date(&args.date)?;
+
+    let game = &mut games[index];
+    let before = game.clone();
+    record_playthrough(game, date, &args.note, &args.platform)?;
+    storage.update(&before.name, game).map_err(|_| String::from("Error Saving to File"))?;
+
+    // Shows in the game's history like a change made in the window
+    let entries = journal_changes_now(std::slice::from_ref(&before), std::slice::from_ref(game));
+    if let Err(error) = append_journal(Path::new(JOURNAL_FILE), &entries) {
+        eprintln!("Error Saving the Change History: {}", error);
+    }
+
+    Ok(format!("Logged playthrough {} of {} on {}", game.times_played, game.name, date))
+}
+
+// Move the log to the backend named in `args`
+fn convert(args: &[String]) -> Result<String, String>
+{
+    let overwrite = args.iter().any(|arg| arg == "--overwrite");
+    let backend = args.iter()
+        .find(|arg| *arg != "--overwrite")
+        .ok_or("Convert to what? e.g. my_gui_app convert sqlite")?;
+    let backend = StorageBackend::parse(backend).ok_or_else(|| format!("Unknown storage backend: {} (Use json or sqlite)", backend))?;
+
+    // An open window would keep saving to the old backend, so it has to be closed first
+    let _window_lock = InstanceLock::acquire(LOCK_FILE).map_err(|error| format!("{}. Close it before converting", error))?;
+    let _write_lock = InstanceLock::wait_for(WRITE_LOCK_FILE, CLI_LOCK_TIMEOUT).map_err(|error| error.to_string())?;
+    let mut settings = load_settings();
+    let (_, count) = switch_backend(&mut settings, backend, overwrite)?;
+
+    Ok(format!("Moved {} game(s) to {}", count, backend))
+}
+
+// Copy the journal somewhere else. Exports and imports of the log itself leave the history out, this takes it along
+fn export_history(args: &[String]) -> Result<String, String>
+{
+    let path = args.first().ok_or("Export to where? e.g. my_gui_app export-history history.jsonl")?;
+
+    let _write_lock = InstanceLock::wait_for(WRITE_LOCK_FILE, CLI_LOCK_TIMEOUT).map_err(|error| error.to_string())?;
+    if !Path::new(JOURNAL_FILE).is_file() {
+        return Err(String::from("No changes have been recorded yet"));
+    }
+    fs::copy(JOURNAL_FILE, path).map_err(|error| format!("Error Exporting the Change History: {}", error))?;
+
+    Ok(format!("Exported {} change(s) to {}", read_journal(Path::new(path)).len(), path))
+}
+
+// Merge a copied journal into this one. Changes already in it are skipped, so importing the same file twice does nothing
+fn import_history(args: &[String]) -> Result<String, String>
+{
+    let path = args.first().ok_or("Import from where? e.g. my_gui_app import-history history.jsonl")?;
+    if !Path::new(path).is_file() {
+        return Err(format!("{} doesn't exist", path));
+    }
+
+    // An open window keeps its own copy of the history, so it has to be closed first
+    let _window_lock = InstanceLock::acquire(LOCK_FILE).map_err(|error| format!("{}. Close it before importing", error))?;
+    let _write_lock = InstanceLock::wait_for(WRITE_LOCK_FILE, CLI_LOCK_TIMEOUT).map_err(|error| error.to_string())?;
+    let before = read_journal(Path::new(JOURNAL_FILE)).len();
+    let merged = merge_journal_file(Path::new(JOURNAL_FILE), Path::new(path)).map_err(|error| format!("Error Importing the Change History: {}", error))?;
+
+    Ok(format!("Imported {} change(s) from {}", merged.len() - before, path))
+}
+
+/// Run a command given on the command line instead of opening the window. Returns the exit code
+pub fn run(args: &[String]) -> i32
+{
+    let result = match args[0].as_str() {
+        "log" => parse_log_args(&args[1..]).and_then(|log_args| log_playthrough(&log_args)),
+        "convert" => convert(&args[1..]),
+        "export-history" => export_history(&args[1..]),
+        "import-history" => import_history(&args[1..]),
+        "help" | "--help" | "-h" => Ok(USAGE.to_string()),
+        other => Err(format!("Unknown command: {}\n\n{}", other, USAGE)),
+    };
+
+    match result {
+        Ok(message) => {
+            println!("{}", message);
+            0
+        },
+        Err(error) => {
+            eprintln!("{}", error);
+            1
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(words: &[&str]) -> Vec<String> {
+        words.iter().map(|word| word.to_string()).collect()
+    }
+
+    #[test]
+    fn reads_log_arguments() {
+        let log_args = parse_log_args(&args(&["Hollow", "Knight", "--date", "2025-02-01", "--note", "Steel Soul", "--platform", "Steam Deck"])).unwrap();
+
+        assert_eq!(log_args, LogArgs { game: String::from("Hollow Knight"), date: String::from("2025-02-01"), note: String::from("Steel Soul"), platform: String::from("Steam Deck") });
+        assert!(parse_log_args(&args(&["Celeste", "--date"])).is_err());
+        assert!(parse_log_args(&args(&["--note", "fun"])).is_err());
+    }
+}
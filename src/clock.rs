@@ -1,7 +1,12 @@
 use chrono::{Local, NaiveDate};
 
 pub fn get_date() -> NaiveDate {
-    let today = Local::now().date_naive(); // Gets the local date (e.g., 2025-06-12)
+    let today = today();
     println!("Today's date is: {}", today);
-    return today
+    today
+}
+
+/// Same as get_date without printing it, for things worked out every frame
+pub fn today() -> NaiveDate {
+    Local::now().date_naive() // Gets the local date (e.g., 2025-06-12)
 }
\ No newline at end of file
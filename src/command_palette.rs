@@ -0,0 +1,244 @@
+// Crates/Imports
+use eframe::egui::{self, Align2, Context, Key, Modifiers, RichText, TextEdit};
+
+use crate::{app_setup::GameLog, enums::{ViewMode, WindowOpened}, json_file_operations::search_for_game, settings::save_settings};
+
+const MAX_RESULTS: usize = 8;
+
+/// Something that can be run from the command palette
+#[derive(Clone, PartialEq, Debug)]
+pub enum PaletteCommand {
+    Open(WindowOpened, &'static str),
+    SetView(ViewMode),
+    Undo,
+    Redo,
+    LogPlaythrough(String), // Asks for an optional date and note before logging
+    EditGame(String),
+}
+
+impl PaletteCommand {
+    pub fn label(&self) -> String {
+        match self {
+            PaletteCommand::Open(_, label) => label.to_string(),
+            PaletteCommand::SetView(view_mode) => format!("View: {}", view_mode),
+            PaletteCommand::Undo => String::from("Undo"),
+            PaletteCommand::Redo => String::from("Redo"),
+            PaletteCommand::LogPlaythrough(name) => format!("Log Playthrough: {}", name),
+            PaletteCommand::EditGame(name) => format!("Edit: {}", name),
+        }
+    }
+}
+
+/// How well a command matches what was typed, lower being better. Every typed letter has to appear in order (None if they don't)
+pub fn match_score(query: &str, label: &str) -> Option<usize>
+{
+    let query = query.trim().to_lowercase();
+    let label = label.to_lowercase();
+
+    if label.starts_with(&query) {
+        return Some(0);
+    }
+    if let Some(position) = label.find(&query) {
+        return Some(1 + position);
+    }
+
+    // Letters in order with gaps between them ("lp cel" finds "Log Playthrough: Celeste")
+    let mut letters = label.chars();
+    let mut skipped = 0;
+    for wanted in query.chars().filter(|letter| !letter.is_whitespace()) {
+        loop {
+            match letters.next() {
+                Some(letter) if letter == wanted => break,
+                Some(_) => skipped += 1,
+                None => return None,
+            }
+        }
+    }
+
+    Some(100 + skipped)
+}
+
+// Command Palette GUI Code
+impl GameLog {
+    // Every command, best matches for the query first
+    fn palette_matches(&self) -> Vec<PaletteCommand> {
+        let mut commands = vec![
+            PaletteCommand::Open(WindowOpened::Adding, "Add Game"),
+            PaletteCommand::Open(WindowOpened::Editing, "Edit Game"),
+            PaletteCommand::Open(WindowOpened::Removing, "Remove Game"),
+            PaletteCommand::Open(WindowOpened::Tags, "Tags"),
+            PaletteCommand::Open(WindowOpened::Import, "Import Games"),
+            PaletteCommand::Open(WindowOpened::Statistics, "Statistics"),
+            PaletteCommand::Open(WindowOpened::Settings, "Settings"),
+            PaletteCommand::Open(WindowOpened::Backups, "Backups"),
+            PaletteCommand::Undo,
+            PaletteCommand::Redo,
+        ];
+        commands.extend(ViewMode::ALL.into_iter().map(PaletteCommand::SetView));
+
+        for game in &self.game_file_contents {
+            commands.push(PaletteCommand::LogPlaythrough(game.name.clone()));
+            commands.push(PaletteCommand::EditGame(game.name.clone()));
+        }
+
+        let mut matches: Vec<(usize, PaletteCommand)> = commands.into_iter()
+            .filter_map(|command| match_score(&self.palette_query, &command.label()).map(|score| (score, command)))
+            .collect();
+        matches.sort_by_key(|(score, _)| *score); // Stable, so equal matches keep the order above
+
+        matches.into_iter().map(|(_, command)| command).take(MAX_RESULTS).collect()
+    }
+
+    fn open_window_from_palette(&mut self, window: WindowOpened) {
+        self.open_window = true;
+        self.current_window_opened = window;
+    }
+
+    fn run_palette_command(&mut self, command: PaletteCommand) {
+        let mut close = true;
+
+        match command {
+            PaletteCommand::Open(window, _) => self.open_window_from_palette(window),
+            PaletteCommand::SetView(view_mode) => {
+                self.settings.view_mode = view_mode;
+                if save_settings(&self.settings).is_err() {
+                    println!("Error Saving Settings");
+                }
+            },
+            PaletteCommand::Undo => self.undo(),
+            PaletteCommand::Redo => self.redo(),
+            PaletteCommand::LogPlaythrough(name) => {
+                self.palette_log_game = Some(name);
+                close = false;
+            },
+            PaletteCommand::EditGame(name) => {
+                if let Ok(index) = search_for_game(&self.game_file_contents, &name) {
+                    self.load_game_for_editing(index);
+                    self.open_window_from_palette(WindowOpened::Editing);
+                }
+            },
+        }
+
+        if close {
+            self.close_palette();
+        }
+    }
+
+    pub fn close_palette(&mut self) {
+        self.palette_open = false;
+        self.palette_query.clear();
+        self.palette_selected = 0;
+        self.palette_log_game = None;
+        self.palette_feedback.clear();
+    }
+
+    /// Ctrl+K box for running commands (and logging playthroughs) by typing
+    pub fn command_palette(&mut self, ctx: &Context) {
+        if ctx.input_mut(|input| input.consume_key(Modifiers::COMMAND, Key::K)) {
+            if self.palette_open {
+                self.close_palette();
+            }
+            else {
+                self.palette_open = true;
+            }
+        }
+
+        if !self.palette_open {
+            return;
+        }
+
+        if ctx.input_mut(|input| input.consume_key(Modifiers::NONE, Key::Escape)) {
+            self.close_palette();
+            return;
+        }
+
+        let mut open = true;
+
+        egui::Window::new("Command Palette")
+            .anchor(Align2::CENTER_TOP, [0.0, 60.0])
+            .collapsible(false)
+            .resizable(false)
+            .open(&mut open)
+            .show(ctx, |ui| {
+                ui.set_width(350.0);
+
+                // Second step of logging a playthrough
+                if let Some(name) = self.palette_log_game.clone() {
+                    ui.label(RichText::new(format!("Log Playthrough: {}", name)).strong());
+
+                    match search_for_game(&self.game_file_contents, &name) {
+                        Ok(index) => match self.playthrough_form(ui, index) {
+                            Some(Ok(_)) => self.close_palette(),
+                            Some(Err(error)) => self.palette_feedback = error,
+                            None => (),
+                        },
+                        Err(_) => self.palette_feedback = String::from("Game Not Found!"),
+                    }
+
+                    if ui.button("Back").clicked() {
+                        self.palette_log_game = None;
+                        self.palette_feedback.clear();
+                    }
+                }
+                else {
+                    let response = ui.add(TextEdit::singleline(&mut self.palette_query)
+                        .hint_text("Type a command or a game's name")
+                        .desired_width(f32::INFINITY));
+                    response.request_focus(); // Straight to typing when it opens
+
+                    let matches = self.palette_matches();
+                    if response.changed() {
+                        self.palette_selected = 0;
+                    }
+
+                    // Arrow keys move through the matches, Enter runs the highlighted one
+                    if ui.input_mut(|input| input.consume_key(Modifiers::NONE, Key::ArrowDown)) {
+                        self.palette_selected = (self.palette_selected + 1).min(matches.len().saturating_sub(1));
+                    }
+                    if ui.input_mut(|input| input.consume_key(Modifiers::NONE, Key::ArrowUp)) {
+                        self.palette_selected = self.palette_selected.saturating_sub(1);
+                    }
+
+                    let mut chosen = None;
+                    if ui.input(|input| input.key_pressed(Key::Enter)) {
+                        chosen = matches.get(self.palette_selected).cloned();
+                    }
+
+                    for (i, command) in matches.iter().enumerate() {
+                        if ui.selectable_label(i == self.palette_selected, command.label()).clicked() {
+                            chosen = Some(command.clone());
+                        }
+                    }
+
+                    if matches.is_empty() {
+                        ui.label(RichText::new("Nothing matches").italics());
+                    }
+
+                    if let Some(command) = chosen {
+                        self.run_palette_command(command);
+                    }
+                }
+
+                if !self.palette_feedback.is_empty() {
+                    ui.label(RichText::new(&self.palette_feedback).color(egui::Color32::RED));
+                }
+            });
+
+        if !open {
+            self.close_palette();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_letters_in_order() {
+        assert_eq!(match_score("sett", "Settings"), Some(0));
+        assert!(match_score("cel", "Log Playthrough: Celeste").unwrap() < match_score("lp cel", "Log Playthrough: Celeste").unwrap());
+        assert_eq!(match_score("xyz", "Log Playthrough: Celeste"), None);
+        assert_eq!(match_score("", "Undo"), Some(0));
+    }
+}
@@ -0,0 +1,342 @@
+// Crates/Imports
+use std::collections::HashMap;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+use std::time::Duration;
+use anyhow::anyhow;
+use chrono::Local;
+use eframe::egui::{self, Color32, ColorImage, Context, CornerRadius, FontId, Rect, RichText, TextEdit, TextureHandle, TextureOptions, Ui, Vec2};
+
+use crate::{app_setup::GameLog, json_file_operations::search_for_game};
+
+pub const COVERS_DIR: &str = "GameLogCovers"; // Thumbnails live here, next to GameLog.Json
+const THUMBNAIL_SIZE: (u32, u32) = (240, 320); // Twice the grid card's cover size so covers stay sharp on high DPI screens
+const MAX_DOWNLOAD_BYTES: u64 = 10 * 1024 * 1024;
+const IMAGE_EXTENSIONS: [&str; 6] = ["png", "jpg", "jpeg", "gif", "bmp", "webp"]; // Files that can be dropped in as covers
+
+// Game names can have anything in them, so cover files are named after a safe version of the name
+fn file_stem(game_name: &str) -> String
+{
+    let stem: String = game_name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '_' })
+        .take(40)
+        .collect();
+
+    // The time keeps names unique, and means a replaced cover never shows a stale texture
+    format!("{}-{}", stem, Local::now().timestamp_millis())
+}
+
+/// Shrink an image (any format the image crate reads) down to a thumbnail and save it as a PNG in `dir`.
+/// Returns the new file's name, which is what gets stored on the game
+pub fn save_thumbnail(bytes: &[u8], dir: &Path, game_name: &str) -> Result<String, anyhow::Error>
+{
+    let image = image::load_from_memory(bytes)?;
+    let thumbnail = image.thumbnail(THUMBNAIL_SIZE.0, THUMBNAIL_SIZE.1);
+
+    fs::create_dir_all(dir)?;
+    let file_name = format!("{}.png", file_stem(game_name));
+    thumbnail.save_with_format(dir.join(&file_name), image::ImageFormat::Png)?;
+
+    Ok(file_name)
+}
+
+/// Make a cover from an image file on disk
+pub fn import_cover_file(path: &Path, game_name: &str) -> Result<String, anyhow::Error>
+{
+    save_thumbnail(&fs::read(path)?, Path::new(COVERS_DIR), game_name)
+}
+
+/// Download a cover (e.g. from a metadata provider's cover URL)
+pub fn download_cover(url: &str, game_name: &str) -> Result<String, anyhow::Error>
+{
+    let response = ureq::AgentBuilder::new().timeout(Duration::from_secs(20)).build()
+        .get(url)
+        .call()
+        .map_err(|error| anyhow!("Couldn't download cover ({})", error))?;
+
+    let mut bytes = Vec::new();
+    response.into_reader().take(MAX_DOWNLOAD_BYTES).read_to_end(&mut bytes)?;
+
+    save_thumbnail(&bytes, Path::new(COVERS_DIR), game_name)
+}
+
+/// Delete a cover that's no longer used (Nothing to do for games without one)
+pub fn remove_cover_file(file_name: &str)
+{
+    if !file_name.is_empty() {
+        let _ = fs::remove_file(Path::new(COVERS_DIR).join(file_name));
+    }
+}
+
+/// Delete cover files in `dir` that none of `in_use` point to. Returns how many were deleted.
+///
+/// Covers stay on disk when a game is removed or given a new one so undo can bring them back, this tidies them up at startup instead
+pub fn remove_unused_covers(dir: &Path, in_use: &[&str]) -> usize
+{
+    let Ok(entries) = fs::read_dir(dir) else { return 0 };
+
+    entries.filter_map(|entry| entry.ok())
+        .filter(|entry| !in_use.contains(&entry.file_name().to_string_lossy().as_ref()))
+        .filter(|entry| fs::remove_file(entry.path()).is_ok())
+        .count()
+}
+
+/// Whether a file looks like an image from its extension (Case doesn't matter)
+fn is_image_file(path: &Path) -> bool
+{
+    path.extension().is_some_and(|extension| IMAGE_EXTENSIONS.iter().any(|image| extension.eq_ignore_ascii_case(image)))
+}
+
+/// Image files dropped onto the picker (only the first one is used). Drops anywhere else in the window are left alone
+fn dropped_file(ui: &Ui, rect: Rect) -> Option<PathBuf>
+{
+    if !ui.rect_contains_pointer(rect) {
+        return None
+    }
+    ui.ctx().input(|input| input.raw.dropped_files.iter().filter_map(|file| file.path.clone()).find(|path| is_image_file(path)))
+}
+
+/// Path box and Load button for giving a game a cover. Image files dropped onto it are used too.
+///
+/// `cover` is set to the new thumbnail's file name. Returns a message if the image couldn't be used
+pub fn cover_picker(ui: &mut Ui, path: &mut String, cover: &mut String, game_name: &str) -> Option<String>
+{
+    let mut chosen = None;
+
+    let picker = ui.horizontal(|ui| {
+        ui.add_sized(Vec2::new(150.0, 20.0), TextEdit::singleline(path).hint_text("Image file (or drop one here)"));
+
+        if ui.button("Load").clicked() && !path.trim().is_empty() {
+            chosen = Some(PathBuf::from(path.trim()));
+        }
+
+        if !cover.is_empty() {
+            ui.label(RichText::new("Cover set").small());
+            if ui.small_button("✖").on_hover_text("Remove cover").clicked() {
+                cover.clear();
+            }
+        }
+    });
+
+    let chosen = chosen.or_else(|| dropped_file(ui, picker.response.rect))?;
+
+    match import_cover_file(&chosen, game_name) {
+        Ok(file_name) => {
+            *cover = file_name;
+            path.clear();
+            None
+        },
+        Err(error) => Some(format!("Couldn't use that image: {}", error)),
+    }
+}
+
+/// Cover textures, loaded the first time they're actually on screen.
+///
+/// Decoding happens on one worker thread so scrolling through a big library never stalls a frame
+#[derive(Default)]
+pub struct CoverCache {
+    textures: HashMap<String, Option<TextureHandle>>, // None while loading, or if the file couldn't be read
+    requests: Option<Sender<String>>,
+    loaded: Option<Receiver<(String, Option<ColorImage>)>>,
+}
+
+impl CoverCache {
+    fn start_worker(&mut self)
+    {
+        let (request_sender, request_receiver) = mpsc::channel::<String>();
+        let (image_sender, image_receiver) = mpsc::channel();
+
+        thread::spawn(move || {
+            for file_name in request_receiver {
+                let image = image::open(Path::new(COVERS_DIR).join(&file_name)).ok().map(|image| {
+                    let rgba = image.to_rgba8();
+                    ColorImage::from_rgba_unmultiplied([rgba.width() as usize, rgba.height() as usize], rgba.as_flat_samples().as_slice())
+                });
+
+                if image_sender.send((file_name, image)).is_err() {
+                    break; // App is closing
+                }
+            }
+        });
+
+        self.requests = Some(request_sender);
+        self.loaded = Some(image_receiver);
+    }
+
+    /// Turn any covers the worker has finished decoding into textures
+    pub fn poll(&mut self, ctx: &Context)
+    {
+        let Some(loaded) = &self.loaded else { return };
+
+        while let Ok((file_name, image)) = loaded.try_recv() {
+            let texture = image.map(|image| ctx.load_texture(&file_name, image, TextureOptions::LINEAR));
+            self.textures.insert(file_name, texture);
+            ctx.request_repaint();
+        }
+    }
+
+    /// The texture for a cover, asking for it to be loaded if this is the first time it's needed
+    pub fn texture(&mut self, file_name: &str) -> Option<&TextureHandle>
+    {
+        if !self.textures.contains_key(file_name) {
+            if self.requests.is_none() {
+                self.start_worker();
+            }
+            if let Some(requests) = &self.requests {
+                let _ = requests.send(file_name.to_string());
+            }
+            self.textures.insert(file_name.to_string(), None);
+        }
+
+        self.textures.get(file_name).and_then(Option::as_ref)
+    }
+}
+
+// Cover GUI Code
+impl GameLog {
+    /// Put finished cover downloads onto their games
+    pub fn poll_cover_downloads(&mut self, ctx: &Context)
+    {
+        self.cover_cache.poll(ctx);
+
+        let Some(downloads) = &self.cover_downloads else { return };
+        let finished: Vec<(String, Result<String, String>)> = downloads.try_iter().collect();
+
+        for (game_name, result) in finished {
+            match (result, search_for_game(&self.game_file_contents, &game_name)) {
+                (Ok(file_name), Ok(i)) => {
+                    let saved = self.apply_change(&format!("Cover for {}", game_name), |game_log| {
+                        game_log.game_file_contents[i].cover = file_name.clone();
+                        Ok(())
+                    });
+
+                    // Keep the edit window in step so confirming other edits doesn't put the old cover back
+                    if saved.is_ok() && self.editing_selected_index == i {
+                        self.edit_game_cover = file_name;
+                    }
+                    else if let Err(error) = saved {
+                        println!("{}", error);
+                    }
+                },
+                (Ok(file_name), Err(_)) => remove_cover_file(&file_name), // Game was removed while downloading
+                (Err(error), Ok(i)) if self.editing_selected_index == i => {
+                    self.editing_feedback_message = error;
+                    self.error_confirmation = true;
+                },
+                (Err(error), _) => println!("{}: {}", game_name, error),
+            }
+        }
+    }
+
+    // Run a cover job off the GUI thread. Whatever cover file it makes is put on the game once it's done
+    fn spawn_cover_job(&mut self, ctx: &Context, game_name: &str, job: impl FnOnce(&str) -> Result<String, anyhow::Error> + Send + 'static)
+    {
+        if self.cover_downloads.is_none() {
+            let (sender, receiver) = mpsc::channel();
+            self.cover_download_sender = Some(sender);
+            self.cover_downloads = Some(receiver);
+        }
+
+        let Some(sender) = self.cover_download_sender.clone() else { return };
+        let game_name = game_name.to_string();
+        let ctx = ctx.clone();
+
+        thread::spawn(move || {
+            let result = job(&game_name).map_err(|error| error.to_string());
+            let _ = sender.send((game_name, result));
+            ctx.request_repaint(); // Wake the GUI up to pick the cover up
+        });
+    }
+
+    /// Download a cover from a known URL in the background
+    pub fn fetch_cover(&mut self, ctx: &Context, game_name: &str, url: &str)
+    {
+        let url = url.to_string();
+        self.spawn_cover_job(ctx, game_name, move |game_name| download_cover(&url, game_name));
+    }
+
+    /// Look a game up with the metadata provider and download the first cover found
+    pub fn look_up_cover(&mut self, ctx: &Context, game_name: &str)
+    {
+        let Some(provider) = self.metadata_provider.clone() else { return };
+
+        self.spawn_cover_job(ctx, game_name, move |game_name| {
+            let url = provider.search(game_name)?.into_iter()
+                .find_map(|metadata| metadata.cover_url)
+                .ok_or_else(|| anyhow!("No cover found"))?;
+            download_cover(&url, game_name)
+        });
+    }
+
+    /// Draw a game's cover into `rect`. Games without one (or whose cover is still loading) get a plain tile with their name
+    pub fn paint_cover(&mut self, ui: &Ui, rect: egui::Rect, name: &str, cover: &str)
+    {
+        // Textures are only asked for once a cover is on screen, which is what keeps big libraries quick to open
+        if !ui.is_rect_visible(rect) {
+            return;
+        }
+
+        let painter = ui.painter_at(rect);
+        let texture = if cover.is_empty() { None } else { self.cover_cache.texture(cover) };
+
+        match texture {
+            Some(texture) => {
+                let uv = egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0));
+                painter.image(texture.id(), rect, uv, Color32::WHITE);
+            },
+            None => {
+                painter.rect_filled(rect, CornerRadius::same(6), ui.visuals().faint_bg_color);
+
+                let galley = painter.layout(name.to_string(), FontId::proportional(14.0), ui.visuals().text_color(), rect.width() - 10.0);
+                painter.galley(rect.center() - galley.size() / 2.0, galley, ui.visuals().text_color());
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn saves_covers_as_small_pngs() {
+        let dir = std::env::temp_dir().join(format!("gamelog_covers_{}", std::process::id()));
+
+        let mut bytes = Vec::new();
+        image::RgbImage::new(1200, 1600).write_to(&mut std::io::Cursor::new(&mut bytes), image::ImageFormat::Jpeg).unwrap();
+
+        let file_name = save_thumbnail(&bytes, &dir, "Half-Life 2: Episode One").unwrap();
+        let saved = image::open(dir.join(&file_name)).unwrap();
+
+        assert!(file_name.starts_with("half_life_2__episode_one-") && file_name.ends_with(".png"));
+        assert_eq!((saved.width(), saved.height()), THUMBNAIL_SIZE); // Same shape, so it fills the bounds exactly
+        assert!(save_thumbnail(b"not an image", &dir, "Broken").is_err());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn only_image_files_can_be_dropped() {
+        assert!(is_image_file(Path::new("/home/me/Pictures/celeste.PNG")));
+        assert!(is_image_file(Path::new("box art.webp")));
+        assert!(!is_image_file(Path::new("GameLog.Json")));
+        assert!(!is_image_file(Path::new("jpg")));
+    }
+
+    #[test]
+    fn only_unused_covers_are_removed() {
+        let dir = std::env::temp_dir().join(format!("gamelog_unused_covers_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        for file_name in ["kept.png", "trashed.png", "old.png"] {
+            fs::write(dir.join(file_name), b"").unwrap();
+        }
+
+        assert_eq!(remove_unused_covers(&dir, &["kept.png", "trashed.png", ""]), 1);
+        assert!(dir.join("kept.png").exists() && !dir.join("old.png").exists());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -1,16 +1,131 @@
 // Crates/Imports
-use eframe::egui::{Align, Button, Checkbox, Color32, Direction, Label, Layout, RichText, TextEdit, Vec2};
+use std::time::Duration;
+use eframe::egui::{Align, Button, Color32, Direction, DragValue, Grid, Label, Layout, RichText, TextEdit, Vec2};
 
-use crate::{app_setup::GameLog, clock::get_date, egui::Ui, enums::Rating, json_file_operations::{save_to_file, search_for_game, Game}};
+use crate::{app_setup::GameLog, clock::today, enums::{EditingTab, RatingScale}, covers::cover_picker, egui::Ui, json_file_operations::{search_for_game, Game, Playthrough}, notes::{markdown_view, notes_editor, redact_spoilers, shorten, SPOILER_REDACTED}, platforms::platform_picker, playtime::format_duration, process_watcher::executable_editor, star_rating::star_rating_input, status::status_picker, tags::{all_tags, tag_editor}, validation::{field_error_label, validate, FieldError, GameDraft, GameField, MAX_NAME_LENGTH}};
+
+const SUMMARY_TEXT_LENGTH: usize = 30; // Notes etc. are cut short in the edit summary
+const NOTES_BOX_WIDTH: f32 = 250.0;
+
+/// One field an edit changes, as shown before confirming it
+#[derive(Debug, PartialEq)]
+pub struct FieldChange {
+    pub label: &'static str,
+    pub old: String,
+    pub new: String,
+    pub automatic: bool, // Changed by the edit itself rather than anything typed (The last played date, when runs are added or taken off)
+}
+
+// Cut a value down for the summary
+fn summary_text(text: &str) -> String
+{
+    if text.is_empty() {
+        String::from("None")
+    }
+    else {
+        shorten(text, SUMMARY_TEXT_LENGTH)
+    }
+}
+
+/// What an edit changes, and the labels of the fields it leaves alone
+pub fn edit_changes(old: &Game, new: &Game, rating_scale: RatingScale, today: &str) -> (Vec<FieldChange>, Vec<&'static str>)
+{
+    let date = |date: &str| if date == today { String::from("today") } else { summary_text(date) };
+    let status = |game: &Game| game.status.map(|status| status.to_string()).unwrap_or_else(|| String::from("None"));
+
+    // Whether it changed is worked out before shortening, so a change past the first line of the notes still counts
+    let fields = [
+        ("Name", old.name != new.name, summary_text(&old.name), summary_text(&new.name), false),
+        ("Rating", old.rating != new.rating, rating_scale.display(old.rating), rating_scale.display(new.rating), false),
+        ("Status", old.status != new.status, status(old), status(new), false),
+        ("Times played", old.times_played != new.times_played, old.times_played.to_string(), new.times_played.to_string(), false),
+        ("Last played", old.last_playthrough != new.last_playthrough, date(&old.last_playthrough), date(&new.last_playthrough), new.times_played != old.times_played),
+        ("Play time", old.play_time != new.play_time, format_duration(old.play_time), format_duration(new.play_time), false),
+        ("Platform", old.platform != new.platform, summary_text(&old.platform), summary_text(&new.platform), false),
+        ("Storefront", old.storefront != new.storefront, summary_text(&old.storefront), summary_text(&new.storefront), false),
+        ("Tags", old.tags != new.tags, summary_text(&old.tags.join(", ")), summary_text(&new.tags.join(", ")), false),
+        ("Notes", old.notes != new.notes, summary_text(&redact_spoilers(&old.notes, SPOILER_REDACTED)), summary_text(&redact_spoilers(&new.notes, SPOILER_REDACTED)), false),
+        ("Executables", old.executables != new.executables, summary_text(&old.executables.join(", ")), summary_text(&new.executables.join(", ")), false),
+        ("Cover", old.cover != new.cover, summary_text(&old.cover), summary_text(&new.cover), false),
+        ("Playthroughs", old.playthroughs != new.playthroughs, old.playthroughs.len().to_string(), new.playthroughs.len().to_string(), false),
+    ];
+
+    let mut changes = Vec::new();
+    let mut unchanged = Vec::new();
+
+    for (label, changed, old_text, new_text, automatic) in fields {
+        if !changed {
+            unchanged.push(label);
+        }
+        else {
+            changes.push(FieldChange { label, old: old_text, new: new_text, automatic });
+        }
+    }
+
+    (changes, unchanged)
+}
+
+/// Add runs to a game being edited, or take them off with a negative `delta` (Never below none).
+///
+/// Each added run is a playthrough today on the game's (possibly new) platform, and makes today the last time it was played.
+/// Taking runs off only drops playthroughs once there are more of them than runs (Older runs may not have one), and puts
+/// the date back to the latest playthrough that's left (Or leaves it alone if there are none)
+fn change_runs(draft: &mut GameDraft, delta: i64, today: &str)
+{
+    let times_played = (draft.times_played as i64 + delta).clamp(0, u32::MAX as i64) as u32;
+
+    if times_played < draft.times_played {
+        draft.playthroughs.truncate(draft.playthroughs.len().min(times_played as usize));
+
+        if let Some(latest) = draft.playthroughs.last() {
+            draft.last_playthrough = latest.date.clone();
+        }
+    }
+
+    if times_played > draft.times_played {
+        draft.last_playthrough = today.to_string();
+
+        for _ in draft.times_played..times_played {
+            draft.playthroughs.push(Playthrough {
+                date: today.to_string(),
+                platform: draft.platform.clone(),
+                storefront: draft.storefront.clone(),
+                play_time: Duration::ZERO,
+                note: String::new(),
+            });
+        }
+    }
+
+    draft.times_played = times_played;
+}
 
 // 'Editing' Window GUI Code
 impl GameLog {
+    /// Fill the edit window in with a game's current details, as if it had been searched for
+    pub fn load_game_for_editing(&mut self, index: usize) {
+        self.editing_search_game_name = self.game_file_contents[index].name.clone();
+        self.editing_search_feedback = String::from("Game Found!");
+        self.editing_search_error_confirmation = false;
+        self.editing_selected_index = index;
+        self.edit_game_tags = self.game_file_contents[index].tags.clone(); // Tags are edited as a whole list, so start from the current ones
+        self.edit_tag_input.clear();
+        self.edit_game_platform = self.game_file_contents[index].platform.clone();
+        self.edit_game_storefront = self.game_file_contents[index].storefront.clone();
+        self.edit_game_status = self.game_file_contents[index].status;
+        self.edit_game_executables = self.game_file_contents[index].executables.clone();
+        self.edit_game_cover = self.game_file_contents[index].cover.clone();
+        self.edit_cover_path.clear();
+        self.edit_executable_input.clear();
+        self.increment_times_played = 0; // Counted from this game's times played, so don't carry it over to another
+    }
+
     pub fn editing_gui (&mut self, ui: &mut Ui) 
     {
         let label_size= Vec2::new(ui.available_width(), 20.0); // IMPORTANT: Biggest Element needs to be the same width as the ui if centering vertically to ensure it's actually centered when resizing
         let input_box_size = Vec2::new(150.0, 20.0);
         let button_size = Vec2::new(100.0, 20.0);
         let increment_button_size = Vec2::new(30.0, 20.0);
+        let known_tags = all_tags(&self.game_file_contents); // For autocompleting tags
 
         ui.add_space(5.0);
 
@@ -26,7 +141,7 @@ impl GameLog {
             // Search Box
             ui.add_sized(input_box_size, TextEdit::singleline(&mut self.editing_search_game_name)
                     .hint_text("Game Name (< 50 Char)")
-                    .char_limit(50)
+                    .char_limit(MAX_NAME_LENGTH)
                     .horizontal_align(Align::Center)
                     .desired_width(input_box_size.x)
             );
@@ -39,7 +154,7 @@ impl GameLog {
                 ui.add_sized(label_size, Label::new(RichText::new(&self.editing_search_feedback)
                     .size(20.0)
                     .color(
-                        if self.editing_search_error_confirmation == true {
+                        if self.editing_search_error_confirmation {
                             Color32::RED
                         }
                         else if self.dark_mode { // Better Color For Dark Mode
@@ -61,13 +176,9 @@ impl GameLog {
 
                 match search_for_game(&self.game_file_contents, &self.editing_search_game_name) // Make sure game isn't already in the log (Remind users who may have forgot)
                     {
-                        Ok(index) =>  {
-                            self.editing_search_feedback = format!("Game Found!");
-                            self.editing_search_error_confirmation = false;
-                            self.editing_selected_index = index
-                        },
+                        Ok(index) => self.load_game_for_editing(index),
                         Err(_) => { 
-                            self.editing_search_feedback = format!("Game Not Found!");
+                            self.editing_search_feedback = String::from("Game Not Found!");
                         }
                     };
             }
@@ -76,9 +187,24 @@ impl GameLog {
             // Once a game is found, Expand the window and add options for editing game information
             // This also allows us to hide these assets should the user search for an invalid game after, as well as seamlessly updating the current information
             if !self.editing_search_error_confirmation {
-                ui.add_space(20.0);
+                ui.add_space(10.0);
+
+                ui.horizontal(|ui| {
+                    ui.selectable_value(&mut self.editing_tab, EditingTab::Details, "Details");
+                    ui.selectable_value(&mut self.editing_tab, EditingTab::History, "History");
+                });
+            }
+
+            if !self.editing_search_error_confirmation && self.editing_tab == EditingTab::History {
+                ui.add_space(10.0);
+                self.game_history_gui(ui, self.editing_selected_index);
+            }
+            else if !self.editing_search_error_confirmation {
+                ui.add_space(10.0);
 
                 let game: &mut Game = &mut self.game_file_contents[self.editing_selected_index];
+                let mut look_up_cover = None; // Started once we're done borrowing the game
+                let mut confirmed_edit = None; // Same here, saved through apply_change once the game isn't borrowed
 
                 let mut container_width = 50.0 + 50.0 + 10.0 + input_box_size.x + 10.0; // Width to hold elements being held in a horizontal container (Updates with each Layout to match the new space needed)
 
@@ -103,13 +229,14 @@ impl GameLog {
                             ui.add_sized(input_box_size,
                                 TextEdit::singleline(&mut self.edit_game_name)
                                     .hint_text("New Game Name (< 50 Char)")
-                                    .char_limit(50)
+                                    .char_limit(MAX_NAME_LENGTH)
                             );
 
                             ui.add_space(10.0);
                         });
                 });
-                
+                field_error_label(ui, &self.editing_field_errors, GameField::Name);
+
                 ui.add_space(5.0);
 
                 // RATING
@@ -124,48 +251,188 @@ impl GameLog {
                         );
 
                         ui.add_sized(Vec2::new(50.0, 20.0),
-                            Label::new(RichText::new(&game.rating.to_string())
+                            Label::new(RichText::new(self.settings.rating_scale.display(game.rating))
                             .strong())
                         );
 
                         ui.add_space(10.0);
 
-                        ui.add_sized(input_box_size,
-                            TextEdit::singleline(&mut self.edit_game_rating)
-                                .hint_text("Number between 1-5")
-                                .char_limit(1)
-                        );
+                        star_rating_input(ui, &mut self.edit_game_rating, self.settings.rating_scale);
                         ui.add_space(10.0);
                     });
+                field_error_label(ui, &self.editing_field_errors, GameField::Rating);
 
                 ui.add_space(5.0);
 
                 // NOTES
-                container_width = 50.0 + 50.0 +  10.0 + input_box_size.x + 10.0;
+                container_width = 50.0 + 50.0 +  10.0 + NOTES_BOX_WIDTH + 10.0;
+
+                let mut load_notes = false;
 
                 ui.allocate_ui_with_layout(
                     Vec2::new(container_width, 20.0),
-                    Layout::left_to_right(Align::Center),
+                    Layout::left_to_right(Align::Min),
                     |ui| { 
+                        ui.vertical(|ui| {
+                            ui.add_sized(Vec2::new(50.0, 20.0),
+                                Label::new(RichText::new("Current Notes: "))
+                            );
+
+                            // Only the start of the notes fits here, hovering shows all of them
+                            let notes = shorten(&redact_spoilers(&game.notes, SPOILER_REDACTED), 10);
+
+                            ui.add_sized(Vec2::new(50.0, 20.0),
+                                Label::new(RichText::new(notes)
+                                .strong())
+                            ).on_hover_ui(|ui| markdown_view(ui, &game.notes));
+
+                            // The box starts empty (which keeps the old notes), so this is how to change them rather than start over
+                            load_notes = !game.notes.is_empty() && ui.button("Edit These").clicked();
+                        });
+
+                        ui.add_space(10.0);
+
+                        notes_editor(ui, &mut self.edit_game_notes, &mut self.edit_notes_preview, "Enter your thoughts", NOTES_BOX_WIDTH);
+
+                        ui.add_space(10.0);
+                });
+
+                if load_notes {
+                    self.edit_game_notes = game.notes.clone();
+                }
+
+                ui.add_space(5.0);
+
+                // PLATFORM & STOREFRONT
+                container_width = 50.0 + 50.0 + 10.0 + input_box_size.x + 10.0;
+
+                ui.allocate_ui_with_layout(
+                    Vec2::new(container_width, 20.0),
+                    Layout::left_to_right(Align::Center),
+                    |ui| {
+                        ui.add_sized(Vec2::new(50.0, 20.0),
+                            Label::new(RichText::new("Platform: "))
+                        );
+
+                        ui.add_space(10.0);
+
+                        platform_picker(ui, "edit_game_platform", &mut self.edit_game_platform, &self.settings.platforms);
+
+                        ui.add_space(10.0);
+
+                        ui.add_sized(Vec2::new(100.0, 20.0),
+                            TextEdit::singleline(&mut self.edit_game_storefront)
+                                .hint_text("Storefront")
+                                .char_limit(30));
+                });
+
+                ui.add_space(5.0);
+
+                // STATUS
+                container_width = 50.0 + 10.0 + input_box_size.x;
+
+                ui.allocate_ui_with_layout(
+                    Vec2::new(container_width, 20.0),
+                    Layout::left_to_right(Align::Center),
+                    |ui| {
                         ui.add_sized(Vec2::new(50.0, 20.0),
-                            Label::new(RichText::new("Current Notes: "))
+                            Label::new(RichText::new("Status: "))
                         );
 
-                        // Truncating Long Game Notes here, people can just check the notes themselves manually if they want to see it all
-                        let notes = Self::truncate_game_attributes(&game.notes, 10);
-                        
+                        ui.add_space(10.0);
+
+                        status_picker(ui, "edit_game_status", &mut self.edit_game_status);
+                });
+
+                ui.add_space(5.0);
+
+                // TAGS
+                container_width = 50.0 + 10.0 + input_box_size.x + 60.0;
+
+                ui.allocate_ui_with_layout(
+                    Vec2::new(container_width, 20.0),
+                    Layout::left_to_right(Align::Min),
+                    |ui| {
                         ui.add_sized(Vec2::new(50.0, 20.0),
-                            Label::new(RichText::new(format!("{}", notes))
-                            .strong())
+                            Label::new(RichText::new("Tags: "))
+                        );
+
+                        ui.add_space(10.0);
+
+                        tag_editor(ui, &mut self.edit_game_tags, &mut self.edit_tag_input, &known_tags);
+                });
+                field_error_label(ui, &self.editing_field_errors, GameField::Tags);
+
+                ui.add_space(5.0);
+
+                // COVER
+                container_width = 50.0 + 10.0 + input_box_size.x + 160.0;
+
+                ui.allocate_ui_with_layout(
+                    Vec2::new(container_width, 20.0),
+                    Layout::left_to_right(Align::Min),
+                    |ui| {
+                        ui.add_sized(Vec2::new(50.0, 20.0),
+                            Label::new(RichText::new("Cover: "))
+                        );
+
+                        ui.add_space(10.0);
+
+                        let game_name = game.name.clone();
+
+                        if let Some(error) = cover_picker(ui, &mut self.edit_cover_path, &mut self.edit_game_cover, &game_name) {
+                            self.editing_feedback_message = error;
+                            self.error_confirmation = true;
+                        }
+
+                        if self.metadata_provider.is_some() && ui.button("Look Up").on_hover_text("Download a cover using the game details lookup").clicked() {
+                            look_up_cover = Some(game_name);
+                        }
+                });
+
+                ui.add_space(5.0);
+
+                // EXECUTABLES (For detecting play time automatically)
+                container_width = 50.0 + 10.0 + input_box_size.x + 60.0;
+
+                ui.allocate_ui_with_layout(
+                    Vec2::new(container_width, 20.0),
+                    Layout::left_to_right(Align::Min),
+                    |ui| {
+                        ui.add_sized(Vec2::new(50.0, 20.0),
+                            Label::new(RichText::new("Executables: "))
                         );
 
                         ui.add_space(10.0);
 
-                        ui.add_sized(input_box_size,
-                            TextEdit::singleline(&mut self.edit_game_notes)
-                                .hint_text("Enter your thoughts"));
+                        executable_editor(ui, &mut self.edit_game_executables, &mut self.edit_executable_input, &self.running_processes);
+                });
+
+                ui.add_space(5.0);
+
+                // PLAY TIME
+                container_width = 50.0 + 50.0 + 10.0 + input_box_size.x + 10.0;
+
+                ui.allocate_ui_with_layout(
+                    Vec2::new(container_width, 20.0),
+                    Layout::left_to_right(Align::Center),
+                    |ui| {
+                        ui.add_sized(Vec2::new(50.0, 20.0),
+                            Label::new(RichText::new("Play Time: "))
+                        );
+
+                        ui.add_sized(Vec2::new(50.0, 20.0),
+                            Label::new(RichText::new(format_duration(game.play_time))
+                            .strong())
+                        );
 
                         ui.add_space(10.0);
+
+                        // Time entered here is added on top of what's already logged, or taken off to correct it
+                        ui.selectable_value(&mut self.edit_play_time_taken_off, false, "Add");
+                        ui.selectable_value(&mut self.edit_play_time_taken_off, true, "Take Off");
+                        ui.add(DragValue::new(&mut self.edit_play_time_hours).range(0..=10_000).suffix("h"));
+                        ui.add(DragValue::new(&mut self.edit_play_time_minutes).range(0..=59).suffix("m"));
                 });
 
                 ui.add_space(20.0);
@@ -180,24 +447,26 @@ impl GameLog {
 
                         ui.horizontal(|ui| {
                             ui.add_sized(Vec2::new(50.0, 20.0),
-                                Label::new(RichText::new("Increment Times Played: "))
+                                Label::new(RichText::new("Times Played: "))
                             );
 
                             ui.add_space(10.0);
 
+                            // The new count can be typed in, or nudged either way (So a mistaken +1 can be taken back)
+                            let current = self.game_file_contents[self.editing_selected_index].times_played as i64;
+                            let mut times_played = current + self.increment_times_played;
+
                             if ui
                                 .add_sized(increment_button_size, Button::new("-").min_size(increment_button_size))
                                 .clicked()
                             {
-                                self.increment_times_played = self.increment_times_played.saturating_sub(1); // Saturating_Sub makes it so the number cant go below 0
+                                times_played -= 1;
                             }
 
                             ui.add_space(10.0);
 
-                            ui.add_sized(
-                                Vec2::new(20.0, 20.0),
-                                Label::new(format!("{}", self.increment_times_played)),
-                            );
+                            ui.add_sized(Vec2::new(50.0, 20.0), DragValue::new(&mut times_played).range(0..=u32::MAX as i64))
+                                .on_hover_text(format!("Currently {}", current));
 
                             ui.add_space(10.0);
 
@@ -205,9 +474,11 @@ impl GameLog {
                                 .add_sized(increment_button_size, Button::new("+").min_size(increment_button_size))
                                 .clicked()
                                 {
-                                self.increment_times_played += 1;
+                                times_played += 1;
                             }
 
+                            self.increment_times_played = times_played.clamp(0, u32::MAX as i64) - current; // Can't go below 0 or past what can be stored
+
                             ui.add_space(input_box_size.x); // This will allow the general container to be the same approximate size as the other property changer rows. This allows us to put the Title and incrementor somewhat in line with the rest so it looks aesthetically pleasing. Obviously not exact but still looks good
                         });
                 });
@@ -233,93 +504,226 @@ impl GameLog {
                     );
                 }
 
-                // Confirma Edit
-                container_width = 30.0 + button_size.x + input_box_size.x;
+                // What Confirm will change, so it can be checked before saving (Replaces the old "Are you sure" checkbox)
+                let index = self.editing_selected_index;
+                let today = today().to_string();
+                let entered = self.edit_entered(index);
+                let edited = self.edited_game(index, &today);
 
-                ui.allocate_ui_with_layout(
-                    Vec2::new(container_width, 20.0),
-                    Layout::left_to_right(Align::Center),
-                    |ui| {
+                if !entered {
+                    ui.label(RichText::new("Nothing to save yet").italics());
+                }
+                else if let Ok(edited_game) = &edited {
+                    self.edit_summary(ui, &self.game_file_contents[index], edited_game, &today);
+                }
+                else {
+                    ui.label(RichText::new("Some boxes aren't valid yet").italics());
+                }
 
-                            ui.add_sized(Vec2::new(20.0, 20.0), Checkbox::new(&mut self.checked, "Are you sure you want to commit these edits?"));
-
-                            self.enabled = self.checked; // Again same variable, just different for readability
-
-                            ui.add_space(20.0);
-
-                            ui.add_enabled_ui(self.enabled, |ui| {
-                               if ui.add_sized(button_size, Button::new("Confirm"))
-                                    .clicked() {
-                                        self.error_confirmation = true; // Reset Error Colour (so i dont have to set it for every possible error message and only for the success message)
-
-                                        let game: &mut Game = &mut self.game_file_contents[self.editing_selected_index];
-
-                                        if self.edit_game_name.is_empty() && self.edit_game_rating.is_empty() && self.edit_game_notes.is_empty() && (self.increment_times_played == 0) {
-                                            self.editing_feedback_message = format!("Please Enter an Edit");
-                                        }
-                                        else {
-                                            if !self.edit_game_name.is_empty() {
-                                                game.name = self.edit_game_name.clone();
-                                            }
-
-                                            if !self.edit_game_rating.is_empty() {
-                                                match self.edit_game_rating.trim().parse::<u8>()
-                                                {
-                                                    Ok(parsed) => 
-                                                        if let Some(parsed_rating) = Rating::from_u8(parsed) {
-                                                            game.rating = parsed_rating;
-                                                            parsed_rating
-                                                        }
-                                                        else{ 
-                                                            println!("Invalid Rating (INVALID NUMBER)"); // Just printing these to the terminal. Users will be able to visually see their invalid ratings havent been added
-                                                            game.rating // Just use current game rating (Unchanged)
-                                                        },
-                                                    Err(_) => {
-                                                        println!("Invalid Rating (ENTER A NUMBER)"); 
-                                                        game.rating
-                                                    }
-                                                };
-                                            }
-
-                                            if !self.edit_game_notes.is_empty() { // Save Notes
-                                                game.notes = self.edit_game_notes.clone();
-                                            }
-
-                                            
-                                            game.last_playthrough = get_date().to_string(); // Get current date to save for last playthrough
-
-
-                                            game.times_played = self.increment_times_played + game.times_played; // Save New times played
-
-                                            // Save Edits
-                                            self.editing_feedback_message = match save_to_file(&mut self.game_file_contents){
-                                                Ok(_) => { 
-                                                    self.error_confirmation = false;
-                                                    format!("Edits Added")
-                                                },
-                                                Err(_) => format!("Error Saving to File"),
-                                            };
-                                        }
-                                    }
-                            });
-                            ui.add_space(input_box_size.x); // This will allow the general container to be the same approximate size as the other property changer rows. This allows us to put the Title and incrementor somewhat in line with the rest so it looks aesthetically pleasing. Obviously not exact but still looks good
-                    },
-                );
+                ui.add_space(10.0);
+
+                // Confirm Edit
+                ui.add_enabled_ui(entered, |ui| {
+                    if ui.add_sized(button_size, Button::new("Confirm")).clicked() {
+                        self.error_confirmation = true; // Reset Error Colour (so i dont have to set it for every possible error message and only for the success message)
+                        self.editing_field_errors.clear();
+
+                        match edited {
+                            Ok(edited_game) => confirmed_edit = Some(edited_game),
+                            Err(errors) => {
+                                // Nothing is changed until every box is valid (Previously a bad rating was quietly ignored)
+                                self.editing_feedback_message = String::from("Some boxes need fixing");
+                                self.editing_field_errors = errors;
+                            }
+                        }
+                    }
+                });
+
+                if let Some(game_name) = look_up_cover {
+                    self.look_up_cover(ui.ctx(), &game_name);
+                }
+
+                if let Some(edited_game) = confirmed_edit {
+                    let index = self.editing_selected_index;
+                    let description = format!("Edit {}", self.game_file_contents[index].name);
+
+                    // Follow renames, otherwise the edit window would lose track of the game
+                    let previous_name = std::mem::replace(&mut self.editing_search_game_name, edited_game.name.clone());
+
+                    // Save Edits
+                    self.editing_feedback_message = match self.apply_change(&description, |game_log| {
+                        game_log.game_file_contents[index] = edited_game;
+                        Ok(())
+                    }) {
+                        Ok(_) => {
+                            self.error_confirmation = false;
+                            self.edit_play_time_hours = 0; // Already added, so don't add it again on the next confirm
+                            self.edit_play_time_minutes = 0;
+                            self.edit_play_time_taken_off = false;
+                            self.increment_times_played = 0;
+                            String::from("Edits Added")
+                        },
+                        Err(error) => {
+                            self.editing_search_game_name = previous_name;
+                            error
+                        },
+                    };
+                }
             };
         });
     }
 
-    // Make long strings that will be displayed smaller with an elipsis at the end
-    fn truncate_game_attributes (attribute: &str, desired_length: usize) -> String
-    { 
-        let mut attribute_string = attribute.to_string();
+    // Whether anything has been typed or picked that would change the game
+    fn edit_entered(&self, index: usize) -> bool {
+        let game = &self.game_file_contents[index];
+
+        !(self.edit_game_name.is_empty() && self.edit_game_rating.is_empty() && self.edit_game_notes.is_empty() && (self.increment_times_played == 0) && (self.edit_game_tags == game.tags)
+            && (self.edit_game_platform == game.platform) && (self.edit_game_storefront == game.storefront) && (self.edit_game_status == game.status) && (self.edit_game_cover == game.cover)
+            && (self.edit_play_time_hours == 0) && (self.edit_play_time_minutes == 0) && (self.edit_game_executables == game.executables))
+    }
+
+    // The game as Confirm would save it. Any box left empty keeps its old value
+    fn edited_game(&self, index: usize, today: &str) -> Result<Game, Vec<FieldError>> {
+        let mut draft = GameDraft::from_game(&self.game_file_contents[index]);
+
+        if !self.edit_game_name.is_empty() {
+            draft.name = self.edit_game_name.clone();
+        }
+
+        if !self.edit_game_rating.is_empty() {
+            draft.rating = self.edit_game_rating.clone();
+            draft.rating_scale = self.settings.rating_scale; // Typed in on the user's scale, unlike the kept rating
+        }
+
+        if !self.edit_game_notes.is_empty() { // Save Notes
+            draft.notes = self.edit_game_notes.clone();
+        }
+
+        draft.tags = self.edit_game_tags.clone();
+        draft.platform = self.edit_game_platform.clone();
+        draft.storefront = self.edit_game_storefront.clone();
+        draft.executables = self.edit_game_executables.clone();
+        draft.status = self.edit_game_status;
+        draft.cover = self.edit_game_cover.clone();
+
+        change_runs(&mut draft, self.increment_times_played, today);
+
+        let entered_time = Duration::from_secs(self.edit_play_time_hours as u64 * 3600 + self.edit_play_time_minutes as u64 * 60);
+        draft.play_time = if self.edit_play_time_taken_off { draft.play_time.saturating_sub(entered_time) } else { draft.play_time + entered_time };
+
+        validate(&draft)
+    }
+
+    // "Rating 4 → 5" style list of what Confirm will change, with the automatic ones and the untouched ones marked
+    fn edit_summary(&self, ui: &mut Ui, game: &Game, edited_game: &Game, today: &str) {
+        let (changes, unchanged) = edit_changes(game, edited_game, self.settings.rating_scale, today);
+
+        ui.label(RichText::new("Confirming will change:").strong());
 
-        if attribute.len() > desired_length {
+        Grid::new("edit_summary").num_columns(2).show(ui, |ui| {
+            for change in &changes {
+                ui.label(change.label);
+                ui.horizontal(|ui| {
+                    ui.label(format!("{} → {}", change.old, change.new));
+                    if change.automatic {
+                        ui.label(RichText::new("(automatic)").weak()).on_hover_text("Set because runs were added or taken off");
+                    }
+                });
+                ui.end_row();
+            }
+        });
 
-            attribute_string.truncate(desired_length);
-            return attribute_string + "..."
+        if !unchanged.is_empty() {
+            ui.label(RichText::new(format!("Unchanged: {}", unchanged.join(", "))).weak());
         }
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn game(points: u8, times_played: u32, last_playthrough: &str) -> Game {
+        serde_json::from_str(&format!(r#"{{"name": "Celeste", "rating": {}, "times_played": {}, "last_playthrough": "{}", "notes": ""}}"#, points, times_played, last_playthrough)).unwrap()
+    }
+
+    #[test]
+    fn summary_lists_changes_and_marks_the_date() {
+        let (changes, unchanged) = edit_changes(&game(80, 5, "2025-07-14"), &game(100, 6, "2025-08-01"), RatingScale::FiveStars, "2025-08-01");
+
+        let labels: Vec<&str> = changes.iter().map(|change| change.label).collect();
+        assert_eq!(labels, ["Rating", "Times played", "Last played"]);
+        assert_eq!((changes[1].old.as_str(), changes[1].new.as_str()), ("5", "6"));
+        assert_eq!(changes[2], FieldChange { label: "Last played", old: String::from("2025-07-14"), new: String::from("today"), automatic: true });
+        assert!(unchanged.contains(&"Name") && unchanged.contains(&"Notes"));
+
+        // Not something the edit did if the runs didn't change
+        let (changes, _) = edit_changes(&game(80, 5, "2025-07-14"), &game(80, 5, "2025-08-01"), RatingScale::FiveStars, "2025-08-01");
+        assert!(!changes[0].automatic);
+    }
+
+    #[test]
+    fn runs_can_be_added_and_taken_off() {
+        let mut celeste = game(80, 2, "2025-07-14");
+        celeste.platform = String::from("PC");
+        celeste.playthroughs = serde_json::from_str(r#"[{"date": "2025-01-01"}, {"date": "2025-07-14"}]"#).unwrap();
+
+        // Adding runs records them today
+        let mut draft = GameDraft::from_game(&celeste);
+        change_runs(&mut draft, 2, "2025-08-01");
+        assert_eq!(draft.times_played, 4);
+        assert_eq!(draft.last_playthrough, "2025-08-01");
+        assert_eq!(draft.playthroughs.iter().map(|playthrough| playthrough.date.as_str()).collect::<Vec<&str>>(), ["2025-01-01", "2025-07-14", "2025-08-01", "2025-08-01"]);
+        assert_eq!(draft.playthroughs[3].platform, "PC");
+
+        // Taking one off drops the latest run, and the date goes back to the run before it
+        let mut draft = GameDraft::from_game(&celeste);
+        change_runs(&mut draft, -1, "2025-08-01");
+        assert_eq!(draft.times_played, 1);
+        assert_eq!(draft.last_playthrough, "2025-01-01");
+        assert_eq!(draft.playthroughs.len(), 1);
+
+        // Runs from before playthroughs were recorded go first, so the recorded ones are kept
+        let mut replayed = celeste.clone();
+        replayed.times_played = 5;
+        let mut draft = GameDraft::from_game(&replayed);
+        change_runs(&mut draft, -1, "2025-08-01");
+        assert_eq!((draft.times_played, draft.playthroughs.len()), (4, 2));
+        assert_eq!(draft.last_playthrough, "2025-07-14");
+
+        // Counts past what a u8 could hold
+        let mut roguelike = game(80, 300, "2025-07-14");
+        roguelike.playthroughs = celeste.playthroughs.clone();
+        let mut draft = GameDraft::from_game(&roguelike);
+        change_runs(&mut draft, -1, "2025-08-01");
+        assert_eq!((draft.times_played, draft.playthroughs.len()), (299, 2));
+        change_runs(&mut draft, 2, "2025-08-01");
+        assert_eq!((draft.times_played, draft.playthroughs.len()), (301, 4));
+        assert_eq!(validate(&draft).unwrap().times_played, 301);
+
+        // Never fewer than none
+        let mut draft = GameDraft::from_game(&celeste);
+        change_runs(&mut draft, -5, "2025-08-01");
+        assert_eq!((draft.times_played, draft.playthroughs.len()), (0, 0));
+        assert_eq!(draft.last_playthrough, "2025-07-14"); // Nothing left to take the date from
+
+        // Editing anything else leaves the runs and the date alone
+        let mut draft = GameDraft::from_game(&celeste);
+        change_runs(&mut draft, 0, "2025-08-01");
+        assert_eq!(validate(&draft).unwrap(), celeste);
+    }
 
-        return attribute_string
+    #[test]
+    fn changes_past_the_cut_off_still_count() {
+        let mut old = game(80, 1, "2025-08-01");
+        old.notes = String::from("Finished the main story, going back for the B-sides");
+        let mut new = old.clone();
+        new.notes = String::from("Finished the main story, going back for the C-sides"); // Same first 30 characters
+
+        let (changes, unchanged) = edit_changes(&old, &new, RatingScale::FiveStars, "2025-08-01");
+        assert_eq!(changes.iter().map(|change| change.label).collect::<Vec<&str>>(), ["Notes"]);
+        assert_eq!(changes[0].old, changes[0].new); // Both shortened to the same text, but still listed
+        assert!(!unchanged.contains(&"Notes"));
     }
 }
@@ -1,55 +1,289 @@
 use std::fmt;
-use serde::{Deserialize, Serialize};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 
-#[derive(Copy, Clone, Serialize, Deserialize)]
-#[repr(u8)] // Rating is represented as an unsigned 8 bit integer in memory
-#[serde(rename_all = "lowercase")] // Change all Enum Values to be lowercase for consistency
-pub enum Rating {
-    One = 1, // Assign each Enum a u8 value for mapping
-    Two = 2,
-    Three = 3,
-    Four = 4,
-    Five = 5
-}
+/// A game's rating, stored as points out of 100 no matter what scale the user picked.
+///
+/// Keeping one normalised form means switching scales never loses anything already in the log,
+/// the scale only changes how the points are shown and typed in (see `RatingScale`).
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug)]
+pub struct Rating(u8);
 
 impl Rating {
-    
-// pub fn to_u8(&self) -> u8 // Parameter is the rating enum (Self allows for us to reference the object we're implementing on)
-// {
-//     return *self as u8; // We dont need a map here as I already mapped it out inb the enum
-// }
-    
-    pub fn from_u8(value: u8) -> Option<Rating> // Parameter is the rating enum (Self allows for us to reference the object we're implementing on)
+    pub const MAX_POINTS: u8 = 100;
+
+    pub fn from_points(points: u8) -> Option<Rating>
+    {
+        if (1..=Self::MAX_POINTS).contains(&points) {
+            Some(Rating(points))
+        }
+        else {
+            None
+        }
+    }
+
+    pub fn points(&self) -> u8
+    {
+        self.0
+    }
+}
+
+// Ratings are written to the file as their points (e.g. 90)
+impl Serialize for Rating {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(self.0)
+    }
+}
+
+// Older logs saved ratings as lowercase words ("one" to "five"), so both forms are accepted when reading
+impl<'de> Deserialize<'de> for Rating {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum StoredRating {
+            Points(u8),
+            Word(String),
+        }
+
+        let points = match StoredRating::deserialize(deserializer)? {
+            StoredRating::Points(points) => points,
+            StoredRating::Word(word) => match word.as_str() {
+                "one" => 20,
+                "two" => 40,
+                "three" => 60,
+                "four" => 80,
+                "five" => 100,
+                _ => return Err(de::Error::custom(format!("unknown rating \"{}\"", word))),
+            },
+        };
+
+        Rating::from_points(points).ok_or_else(|| de::Error::custom(format!("rating {} is out of range", points)))
+    }
+}
+
+/// How ratings are shown and entered. Chosen in the settings window
+#[derive(Copy, Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RatingScale {
+    #[default]
+    FiveStars, // The original 1-5 whole stars
+    HalfStars,
+    TenPoints,
+    HundredPoints,
+}
+
+impl RatingScale {
+    pub const ALL: [RatingScale; 4] = [RatingScale::FiveStars, RatingScale::HalfStars, RatingScale::TenPoints, RatingScale::HundredPoints];
+
+    // How many of the 100 internal points one step on this scale is worth (One whole star = 20 points, one half star = 10, etc.)
+    fn points_per_step(&self) -> u8 {
+        match self {
+            RatingScale::FiveStars => 20,
+            RatingScale::HalfStars | RatingScale::TenPoints => 10,
+            RatingScale::HundredPoints => 1,
+        }
+    }
+
+    /// Highest number of steps a rating can have on this scale
+    pub fn max_steps(&self) -> u8 {
+        Rating::MAX_POINTS / self.points_per_step()
+    }
+
+    /// Turn a rating into steps on this scale, rounding to the nearest step (never below one)
+    pub fn steps(&self, rating: Rating) -> u8 {
+        let per_step = self.points_per_step();
+        ((rating.points() + per_step / 2) / per_step).max(1)
+    }
+
+    pub fn from_steps(&self, steps: u8) -> Option<Rating> {
+        Rating::from_points(steps.checked_mul(self.points_per_step())?)
+    }
+
+    /// Read a rating typed in on this scale ("4", "4.5", "8", "85"...)
+    pub fn parse(&self, text: &str) -> Option<Rating> {
+        let text = text.trim();
+        match self {
+            RatingScale::HalfStars => {
+                let stars = text.parse::<f32>().ok()?;
+                let halves = stars * 2.0;
+                if halves.fract() != 0.0 || !(1.0..=10.0).contains(&halves) { // Only whole and half stars allowed
+                    return None
+                }
+                self.from_steps(halves as u8)
+            },
+            _ => self.from_steps(text.parse::<u8>().ok()?),
+        }
+    }
+
+    /// The plain value as it would be typed in (the opposite of `parse`)
+    pub fn format_value(&self, rating: Rating) -> String {
+        let steps = self.steps(rating);
+        match self {
+            RatingScale::HalfStars if steps % 2 == 1 => format!("{}.5", steps / 2),
+            RatingScale::HalfStars => format!("{}", steps / 2),
+            _ => steps.to_string(),
+        }
+    }
+
+    /// How a rating is shown in the game list (e.g. ★★★★½ or 8/10)
+    pub fn display(&self, rating: Rating) -> String {
+        let steps = self.steps(rating) as usize;
+        match self {
+            RatingScale::FiveStars => format!("{}{}", "★".repeat(steps), "☆".repeat(5 - steps)),
+            RatingScale::HalfStars => {
+                let half = if steps % 2 == 1 { "½" } else { "" };
+                format!("{}{}{}", "★".repeat(steps / 2), half, "☆".repeat(5 - steps.div_ceil(2)))
+            },
+            RatingScale::TenPoints => format!("{}/10", steps),
+            RatingScale::HundredPoints => format!("{}/100", steps),
+        }
+    }
+
+    /// Tells users what they can enter (Used for hints and error messages)
+    pub fn hint(&self) -> &'static str {
+        match self {
+            RatingScale::FiveStars => "Number between 1-5",
+            RatingScale::HalfStars => "Stars between 0.5-5 (halves allowed)",
+            RatingScale::TenPoints => "Number between 1-10",
+            RatingScale::HundredPoints => "Number between 1-100",
+        }
+    }
+}
+
+impl fmt::Display for RatingScale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            RatingScale::FiveStars => "Five Stars",
+            RatingScale::HalfStars => "Five Stars (Halves)",
+            RatingScale::TenPoints => "Ten Points",
+            RatingScale::HundredPoints => "100 Points",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// Where a game is up to. Games added before statuses existed have none
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GameStatus {
+    Backlog,
+    Wishlist,
+    Playing,
+    Played, // Played without finishing, but not given up on either
+    Completed,
+    Shelved,
+    Abandoned,
+}
+
+impl GameStatus {
+    pub const ALL: [GameStatus; 7] = [GameStatus::Backlog, GameStatus::Wishlist, GameStatus::Playing, GameStatus::Played,
+        GameStatus::Completed, GameStatus::Shelved, GameStatus::Abandoned];
+
+    /// Read a status the way other sites and exports word them ("Beaten", "Wish List", "Retired"...)
+    pub fn parse(text: &str) -> Option<GameStatus>
     {
-        match value {
-            1 => Some(Rating::One),
-            2 => Some(Rating::Two),
-            3 => Some(Rating::Three),
-            4 => Some(Rating::Four),
-            5 => Some(Rating::Five),
-            _ => None
+        let text = text.trim().to_lowercase().replace([' ', '_', '-'], "");
+
+        match text.as_str() {
+            "backlog" | "backlogged" | "planned" | "plantoplay" | "owned" | "unplayed" => Some(GameStatus::Backlog),
+            "wishlist" | "wishlisted" | "wanttoplay" => Some(GameStatus::Wishlist),
+            "playing" | "currentlyplaying" | "inprogress" => Some(GameStatus::Playing),
+            "played" => Some(GameStatus::Played),
+            "completed" | "complete" | "beaten" | "finished" | "mastered" => Some(GameStatus::Completed),
+            "shelved" | "onhold" | "paused" => Some(GameStatus::Shelved),
+            "abandoned" | "retired" | "dropped" => Some(GameStatus::Abandoned),
+            _ => None,
         }
     }
 }
 
-// So we can display the enum
-impl fmt::Display for Rating {
+impl fmt::Display for GameStatus {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let s = match self {
-            Rating::One => "1",
-            Rating::Two => "2",
-            Rating::Three => "3",
-            Rating::Four => "4",
-            Rating::Five => "5",
+            GameStatus::Backlog => "Backlog",
+            GameStatus::Wishlist => "Wishlist",
+            GameStatus::Playing => "Playing",
+            GameStatus::Played => "Played",
+            GameStatus::Completed => "Completed",
+            GameStatus::Shelved => "Shelved",
+            GameStatus::Abandoned => "Abandoned",
         };
         write!(f, "{}", s)
     }
 }
 
+/// How the main window shows the log. Kept in the settings file
+#[derive(Copy, Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ViewMode {
+    #[default]
+    List, // The original text list
+    Table,
+    Grid, // Cards with covers
+}
+
+impl ViewMode {
+    pub const ALL: [ViewMode; 3] = [ViewMode::List, ViewMode::Table, ViewMode::Grid];
+}
+
+impl fmt::Display for ViewMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            ViewMode::List => "List",
+            ViewMode::Table => "Table",
+            ViewMode::Grid => "Grid",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// Tabs along the top of the 'Editing' window once a game is found
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+pub enum EditingTab {
+    #[default]
+    Details,
+    History, // Timeline of changes to the game, from the change journal
+}
+
 // For Opening a window
+#[derive(Copy, Clone, PartialEq, Debug)]
 pub enum WindowOpened {
     Adding,
     Removing,
     Editing,
+    Tags,
+    Import,
+    Statistics,
+    Settings,
+    Backups,
     Default
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn old_word_ratings_still_load() {
+        let ratings: Vec<Rating> = serde_json::from_str(r#"["one", "two", "three", "four", "five", 90]"#).unwrap();
+        let points: Vec<u8> = ratings.iter().map(Rating::points).collect();
+        assert_eq!(points, [20, 40, 60, 80, 100, 90]);
+
+        assert!(serde_json::from_str::<Rating>(r#""six""#).is_err());
+        assert!(serde_json::from_str::<Rating>("0").is_err());
+    }
+
+    #[test]
+    fn scales_parse_and_round_ratings() {
+        assert_eq!(RatingScale::FiveStars.parse("4"), Rating::from_points(80));
+        assert_eq!(RatingScale::HalfStars.parse("3.5"), Rating::from_points(70));
+        assert_eq!(RatingScale::HalfStars.parse("3.25"), None);
+        assert_eq!(RatingScale::TenPoints.parse(" 7 "), Rating::from_points(70));
+        assert_eq!(RatingScale::HundredPoints.parse("101"), None);
+        assert_eq!(RatingScale::FiveStars.parse("0"), None);
+
+        let rating = Rating::from_points(90).unwrap();
+        assert_eq!(RatingScale::FiveStars.steps(rating), 5); // Rounds to the nearest star
+        assert_eq!(RatingScale::HalfStars.steps(rating), 9);
+        assert_eq!(RatingScale::FiveStars.steps(Rating::from_points(1).unwrap()), 1); // Never below one
+        assert_eq!(RatingScale::HalfStars.format_value(rating), "4.5");
+    }
+}
@@ -0,0 +1,101 @@
+// Crates/Imports
+use std::fs::OpenOptions;
+use std::io::Write;
+
+use crate::{enums::RatingScale, json_file_operations::Game, notes::{redact_spoilers, SPOILER_REDACTED}, playtime::play_time_summary};
+
+const CSV_EXPORT_FILE: &str = "GameLogExport.csv";
+
+// Quote a CSV value if it has anything in it that would break the columns
+fn csv_field(value: &str) -> String
+{
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    }
+    else {
+        value.to_string()
+    }
+}
+
+// Notes as they go in an export. Spoilers are only left in if the user has said so in the settings
+fn export_notes(notes: &str, include_spoilers: bool) -> String
+{
+    if include_spoilers { notes.to_string() } else { redact_spoilers(notes, SPOILER_REDACTED) }
+}
+
+/// Turn games into CSV text (one game per row). Tags are joined together with semicolons in one column
+pub fn games_to_csv(games: &[Game], include_spoilers: bool) -> String
+{
+    let mut csv = String::from("name,rating,times_played,last_playthrough,platform,storefront,status,release_year,developer,genres,play_time_seconds,tags,notes\n");
+
+    for game in games {
+        let row = [
+            csv_field(&game.name),
+            game.rating.points().to_string(), // Points out of 100 so the export doesn't depend on the chosen scale
+            game.times_played.to_string(),
+            csv_field(&game.last_playthrough),
+            csv_field(&game.platform),
+            csv_field(&game.storefront),
+            game.status.map(|status| status.to_string()).unwrap_or_default(),
+            game.release_year.map(|year| year.to_string()).unwrap_or_default(),
+            csv_field(&game.developer),
+            csv_field(&game.genres.join(";")),
+            game.play_time.as_secs().to_string(),
+            csv_field(&game.tags.join(";")),
+            csv_field(&export_notes(&game.notes, include_spoilers)),
+        ];
+        csv.push_str(&row.join(","));
+        csv.push('\n');
+    }
+
+    csv
+}
+
+/// Write games to the CSV export file next to the log, returning where it was saved
+pub fn write_csv_export(games: &[Game], include_spoilers: bool) -> Result<String, Box<dyn std::error::Error>>
+{
+    let mut file = OpenOptions::new()
+        .create(true)
+        .write(true)
+        .truncate(true)
+        .open(CSV_EXPORT_FILE)?;
+
+    file.write_all(games_to_csv(games, include_spoilers).as_bytes())?;
+
+    Ok(CSV_EXPORT_FILE.to_string())
+}
+
+/// A game as a small Markdown section (Copied from the game's right click menu). Empty details are left out
+pub fn game_to_markdown(game: &Game, rating_scale: RatingScale, include_spoilers: bool) -> String
+{
+    let mut markdown = format!("## {}\n\n", game.name);
+
+    let details = [
+        ("Rating", rating_scale.display(game.rating)),
+        ("Status", game.status.map(|status| status.to_string()).unwrap_or_default()),
+        ("Times Played", game.times_played.to_string()),
+        ("Play Time", play_time_summary(game)),
+        ("Last Playthrough", game.last_playthrough.clone()),
+        ("Platform", game.platform.clone()),
+        ("Tags", game.tags.join(", ")),
+    ];
+
+    for (label, value) in details.iter().filter(|(_, value)| !value.is_empty()) {
+        markdown.push_str(&format!("- **{}:** {}\n", label, value));
+    }
+
+    if !game.notes.is_empty() {
+        markdown.push_str(&format!("\n{}\n", export_notes(&game.notes, include_spoilers)));
+    }
+
+    // Notes left on individual playthroughs
+    let run_notes: Vec<String> = game.playthroughs.iter()
+        .filter(|playthrough| !playthrough.note.is_empty())
+        .map(|playthrough| format!("- {}: {}", playthrough.date, export_notes(&playthrough.note, include_spoilers)))
+        .collect();
+    if !run_notes.is_empty() {
+        markdown.push_str(&format!("\n### Playthroughs\n{}\n", run_notes.join("\n")));
+    }
+
+    markdown
+}
@@ -0,0 +1,443 @@
+// Crates/Imports
+use eframe::egui::{self, text::{LayoutJob, TextWrapping}, Button, Color32, CornerRadius, FontId, Grid, Label, Modifiers, Response, RichText, Sense, Stroke, TextEdit, Ui, Vec2};
+
+use crate::{app_setup::GameLog, enums::{EditingTab, GameStatus, ViewMode, WindowOpened}, export::{game_to_markdown, write_csv_export}, json_file_operations::{search_for_game, Game}, notes::markdown_view, platforms::platform_picker, playtime::play_time_summary, status::status_picker, tags::{normalise_tags, same_tag}};
+
+pub const CARD_WIDTH: f32 = 140.0;
+const CARD_SIZE: Vec2 = Vec2::new(CARD_WIDTH, 260.0);
+const COVER_SIZE: Vec2 = Vec2::new(120.0, 160.0);
+const CARD_SPACING: f32 = 10.0;
+const FRAME_MARGIN: f32 = 40.0; // Frame padding and the scroll bar
+
+/// How wide the frame holding the games is for the window's `available_width`.
+///
+/// The list keeps `list_width` (The width the search bar layout is built around), while the table and grid use all the room there is, down to one card
+pub fn frame_width(view_mode: ViewMode, available_width: f32, list_width: f32) -> f32
+{
+    match view_mode {
+        ViewMode::List => list_width,
+        ViewMode::Table | ViewMode::Grid => (available_width - FRAME_MARGIN).max(CARD_WIDTH),
+    }
+}
+
+/// How many cards fit on a grid row `width` wide (Always at least one)
+pub fn grid_columns(width: f32) -> usize
+{
+    (((width + CARD_SPACING) / (CARD_WIDTH + CARD_SPACING)).floor() as usize).max(1)
+}
+
+/// What a click does to the selection (By name, with `shown` being the games on screen in order).
+///
+/// A plain click picks just that game, Ctrl toggles it, and Shift picks everything between the anchor and it (Ctrl+Shift adds that range on)
+pub fn click_selection(selected: &[String], anchor: Option<&str>, shown: &[String], clicked: &str, modifiers: Modifiers) -> Vec<String>
+{
+    let position = |name: &str| shown.iter().position(|shown_name| shown_name == name);
+
+    if modifiers.shift
+        && let (Some(start), Some(end)) = (anchor.and_then(position), position(clicked))
+    {
+        let range = &shown[start.min(end)..=start.max(end)];
+        let mut selection = if modifiers.command { selected.to_vec() } else { Vec::new() };
+
+        for name in range {
+            if !selection.contains(name) {
+                selection.push(name.clone());
+            }
+        }
+        return selection;
+    }
+
+    if modifiers.command {
+        let mut selection = selected.to_vec();
+        match selection.iter().position(|name| name == clicked) {
+            Some(i) => { selection.remove(i); },
+            None => selection.push(clicked.to_string()),
+        }
+        return selection;
+    }
+
+    vec![clicked.to_string()]
+}
+
+// One line of card text, cut off with "…" if it's wider than the card
+fn card_line(text: &str, font: FontId, color: Color32, width: f32) -> LayoutJob
+{
+    let mut job = LayoutJob::simple_singleline(text.to_string(), font, color);
+    job.wrap = TextWrapping::truncate_at_width(width);
+    job
+}
+
+/// A game's notes under its entry in the list view, with the Markdown shown as formatting
+pub fn list_entry_notes(ui: &mut Ui, notes: &str, index: usize)
+{
+    if !notes.trim().is_empty() {
+        ui.label(RichText::new("Notes:").size(20.0).strong());
+        ui.indent(("notes", index), |ui| markdown_view(ui, notes));
+    }
+    ui.add_space(20.0); // Gap before the next game
+}
+
+// Table/Grid GUI Code
+impl GameLog {
+    /// Indexes of the games the main window should show: the search result, or everything that passes the sidebar filters
+    pub fn shown_games(&self) -> Vec<usize> {
+        match &self.search_result {
+            Some(games_found) => games_found.iter()
+                .filter_map(|game| search_for_game(&self.game_file_contents, &game.name).ok())
+                .collect(),
+            None => (0..self.game_file_contents.len())
+                .filter(|&i| self.is_game_shown(&self.game_file_contents[i]))
+                .collect(),
+        }
+    }
+
+    pub fn is_selected(&self, index: usize) -> bool {
+        self.selected_games.contains(&self.game_file_contents[index].name)
+    }
+
+    /// Text for one game in the list view, highlighted when it's selected
+    pub fn list_entry_text(&self, ui: &Ui, text: &str, index: usize) -> RichText {
+        let text = RichText::new(text).size(20.0).strong();
+
+        if self.is_selected(index) {
+            text.color(ui.visuals().strong_text_color()).background_color(ui.visuals().selection.bg_fill)
+        }
+        else {
+            text.color(ui.visuals().text_color())
+        }
+    }
+
+    /// Indexes of the selected games, in log order
+    pub fn selected_indexes(&self) -> Vec<usize> {
+        (0..self.game_file_contents.len()).filter(|&i| self.is_selected(i)).collect()
+    }
+
+    /// Clicking (or right clicking) a game selects it, and right clicking opens a menu of things to do with it
+    pub fn game_interactions(&mut self, response: Response, index: usize) {
+        let name = self.game_file_contents[index].name.clone();
+
+        if response.clicked() {
+            let modifiers = response.ctx.input(|input| input.modifiers);
+            let shown: Vec<String> = self.shown_games().into_iter().map(|i| self.game_file_contents[i].name.clone()).collect();
+
+            self.selected_games = click_selection(&self.selected_games, self.selection_anchor.as_deref(), &shown, &name, modifiers);
+            if !modifiers.shift {
+                self.selection_anchor = Some(name);
+            }
+        }
+        // Right clicking outside the selection starts a new one, so the menu is clearly about that game
+        else if response.secondary_clicked() && !self.selected_games.contains(&name) {
+            self.selected_games = vec![name.clone()];
+            self.selection_anchor = Some(name);
+        }
+
+        response.context_menu(|ui| {
+            let game_name = self.game_file_contents[index].name.clone();
+            let mut result = None; // Outcome of whichever entry was picked
+
+            if ui.button("Edit").clicked() {
+                self.load_game_for_editing(index);
+                self.editing_tab = EditingTab::Details;
+                self.open_window = true;
+                self.current_window_opened = WindowOpened::Editing;
+                ui.close_menu();
+            }
+
+            if ui.button("History").clicked() {
+                self.load_game_for_editing(index);
+                self.editing_tab = EditingTab::History;
+                self.open_window = true;
+                self.current_window_opened = WindowOpened::Editing;
+                ui.close_menu();
+            }
+
+            if ui.button("Log a New Playthrough").on_hover_text("+1 times played, dated today").clicked() {
+                result = Some(self.log_playthrough(index, "", "", ""));
+                ui.close_menu();
+            }
+
+            ui.menu_button("Log a Playthrough On...", |ui| {
+                if let Some(logged) = self.playthrough_form(ui, index) {
+                    result = Some(logged);
+                    ui.close_menu();
+                }
+            });
+
+            ui.menu_button("Change Status", |ui| {
+                let current = self.game_file_contents[index].status;
+
+                for status in std::iter::once(None).chain(GameStatus::ALL.into_iter().map(Some)) {
+                    let label = status.map(|status| status.to_string()).unwrap_or_else(|| String::from("None"));
+
+                    if ui.selectable_label(current == status, label).clicked() {
+                        result = Some(self.set_status(index, status));
+                        ui.close_menu();
+                    }
+                }
+            });
+
+            if ui.button("Copy as Markdown").clicked() {
+                ui.ctx().copy_text(game_to_markdown(&self.game_file_contents[index], self.settings.rating_scale, self.settings.export_spoilers));
+                ui.close_menu();
+            }
+
+            if ui.button("Duplicate").clicked() {
+                result = Some(self.duplicate_game(index).map(|copy_name| self.selected_games = vec![copy_name]));
+                ui.close_menu();
+            }
+
+            if self.metadata_provider.is_some() && ui.button("Look Up Cover").clicked() {
+                self.look_up_cover(ui.ctx(), &game_name);
+                ui.close_menu();
+            }
+
+            ui.separator();
+
+            if ui.button("Move to Trash").clicked() {
+                result = Some(self.move_to_trash(index));
+                ui.close_menu();
+            }
+
+            if let Some(result) = result {
+                self.list_feedback_error = result.is_err();
+                self.list_feedback_message = result.err().unwrap_or_default();
+            }
+        });
+    }
+
+    /// Date and note boxes for logging a run, shared by the right click menu and the command palette.
+    ///
+    /// Returns the outcome once Log is pressed (or Enter in the note box)
+    pub fn playthrough_form(&mut self, ui: &mut Ui, index: usize) -> Option<Result<(), String>> {
+        ui.add(TextEdit::singleline(&mut self.log_run_date).hint_text("Date (Empty for today)").desired_width(180.0));
+        let note_response = ui.add(TextEdit::singleline(&mut self.log_run_note).hint_text("Note (Optional)").desired_width(180.0));
+        ui.horizontal(|ui| {
+            ui.label("Played On:").on_hover_text("None uses the game's own platform");
+            platform_picker(ui, "run_platform", &mut self.log_run_platform, &self.settings.platforms);
+        });
+
+        let submitted = note_response.lost_focus() && ui.input(|input| input.key_pressed(egui::Key::Enter));
+        if !(ui.button("Log").clicked() || submitted) {
+            return None;
+        }
+
+        let date = std::mem::take(&mut self.log_run_date);
+        let note = std::mem::take(&mut self.log_run_note);
+        let result = self.log_playthrough(index, &date, &note, &self.log_run_platform.clone());
+
+        // Leave what was typed in if it didn't work, so it can be fixed
+        if result.is_err() {
+            self.log_run_date = date;
+            self.log_run_note = note;
+        }
+
+        Some(result)
+    }
+
+    /// Actions for every selected game at once. Each one is a single change, so one undo puts the whole batch back
+    pub fn batch_action_bar(&mut self, ui: &mut Ui) {
+        let indexes = self.selected_indexes();
+        if indexes.is_empty() {
+            return;
+        }
+
+        let count = indexes.len();
+        let mut result: Option<Result<String, String>> = None;
+
+        ui.horizontal_wrapped(|ui| {
+            ui.label(RichText::new(format!("{} Selected", count)).strong());
+            ui.separator();
+
+            status_picker(ui, "batch_status", &mut self.batch_status);
+            if ui.button("Set Status").clicked() {
+                let status = self.batch_status;
+                result = Some(self.change_games(&format!("Set status of {} game(s)", count), &indexes, |game| game.status = status)
+                    .map(|changed| format!("Set the status of {} game(s)", changed)));
+            }
+
+            ui.separator();
+
+            ui.add(TextEdit::singleline(&mut self.batch_tag).hint_text("Tag").desired_width(90.0));
+            let tag = self.batch_tag.trim().to_string();
+
+            if ui.add_enabled(!tag.is_empty(), Button::new("Add Tag")).clicked() {
+                result = Some(self.change_games(&format!("Tag {} game(s) {}", count, tag), &indexes, |game| {
+                    game.tags.push(tag.clone());
+                    game.tags = normalise_tags(&game.tags);
+                }).map(|changed| format!("Tagged {} game(s) {}", changed, tag)));
+            }
+            if ui.add_enabled(!tag.is_empty(), Button::new("Remove Tag")).clicked() {
+                result = Some(self.change_games(&format!("Untag {} game(s) {}", count, tag), &indexes, |game| {
+                    game.tags.retain(|game_tag| !same_tag(game_tag, &tag));
+                }).map(|changed| format!("Removed {} from {} game(s)", tag, changed)));
+            }
+
+            ui.separator();
+
+            platform_picker(ui, "batch_platform", &mut self.batch_platform, &self.settings.platforms);
+            if ui.button("Set Platform").clicked() {
+                let platform = self.batch_platform.clone();
+                result = Some(self.change_games(&format!("Set platform of {} game(s)", count), &indexes, |game| game.platform = platform.clone())
+                    .map(|changed| format!("Set the platform of {} game(s)", changed)));
+            }
+
+            ui.separator();
+
+            if ui.button("Export").clicked() {
+                let games: Vec<Game> = indexes.iter().map(|&i| self.game_file_contents[i].clone()).collect();
+                result = Some(write_csv_export(&games, self.settings.export_spoilers)
+                    .map(|path| format!("Exported {} game(s) to {}", count, path))
+                    .map_err(|_| String::from("Error Exporting Games")));
+            }
+
+            if ui.button("Move to Trash").clicked() {
+                result = Some(self.trash_games(&indexes).map(|moved| format!("Moved {} game(s) to the trash", moved)));
+                self.selected_games.clear();
+            }
+
+            if ui.button("Clear Selection").clicked() {
+                self.selected_games.clear();
+            }
+        });
+
+        if let Some(result) = result {
+            self.list_feedback_error = result.is_err();
+            self.list_feedback_message = result.unwrap_or_else(|error| error);
+        }
+    }
+
+    /// One row per game, with the most used details as columns
+    pub fn game_table(&mut self, ui: &mut Ui, games: &[usize]) {
+        Grid::new("game_table").striped(true).spacing(Vec2::new(15.0, 6.0)).show(ui, |ui| {
+            for heading in ["Name", "Rating", "Status", "Times Played", "Play Time", "Last Played", "Platform"] {
+                ui.label(RichText::new(heading).strong());
+            }
+            ui.end_row();
+
+            for &index in games {
+                let game = &self.game_file_contents[index];
+                let row = [
+                    self.settings.rating_scale.display(game.rating),
+                    game.status.map(|status| status.to_string()).unwrap_or_default(),
+                    game.times_played.to_string(),
+                    play_time_summary(game),
+                    game.last_playthrough.clone(),
+                    game.platform.clone(),
+                ];
+
+                let response = ui.selectable_label(self.is_selected(index), &game.name);
+                self.game_interactions(response, index);
+
+                for cell in row {
+                    ui.add(Label::new(cell).truncate());
+                }
+                ui.end_row();
+            }
+        });
+    }
+
+    /// Cards with each game's cover, rating, status and when it was last played.
+    ///
+    /// As many cards go on a row as fit in `width`, so the grid reflows as the window is resized
+    pub fn game_grid(&mut self, ui: &mut Ui, games: &[usize], width: f32) {
+        let columns = grid_columns(width);
+
+        ui.spacing_mut().item_spacing = Vec2::splat(CARD_SPACING);
+
+        for row in games.chunks(columns) {
+            ui.horizontal(|ui| {
+                for &index in row {
+                    let (rect, response) = ui.allocate_exact_size(CARD_SIZE, Sense::click());
+
+                    if ui.is_rect_visible(rect) {
+                        self.game_card(ui, rect, index, response.hovered());
+                    }
+
+                    self.game_interactions(response, index);
+                }
+            });
+        }
+    }
+
+    // Draw one grid card
+    fn game_card(&mut self, ui: &Ui, rect: egui::Rect, index: usize, hovered: bool) {
+        let visuals = ui.visuals();
+        let outline = if self.is_selected(index) {
+            visuals.selection.stroke
+        }
+        else if hovered {
+            visuals.widgets.hovered.bg_stroke
+        }
+        else {
+            Stroke::new(1.0, visuals.widgets.noninteractive.bg_stroke.color)
+        };
+
+        ui.painter().rect(rect, CornerRadius::same(8), visuals.faint_bg_color, outline, egui::StrokeKind::Inside);
+
+        let game = &self.game_file_contents[index];
+        let (name, cover) = (game.name.clone(), game.cover.clone());
+        let text_width = rect.width() - 16.0;
+        let text_color = visuals.text_color();
+        let weak_color = visuals.weak_text_color();
+
+        let lines = [
+            card_line(&name, FontId::proportional(15.0), text_color, text_width),
+            card_line(&self.settings.rating_scale.display(game.rating), FontId::proportional(14.0), Color32::GOLD, text_width),
+            card_line(&game.status.map(|status| status.to_string()).unwrap_or_default(), FontId::proportional(12.0), weak_color, text_width),
+            card_line(&format!("Last Played: {}", game.last_playthrough), FontId::proportional(12.0), weak_color, text_width),
+        ];
+
+        let cover_rect = egui::Rect::from_min_size(rect.min + Vec2::new((rect.width() - COVER_SIZE.x) / 2.0, 10.0), COVER_SIZE);
+        self.paint_cover(ui, cover_rect, &name, &cover);
+
+        let painter = ui.painter_at(rect);
+        let mut top = cover_rect.bottom() + 8.0;
+        for line in lines {
+            let galley = painter.layout_job(line);
+            let height = galley.size().y;
+            painter.galley(egui::pos2(rect.left() + 8.0, top), galley, text_color);
+            top += height + 4.0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grid_reflows_with_the_window() {
+        assert_eq!(frame_width(ViewMode::List, 1200.0, 600.0), 600.0);
+        assert_eq!(frame_width(ViewMode::Grid, 1200.0, 600.0), 1160.0);
+        assert_eq!(frame_width(ViewMode::Table, 100.0, 600.0), CARD_WIDTH); // Never narrower than a card
+
+        assert_eq!(grid_columns(CARD_WIDTH), 1);
+        assert_eq!(grid_columns(CARD_WIDTH * 2.0 + CARD_SPACING - 1.0), 1); // Just short of room for the spacing
+        assert_eq!(grid_columns(CARD_WIDTH * 2.0 + CARD_SPACING), 2);
+        assert_eq!(grid_columns(frame_width(ViewMode::Grid, 1200.0, 600.0)), 7);
+        assert_eq!(grid_columns(0.0), 1);
+    }
+
+    fn names(list: &[&str]) -> Vec<String> {
+        list.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn clicks_build_up_selections() {
+        let shown = names(&["Celeste", "Hades", "Tunic", "Hollow Knight"]);
+        let ctrl = Modifiers::COMMAND;
+        let shift = Modifiers::SHIFT;
+
+        let selected = click_selection(&[], None, &shown, "Hades", Modifiers::NONE);
+        assert_eq!(selected, names(&["Hades"]));
+
+        let selected = click_selection(&selected, Some("Hades"), &shown, "Hollow Knight", shift);
+        assert_eq!(selected, names(&["Hades", "Tunic", "Hollow Knight"]));
+
+        let selected = click_selection(&selected, Some("Hades"), &shown, "Tunic", ctrl);
+        assert_eq!(selected, names(&["Hades", "Hollow Knight"]));
+
+        let selected = click_selection(&selected, Some("Tunic"), &shown, "Celeste", ctrl | shift); // Range added on
+        assert_eq!(selected, names(&["Hades", "Hollow Knight", "Celeste", "Tunic"]));
+    }
+}
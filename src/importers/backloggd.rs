@@ -0,0 +1,102 @@
+// Crates/Imports
+use std::path::{Path, PathBuf};
+
+use crate::{enums::{GameStatus, Rating}, importers::{merge_duplicates, parse_date, rows::read_rows, ImportCandidate, Importer}};
+
+/// Games from a Backloggd data export (CSV or JSON)
+pub struct BackloggdImporter;
+
+impl Importer for BackloggdImporter {
+    fn name(&self) -> &'static str {
+        "Backloggd"
+    }
+
+    fn path_hint(&self) -> &'static str {
+        "Exported .csv or .json file"
+    }
+
+    fn default_path(&self) -> Option<PathBuf> {
+        None
+    }
+
+    fn read(&self, path: &Path) -> Result<Vec<ImportCandidate>, anyhow::Error> {
+        read_backloggd_export(path)
+    }
+}
+
+/// Backloggd rates out of 5 stars with halves (0.5 - 5)
+fn stars_to_rating(text: &str) -> Option<Rating>
+{
+    let stars = text.parse::<f32>().ok()?;
+    Rating::from_points((stars.clamp(0.0, 5.0) * 20.0).round() as u8)
+}
+
+/// Read a Backloggd export. Each row is a log entry, so games logged more than once become one game with several playthroughs
+pub fn read_backloggd_export(path: &Path) -> Result<Vec<ImportCandidate>, anyhow::Error>
+{
+    let mut candidates = Vec::new();
+
+    for row in read_rows(path)? {
+        let name = row.get(&["Game", "Name", "Title"]);
+        if name.is_empty() {
+            continue;
+        }
+
+        let finished = parse_date(row.get(&["Finish Date", "Finished", "Finished On", "Completed"]));
+        let started = parse_date(row.get(&["Start Date", "Started", "Started On"]));
+
+        candidates.push(ImportCandidate {
+            name: name.to_string(),
+            platform: row.get(&["Platform", "Played Platform"]).to_string(),
+            storefront: row.get(&["Ownership", "Storefront"]).to_string(),
+            play_dates: finished.clone().or(started).into_iter().collect(), // The day a run ended counts, otherwise the day it started
+            last_played: finished,
+            rating: stars_to_rating(row.get(&["Rating", "Stars"])),
+            status: GameStatus::parse(row.get(&["Status", "Play Status"])),
+            notes: row.get(&["Review", "Notes"]).to_string(),
+            ..ImportCandidate::default()
+        });
+    }
+
+    let mut candidates = merge_duplicates(candidates);
+    candidates.sort_by_key(|candidate| candidate.name.to_lowercase());
+    Ok(candidates)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_fixture_export() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures/backloggd_export.csv");
+
+        let candidates = read_backloggd_export(&path).unwrap();
+        let names: Vec<&str> = candidates.iter().map(|candidate| candidate.name.as_str()).collect();
+
+        assert_eq!(names, vec!["Elden Ring", "Outer Wilds", "Pentiment"]);
+
+        let outer_wilds = &candidates[1];
+        assert_eq!(outer_wilds.rating.map(|rating| rating.points()), Some(90)); // 4.5 stars
+        assert_eq!(outer_wilds.status, Some(GameStatus::Completed));
+        assert_eq!(outer_wilds.play_dates, vec!["2023-05-20", "2024-01-07"]); // Logged twice
+        assert_eq!(outer_wilds.notes, "Best ending, \"no spoilers\"");
+
+        assert_eq!(candidates[0].status, Some(GameStatus::Abandoned)); // "Retired"
+        assert_eq!(candidates[2].rating, None);
+        assert_eq!(candidates[2].status, Some(GameStatus::Wishlist));
+    }
+
+    #[test]
+    fn json_exports_use_the_same_columns() {
+        let path = std::env::temp_dir().join(format!("gamelog_backloggd_{}.json", std::process::id()));
+        std::fs::write(&path, r#"[{"Game": "Celeste", "Rating": 5, "Status": "Completed", "Finish Date": "2022-02-02"}]"#).unwrap();
+
+        let candidates = read_backloggd_export(&path).unwrap();
+
+        assert_eq!(candidates[0].rating.map(|rating| rating.points()), Some(100));
+        assert_eq!(candidates[0].play_dates, vec!["2022-02-02"]);
+
+        std::fs::remove_file(path).unwrap();
+    }
+}
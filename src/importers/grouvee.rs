@@ -0,0 +1,108 @@
+// Crates/Imports
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use serde_json::Value;
+
+use crate::{enums::{GameStatus, Rating}, importers::{parse_date, rows::read_rows, ImportCandidate, Importer}};
+
+/// Games from a Grouvee CSV export
+pub struct GrouveeImporter;
+
+impl Importer for GrouveeImporter {
+    fn name(&self) -> &'static str {
+        "Grouvee"
+    }
+
+    fn path_hint(&self) -> &'static str {
+        "Exported .csv file"
+    }
+
+    fn default_path(&self) -> Option<PathBuf> {
+        None
+    }
+
+    fn read(&self, path: &Path) -> Result<Vec<ImportCandidate>, anyhow::Error> {
+        read_grouvee_export(path)
+    }
+}
+
+// Grouvee puts JSON inside some of its CSV columns (shelves, platforms, dates...)
+fn json_column(text: &str) -> Value
+{
+    serde_json::from_str(text).unwrap_or(Value::Null)
+}
+
+/// Read a Grouvee export. Ratings are out of 5, and the "dates" column holds every playthrough with its play time
+pub fn read_grouvee_export(path: &Path) -> Result<Vec<ImportCandidate>, anyhow::Error>
+{
+    let mut candidates = Vec::new();
+
+    for row in read_rows(path)? {
+        let name = row.get(&["name"]);
+        if name.is_empty() {
+            continue;
+        }
+
+        let dates = json_column(row.get(&["dates"]));
+        let runs = dates.as_array().map(Vec::as_slice).unwrap_or_default();
+
+        let play_dates: Vec<String> = runs.iter()
+            .filter_map(|run| ["date_finished", "date_started"].iter().find_map(|key| run.get(key).and_then(Value::as_str).and_then(parse_date)))
+            .collect();
+        let play_time = runs.iter().filter_map(|run| run.get("seconds_played").and_then(Value::as_u64)).sum();
+
+        // The latest status change wins, otherwise the shelf the game sits on (shelves are a {"Name": {...}} object)
+        let status = json_column(row.get(&["statuses"])).as_array()
+            .and_then(|statuses| statuses.last())
+            .and_then(|status| status.get("status").and_then(Value::as_str).and_then(GameStatus::parse))
+            .or_else(|| json_column(row.get(&["shelves"])).as_object()
+                .and_then(|shelves| shelves.keys().find_map(|shelf| GameStatus::parse(shelf))));
+
+        let platform = json_column(row.get(&["platforms"])).as_object()
+            .and_then(|platforms| platforms.keys().next().cloned())
+            .unwrap_or_default();
+
+        candidates.push(ImportCandidate {
+            name: name.to_string(),
+            platform,
+            play_time: Duration::from_secs(play_time),
+            last_played: play_dates.iter().max().cloned(),
+            play_dates,
+            rating: row.get(&["rating"]).parse::<u8>().ok().and_then(|stars| Rating::from_points(stars.min(5) * 20)),
+            status,
+            notes: row.get(&["review"]).to_string(),
+            ..ImportCandidate::default()
+        });
+    }
+
+    candidates.sort_by_key(|candidate| candidate.name.to_lowercase());
+    Ok(candidates)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_fixture_export() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures/grouvee_export.csv");
+
+        let candidates = read_grouvee_export(&path).unwrap();
+        let names: Vec<&str> = candidates.iter().map(|candidate| candidate.name.as_str()).collect();
+
+        assert_eq!(names, vec!["Chrono Trigger", "Hades"]);
+
+        let chrono_trigger = &candidates[0];
+        assert_eq!(chrono_trigger.platform, "Super Nintendo");
+        assert_eq!(chrono_trigger.rating.map(|rating| rating.points()), Some(100));
+        assert_eq!(chrono_trigger.status, Some(GameStatus::Completed)); // "Beaten"
+        assert_eq!(chrono_trigger.play_dates, vec!["2019-04-12", "2022-12-30"]);
+        assert_eq!(chrono_trigger.play_time, Duration::from_secs(72_000));
+        assert_eq!(chrono_trigger.last_played.as_deref(), Some("2022-12-30"));
+        assert_eq!(chrono_trigger.notes, "Still the best, 10/10");
+
+        assert_eq!(candidates[1].status, Some(GameStatus::Backlog)); // From its shelf
+        assert_eq!(candidates[1].rating, None);
+        assert!(candidates[1].play_dates.is_empty());
+    }
+}
@@ -0,0 +1,124 @@
+// Crates/Imports
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use anyhow::anyhow;
+use serde_json::Value;
+
+use crate::importers::{home_dir, iso_to_date, ImportCandidate, Importer};
+
+// Library cache file, the list inside it and the store its games come from
+const LIBRARIES: [(&str, &str, &str); 3] = [
+    ("store_cache/legendary_library.json", "library", "Epic"),
+    ("store_cache/gog_library.json", "games", "GOG"),
+    ("store_cache/nile_library.json", "library", "Amazon"),
+];
+
+/// Games from Heroic Games Launcher's library caches (Epic, GOG and Amazon)
+pub struct HeroicImporter;
+
+impl Importer for HeroicImporter {
+    fn name(&self) -> &'static str {
+        "Heroic"
+    }
+
+    fn path_hint(&self) -> &'static str {
+        "Heroic config folder, e.g. ~/.config/heroic"
+    }
+
+    fn default_path(&self) -> Option<PathBuf> {
+        let home = home_dir()?;
+
+        [".config/heroic", ".var/app/com.heroicgameslauncher.hgl/config/heroic", "AppData/Roaming/heroic"] // Linux, Flatpak, Windows
+            .into_iter()
+            .map(|path| home.join(path))
+            .find(|path| path.join("store_cache").is_dir())
+    }
+
+    fn read(&self, path: &Path) -> Result<Vec<ImportCandidate>, anyhow::Error> {
+        read_heroic_library(path)
+    }
+}
+
+/// Play time (minutes) and last played date per app name, from store/timestamp.json
+fn play_data(config_dir: &Path) -> HashMap<String, (u64, Option<String>)>
+{
+    let Ok(text) = fs::read_to_string(config_dir.join("store/timestamp.json")) else { return HashMap::new() };
+    let Ok(Value::Object(apps)) = serde_json::from_str::<Value>(&text) else { return HashMap::new() };
+
+    apps.into_iter()
+        .map(|(app_name, data)| {
+            let minutes = data.get("totalPlayed").and_then(Value::as_f64).unwrap_or(0.0).max(0.0).round() as u64;
+            let last_played = data.get("lastPlayed").and_then(Value::as_str).and_then(iso_to_date);
+            (app_name, (minutes, last_played))
+        })
+        .collect()
+}
+
+/// Read every game in Heroic's library caches, adding play time from Heroic's own tracking
+pub fn read_heroic_library(config_dir: &Path) -> Result<Vec<ImportCandidate>, anyhow::Error>
+{
+    if !config_dir.join("store_cache").is_dir() {
+        return Err(anyhow!("No store_cache folder in {}", config_dir.display()))
+    }
+
+    let play_data = play_data(config_dir);
+    let mut candidates: Vec<ImportCandidate> = Vec::new();
+
+    for (file, list, storefront) in LIBRARIES {
+        let Ok(text) = fs::read_to_string(config_dir.join(file)) else { continue }; // Store not logged in
+        let json: Value = serde_json::from_str(&text)?;
+
+        for game in json.get(list).and_then(Value::as_array).into_iter().flatten() {
+            let Some(name) = game.get("title").and_then(Value::as_str).map(str::trim).filter(|name| !name.is_empty()) else { continue };
+
+            // DLC is listed alongside games in the Epic cache
+            if game.get("is_dlc").and_then(Value::as_bool).unwrap_or(false) {
+                continue;
+            }
+
+            let app_name = game.get("app_name").and_then(Value::as_str).unwrap_or_default();
+            let (minutes, last_played) = play_data.get(app_name).cloned().unwrap_or_default();
+
+            candidates.push(ImportCandidate {
+                name: name.to_string(),
+                platform: String::from("PC"),
+                storefront: storefront.to_string(),
+                play_time: Duration::from_secs(minutes.saturating_mul(60)),
+                last_played,
+                ..ImportCandidate::default()
+            });
+        }
+    }
+
+    candidates.sort_by_key(|candidate| candidate.name.to_lowercase());
+    Ok(candidates)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_fixture_library() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures/heroic");
+
+        let candidates = read_heroic_library(&path).unwrap();
+        let names: Vec<&str> = candidates.iter().map(|candidate| candidate.name.as_str()).collect();
+
+        assert_eq!(names, vec!["Control", "Death Stranding", "Disco Elysium"]); // DLC is left out
+
+        assert_eq!(candidates[0].storefront, "Amazon");
+        assert_eq!(candidates[1].storefront, "Epic");
+        assert_eq!(candidates[1].play_time, Duration::from_secs(754 * 60));
+        assert_eq!(candidates[1].last_played.as_deref(), Some("2024-05-01"));
+        assert_eq!(candidates[2].storefront, "GOG");
+        assert_eq!(candidates[2].play_time, Duration::ZERO);
+    }
+
+    #[test]
+    fn folder_without_caches_is_an_error() {
+        assert!(read_heroic_library(Path::new(env!("CARGO_MANIFEST_DIR")).join("src").as_path()).is_err());
+    }
+}
@@ -0,0 +1,94 @@
+// Crates/Imports
+use std::path::{Path, PathBuf};
+
+use crate::{enums::{GameStatus, Rating}, importers::{merge_duplicates, parse_clock_time, parse_date, rows::{read_rows, Row}, ImportCandidate, Importer}};
+
+/// Games from a HowLongToBeat CSV export
+pub struct HowLongToBeatImporter;
+
+impl Importer for HowLongToBeatImporter {
+    fn name(&self) -> &'static str {
+        "HowLongToBeat"
+    }
+
+    fn path_hint(&self) -> &'static str {
+        "Exported .csv file"
+    }
+
+    fn default_path(&self) -> Option<PathBuf> {
+        None
+    }
+
+    fn read(&self, path: &Path) -> Result<Vec<ImportCandidate>, anyhow::Error> {
+        read_hltb_export(path)
+    }
+}
+
+// HowLongToBeat uses a column per list, with anything in it meaning the game is on that list
+fn status(row: &Row) -> Option<GameStatus>
+{
+    [("Completed", GameStatus::Completed), ("Retired", GameStatus::Abandoned), ("Playing", GameStatus::Playing), ("Backlog", GameStatus::Backlog)]
+        .into_iter()
+        .find(|(column, _)| !row.get(&[column]).is_empty())
+        .map(|(_, status)| status)
+}
+
+/// Read a HowLongToBeat export. Reviews are scored out of 100, and each playthrough of a game has its own row
+pub fn read_hltb_export(path: &Path) -> Result<Vec<ImportCandidate>, anyhow::Error>
+{
+    let mut candidates = Vec::new();
+
+    for row in read_rows(path)? {
+        let name = row.get(&["Title", "Game"]);
+        if name.is_empty() {
+            continue;
+        }
+
+        let completed = parse_date(row.get(&["Completion Date", "Completed Date"]));
+        let started = parse_date(row.get(&["Start Date"]));
+
+        candidates.push(ImportCandidate {
+            name: name.to_string(),
+            platform: row.get(&["Platform"]).to_string(),
+            storefront: row.get(&["Storefront"]).to_string(),
+            play_time: parse_clock_time(row.get(&["Progress", "Main Story", "Main + Extras", "Completionist"])),
+            play_dates: completed.clone().or(started).into_iter().collect(),
+            last_played: completed,
+            rating: row.get(&["Review"]).parse::<u8>().ok().and_then(Rating::from_points), // 0 means not reviewed
+            status: status(&row),
+            notes: row.get(&["Review Notes", "General Notes"]).to_string(),
+            ..ImportCandidate::default()
+        });
+    }
+
+    let mut candidates = merge_duplicates(candidates);
+    candidates.sort_by_key(|candidate| candidate.name.to_lowercase());
+    Ok(candidates)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use super::*;
+
+    #[test]
+    fn reads_fixture_export() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures/hltb_export.csv");
+
+        let candidates = read_hltb_export(&path).unwrap();
+        let names: Vec<&str> = candidates.iter().map(|candidate| candidate.name.as_str()).collect();
+
+        assert_eq!(names, vec!["Hollow Knight", "Persona 5 Royal", "Tunic"]);
+
+        let hollow_knight = &candidates[0];
+        assert_eq!(hollow_knight.rating.map(|rating| rating.points()), Some(85));
+        assert_eq!(hollow_knight.status, Some(GameStatus::Completed));
+        assert_eq!(hollow_knight.play_time, Duration::from_secs(42 * 3600 + 30 * 60 + 20 * 3600)); // Both runs added together
+        assert_eq!(hollow_knight.play_dates, vec!["2021-08-14", "2023-02-02"]);
+        assert_eq!(hollow_knight.notes, "Steel Soul next time");
+
+        assert_eq!(candidates[1].status, Some(GameStatus::Playing));
+        assert_eq!(candidates[1].rating, None); // Review of 0
+        assert_eq!(candidates[2].status, Some(GameStatus::Abandoned));
+    }
+}
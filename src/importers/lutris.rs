@@ -0,0 +1,144 @@
+// Crates/Imports
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use anyhow::anyhow;
+use rusqlite::{Connection, OpenFlags};
+
+use crate::importers::{home_dir, unix_to_date, ImportCandidate, Importer};
+
+/// Games from Lutris's pga.db SQLite database
+pub struct LutrisImporter;
+
+impl Importer for LutrisImporter {
+    fn name(&self) -> &'static str {
+        "Lutris"
+    }
+
+    fn path_hint(&self) -> &'static str {
+        "pga.db file, e.g. ~/.local/share/lutris/pga.db"
+    }
+
+    fn default_path(&self) -> Option<PathBuf> {
+        let home = home_dir()?;
+
+        [".local/share/lutris/pga.db", ".var/app/net.lutris.Lutris/data/lutris/pga.db"] // Normal install, then Flatpak
+            .into_iter()
+            .map(|path| home.join(path))
+            .find(|path| path.is_file())
+    }
+
+    fn read(&self, path: &Path) -> Result<Vec<ImportCandidate>, anyhow::Error> {
+        read_lutris_database(path)
+    }
+}
+
+// Lutris names platforms after the operating system for native and Wine games, which are all PC here
+fn tidy_platform(platform: &str) -> String
+{
+    match platform {
+        "Linux" | "Windows" | "macOS" | "MS-DOS" => String::from("PC"),
+        other => other.strip_prefix("Sony ").unwrap_or(other).to_string(),
+    }
+}
+
+/// Read every installed or played game from pga.db. Play time is stored in hours, last played as a Unix timestamp
+pub fn read_lutris_database(path: &Path) -> Result<Vec<ImportCandidate>, anyhow::Error>
+{
+    if !path.is_file() {
+        return Err(anyhow!("{} doesn't exist", path.display())) // Opening would otherwise create an empty database
+    }
+
+    // Read only, Lutris may be running and using the file
+    let connection = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+
+    let mut statement = connection.prepare(
+        "SELECT name, platform, runner, lastplayed, playtime, service FROM games WHERE installed = 1 OR lastplayed > 0 OR playtime > 0"
+    )?;
+
+    let rows = statement.query_map([], |row| {
+        Ok((
+            row.get::<_, Option<String>>(0)?.unwrap_or_default(),
+            row.get::<_, Option<String>>(1)?.unwrap_or_default(),
+            row.get::<_, Option<String>>(2)?.unwrap_or_default(),
+            row.get::<_, Option<i64>>(3)?.unwrap_or(0),
+            row.get::<_, Option<f64>>(4)?.unwrap_or(0.0),
+            row.get::<_, Option<String>>(5)?.unwrap_or_default(),
+        ))
+    })?;
+
+    let mut candidates = Vec::new();
+
+    for row in rows {
+        let (name, platform, runner, last_played, hours, service) = row?;
+
+        if name.trim().is_empty() {
+            continue;
+        }
+
+        // Games without a platform (e.g. added from a store but never set up) fall back on what runs them
+        let platform = if platform.is_empty() && runner == "wine" { String::from("PC") } else { tidy_platform(&platform) };
+
+        candidates.push(ImportCandidate {
+            name: name.trim().to_string(),
+            platform,
+            storefront: match service.as_str() { // Games added from a store inside Lutris keep that store's name
+                "" | "lutris" => String::from("Lutris"),
+                "steam" => String::from("Steam"),
+                "gog" => String::from("GOG"),
+                "egs" => String::from("Epic"),
+                "humblebundle" => String::from("Humble"),
+                "itchio" => String::from("itch.io"),
+                other => other.to_string(),
+            },
+            play_time: Duration::from_secs((hours.max(0.0) * 3600.0).round() as u64),
+            last_played: if last_played > 0 { unix_to_date(last_played) } else { None },
+            ..ImportCandidate::default()
+        });
+    }
+
+    candidates.sort_by_key(|candidate| candidate.name.to_lowercase());
+    Ok(candidates)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Build a pga.db from the SQL fixture
+    fn fixture_database() -> PathBuf {
+        let path = std::env::temp_dir().join(format!("gamelog_lutris_{}.db", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let sql = std::fs::read_to_string(Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures/lutris_pga.sql")).unwrap();
+        Connection::open(&path).unwrap().execute_batch(&sql).unwrap();
+
+        path
+    }
+
+    #[test]
+    fn reads_fixture_database() {
+        let path = fixture_database();
+
+        let candidates = read_lutris_database(&path).unwrap();
+        let names: Vec<&str> = candidates.iter().map(|candidate| candidate.name.as_str()).collect();
+
+        assert_eq!(names, vec!["Celeste", "Disco Elysium", "Super Metroid"]); // The uninstalled, unplayed game is left out
+
+        assert_eq!(candidates[0].platform, "PC");
+        assert_eq!(candidates[0].storefront, "Lutris");
+        assert_eq!(candidates[0].play_time, Duration::from_secs(9000)); // 2.5 hours
+        assert_eq!(candidates[0].last_played.as_deref(), Some("2023-11-14"));
+
+        assert_eq!(candidates[1].storefront, "GOG");
+        assert_eq!(candidates[1].platform, "PC"); // From the wine runner
+        assert_eq!(candidates[2].platform, "Nintendo SNES");
+        assert_eq!(candidates[2].last_played, None);
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn missing_database_is_an_error() {
+        assert!(read_lutris_database(Path::new("/nonexistent/pga.db")).is_err());
+    }
+}
@@ -0,0 +1,571 @@
+pub mod vdf;
+pub mod steam;
+pub mod playnite;
+pub mod lutris;
+pub mod heroic;
+pub mod rows;
+pub mod backloggd;
+pub mod hltb;
+pub mod grouvee;
+pub mod retroarch;
+
+// Crates/Imports
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use chrono::{DateTime, NaiveDate};
+use eframe::egui::{self, Button, Checkbox, Color32, ComboBox, Label, RichText, TextEdit, Ui, Vec2};
+
+use crate::{app_setup::GameLog, clock::get_date, enums::{GameStatus, Rating, RatingScale}, json_file_operations::{search_for_game, Game, Playthrough}, notes::{redact_spoilers, shorten, SPOILER_REDACTED},
+    playtime::format_duration, star_rating::star_rating_input, validation::{validate, GameDraft}};
+
+/// Somewhere games can be imported from (a launcher's files, an export...).
+///
+/// Each source only has to turn a file or folder into candidates, the review screen and validation are shared
+pub trait Importer {
+    /// Name shown in the source drop down
+    fn name(&self) -> &'static str;
+
+    /// Tells users what path to enter
+    fn path_hint(&self) -> &'static str;
+
+    /// Where the files usually are, if they can be found
+    fn default_path(&self) -> Option<PathBuf>;
+
+    fn read(&self, path: &Path) -> Result<Vec<ImportCandidate>, anyhow::Error>;
+}
+
+/// Every import source, in the order they appear in the import window
+pub fn all_importers() -> Vec<Box<dyn Importer>>
+{
+    vec![
+        Box::new(steam::SteamImporter),
+        Box::new(playnite::PlayniteImporter),
+        Box::new(lutris::LutrisImporter),
+        Box::new(heroic::HeroicImporter),
+        Box::new(backloggd::BackloggdImporter),
+        Box::new(hltb::HowLongToBeatImporter),
+        Box::new(grouvee::GrouveeImporter),
+        Box::new(retroarch::RetroArchImporter),
+    ]
+}
+
+/// A game found by an importer, waiting for the user to decide whether it goes into the log
+#[derive(Clone, Default, Debug)]
+pub struct ImportCandidate {
+    pub name: String,
+    pub platform: String,
+    pub storefront: String,
+    pub play_time: Duration,
+    pub last_played: Option<String>, // YYYY-MM-DD
+    pub play_count: u32, // 0 if the source doesn't know
+    pub play_dates: Vec<String>, // YYYY-MM-DD, one per playthrough the source knows about
+    pub rating: Option<Rating>, // Already converted from the source's own scale
+    pub status: Option<GameStatus>,
+    pub notes: String, // Reviews etc.
+    pub selected: bool,
+    pub already_in_log: bool, // search_for_game already finds it, so it's skipped
+}
+
+impl ImportCandidate {
+    /// Turn the candidate into a draft so it goes through the same validation as games added by hand.
+    ///
+    /// `rating` is only used when the source didn't have a rating of its own
+    pub fn to_draft(&self, rating: &str, rating_scale: RatingScale) -> GameDraft
+    {
+        let mut dates = self.play_dates.clone();
+        dates.sort();
+
+        // Sources that only know when a game was last played still get one playthrough for it
+        if dates.is_empty() && (self.last_played.is_some() || !self.play_time.is_zero()) {
+            dates.push(self.last_played.clone().unwrap_or_else(|| get_date().to_string()));
+        }
+
+        let last_playthrough = dates.last().cloned().max(self.last_played.clone()).unwrap_or_else(|| get_date().to_string());
+
+        // Play time isn't split up by the sources, so it all goes on the latest playthrough
+        let playthroughs: Vec<Playthrough> = dates.iter().enumerate()
+            .map(|(i, date)| Playthrough {
+                date: date.clone(),
+                platform: self.platform.clone(),
+                storefront: self.storefront.clone(),
+                play_time: if i + 1 == dates.len() { self.play_time } else { Duration::ZERO },
+                note: String::new(),
+            })
+            .collect();
+
+        // The source's own rating is passed through as points so nothing is lost rounding it to the user's scale
+        let (rating, rating_scale) = match self.rating {
+            Some(own_rating) => (own_rating.points().to_string(), RatingScale::HundredPoints),
+            None => (rating.to_string(), rating_scale),
+        };
+
+        GameDraft {
+            name: self.name.clone(),
+            rating,
+            rating_scale,
+            times_played: match self.play_count {
+                0 => playthroughs.len().try_into().unwrap_or(u32::MAX), // Owned but never started games count as unplayed
+                count => count,
+            },
+            last_playthrough,
+            notes: self.notes.clone(),
+            platform: self.platform.clone(),
+            storefront: self.storefront.clone(),
+            playthroughs,
+            play_time: self.play_time,
+            status: self.status,
+            ..GameDraft::default()
+        }
+    }
+}
+
+/// Unix timestamp to a YYYY-MM-DD date (the format the log uses for dates)
+pub fn unix_to_date(seconds: i64) -> Option<String>
+{
+    DateTime::from_timestamp(seconds, 0).map(|date| date.date_naive().to_string())
+}
+
+/// Date part of an ISO 8601 date/time ("2024-03-09T18:22:10Z" -> "2024-03-09")
+pub fn iso_to_date(text: &str) -> Option<String>
+{
+    let date = text.get(..10)?;
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").ok().map(|date| date.to_string())
+}
+
+/// Read the date formats export files use (2024-03-09, 2024-03-09T18:22:10Z, 03/09/2024, Mar 09, 2024) as YYYY-MM-DD
+pub fn parse_date(text: &str) -> Option<String>
+{
+    let text = text.trim();
+
+    iso_to_date(text).or_else(|| {
+        ["%m/%d/%Y", "%Y/%m/%d", "%b %d, %Y", "%B %d, %Y"].iter()
+            .find_map(|format| NaiveDate::parse_from_str(text, format).ok())
+            .map(|date| date.to_string())
+    })
+}
+
+/// Read play time written as "HH:MM:SS" (or "HH:MM"), with hours allowed past 24. Anything else (Including times too big to add up) counts as no time
+pub fn parse_clock_time(text: &str) -> Duration
+{
+    let parts: Vec<u64> = text.split(':').map_while(|part| part.trim().parse().ok()).collect();
+
+    let total_seconds = |hours: u64, minutes: u64, seconds: u64| {
+        hours.checked_mul(3600)?.checked_add(minutes.checked_mul(60)?)?.checked_add(seconds)
+    };
+
+    let seconds = match parts[..] {
+        [hours, minutes, seconds] => total_seconds(hours, minutes, seconds),
+        [hours, minutes] => total_seconds(hours, minutes, 0),
+        _ => None,
+    };
+    seconds.map_or(Duration::ZERO, Duration::from_secs)
+}
+
+/// Some exports have a row per playthrough, so rows for the same game are folded into one candidate
+pub fn merge_duplicates(candidates: Vec<ImportCandidate>) -> Vec<ImportCandidate>
+{
+    let mut merged: Vec<ImportCandidate> = Vec::new();
+
+    for candidate in candidates {
+        match merged.iter_mut().find(|existing| existing.name.eq_ignore_ascii_case(&candidate.name)) {
+            Some(existing) => {
+                existing.play_time += candidate.play_time;
+                existing.play_dates.extend(candidate.play_dates);
+                existing.last_played = existing.last_played.take().max(candidate.last_played);
+                existing.rating = existing.rating.or(candidate.rating);
+                existing.status = existing.status.or(candidate.status);
+
+                if existing.notes.is_empty() {
+                    existing.notes = candidate.notes;
+                }
+                if existing.platform.is_empty() {
+                    existing.platform = candidate.platform;
+                }
+            },
+            None => merged.push(candidate),
+        }
+    }
+
+    merged
+}
+
+/// What importing one candidate would do, worked out before anything is saved
+pub struct ImportPreview {
+    pub name: String,
+    pub result: Result<Game, String>, // The game that would be added, or why it would be skipped
+}
+
+/// Home folder (Used to find launchers' default folders)
+pub fn home_dir() -> Option<PathBuf>
+{
+    std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE")).map(PathBuf::from)
+}
+
+/// Path typed into the import window, with a leading `~` meaning the home folder like the path hints show
+pub fn expand_home(path: &str) -> PathBuf
+{
+    let path = path.trim();
+
+    let rest = match path.strip_prefix('~') {
+        Some("") => "",
+        Some(rest) if rest.starts_with(['/', '\\']) => &rest[1..],
+        _ => return PathBuf::from(path), // "~user" style paths aren't supported
+    };
+
+    match home_dir() {
+        Some(home) if rest.is_empty() => home,
+        Some(home) => home.join(rest),
+        None => PathBuf::from(path),
+    }
+}
+
+/// One line summary of an imported game for the dry run, e.g. "★★★★☆ · Completed · 2 playthrough(s) · 12h 00m · PC · Steam"
+pub fn describe_import(game: &Game, rating_scale: RatingScale) -> String
+{
+    let mut parts = vec![rating_scale.display(game.rating)];
+
+    if let Some(status) = game.status {
+        parts.push(status.to_string());
+    }
+    parts.push(format!("{} playthrough(s)", game.playthroughs.len()));
+    if !game.play_time.is_zero() {
+        parts.push(format_duration(game.play_time));
+    }
+    parts.extend([&game.platform, &game.storefront].into_iter().filter(|text| !text.is_empty()).cloned());
+    if !game.notes.is_empty() {
+        parts.push(format!("Notes: {}", shorten(&redact_spoilers(&game.notes, SPOILER_REDACTED), 40)));
+    }
+
+    parts.join(" · ")
+}
+
+/// Flag candidates that are already in the log (and untick them), tick everything else
+pub fn mark_existing(candidates: &mut [ImportCandidate], game_log: &[Game])
+{
+    for candidate in candidates.iter_mut() {
+        candidate.already_in_log = search_for_game(game_log, &candidate.name).is_ok();
+        candidate.selected = !candidate.already_in_log;
+    }
+}
+
+// 'Import' Window GUI Code (Review screen for picking which found games go into the log)
+impl GameLog {
+    pub fn import_gui (&mut self, ui: &mut Ui)
+    {
+        let label_size = Vec2::new(100.0, 20.0);
+
+        ui.add_space(5.0);
+
+        let importers = all_importers();
+
+        ui.horizontal(|ui| {
+            ui.add_sized(label_size, Label::new(RichText::new("Import From").strong()));
+
+            let previous_source = self.import_source;
+
+            ComboBox::from_id_salt("import_source")
+                .selected_text(importers[self.import_source].name())
+                .show_ui(ui, |ui| {
+                    for (i, importer) in importers.iter().enumerate() {
+                        ui.selectable_value(&mut self.import_source, i, importer.name());
+                    }
+                });
+
+            // New source, so the old path and results don't apply any more
+            if previous_source != self.import_source {
+                self.import_source_path = importers[self.import_source].default_path().map(|path| path.display().to_string()).unwrap_or_default();
+                self.import_candidates.clear();
+                self.import_preview.clear();
+                self.import_feedback_message.clear();
+            }
+        });
+
+        let importer = &importers[self.import_source];
+
+        ui.horizontal(|ui| {
+            ui.add_sized(label_size, Label::new(RichText::new("Path").strong()));
+
+            ui.add_sized(Vec2::new(250.0, 20.0), TextEdit::singleline(&mut self.import_source_path)
+                .hint_text(importer.path_hint()));
+
+            if ui.button("Scan").clicked() {
+                self.error_confirmation = true;
+
+                match importer.read(&expand_home(&self.import_source_path)) {
+                    Ok(mut candidates) => {
+                        mark_existing(&mut candidates, &self.game_file_contents);
+                        self.import_feedback_message = format!("Found {} game(s)", candidates.len());
+                        self.error_confirmation = false;
+                        self.import_candidates = candidates;
+                        self.import_preview.clear();
+                    },
+                    Err(error) => {
+                        self.import_feedback_message = format!("Couldn't read {} library: {}", importer.name(), error);
+                        self.import_candidates.clear();
+                    }
+                }
+            }
+        });
+
+        ui.add_space(5.0);
+
+        if !self.import_feedback_message.is_empty() {
+            ui.label(RichText::new(&self.import_feedback_message).color(
+                if self.error_confirmation {
+                    Color32::RED
+                }
+                else if self.dark_mode {
+                    Color32::GREEN
+                }
+                else {
+                    Color32::DARK_GREEN
+                }
+            ));
+        }
+
+        if !self.import_preview.is_empty() {
+            self.import_preview_gui(ui);
+            return
+        }
+
+        if self.import_candidates.is_empty() {
+            return
+        }
+
+        ui.horizontal(|ui| {
+            if ui.button("Select All").clicked() {
+                self.import_candidates.iter_mut().filter(|candidate| !candidate.already_in_log).for_each(|candidate| candidate.selected = true);
+            }
+            if ui.button("Select None").clicked() {
+                self.import_candidates.iter_mut().for_each(|candidate| candidate.selected = false);
+            }
+
+            // Re-scanning a source picks up new games, this brings the ones already imported up to date
+            let behind = self.games_behind_source().len();
+            if behind > 0 && ui.button(format!("Sync Play Time of {} Logged Game(s)", behind)).clicked() {
+                self.sync_play_time();
+            }
+        });
+
+        ui.add_space(5.0);
+
+        egui::ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+            egui::Grid::new("import_candidates").striped(true).num_columns(5).show(ui, |ui| {
+                ui.label("");
+                ui.label(RichText::new("Game").strong());
+                ui.label(RichText::new("Status").strong());
+                ui.label(RichText::new("Play Time").strong());
+                ui.label(RichText::new("Last Played").strong());
+                ui.end_row();
+
+                for candidate in self.import_candidates.iter_mut() {
+                    ui.add_enabled(!candidate.already_in_log, Checkbox::without_text(&mut candidate.selected));
+
+                    if candidate.already_in_log {
+                        ui.label(RichText::new(format!("{} (Already in log)", candidate.name)).weak());
+                    }
+                    else {
+                        ui.label(&candidate.name);
+                    }
+
+                    ui.label(candidate.status.map(|status| status.to_string()).unwrap_or_default());
+                    ui.label(format_duration(candidate.play_time));
+                    ui.label(candidate.last_played.as_deref().or(candidate.play_dates.iter().max().map(String::as_str)).unwrap_or("Never"));
+                    ui.end_row();
+                }
+            });
+        });
+
+        ui.add_space(10.0);
+
+        // Ratings are required, so games the source didn't rate all start with the same one which can be changed later
+        ui.horizontal(|ui| {
+            ui.add_sized(label_size, Label::new(RichText::new("Rating For Unrated").strong()));
+            star_rating_input(ui, &mut self.import_rating, self.settings.rating_scale);
+        });
+
+        ui.add_space(5.0);
+
+        let selected = self.import_candidates.iter().filter(|candidate| candidate.selected).count();
+
+        if ui.add_enabled(selected > 0, Button::new(format!("Preview Import of {} Game(s)", selected)).min_size(Vec2::new(150.0, 20.0))).clicked() {
+            self.import_preview = self.preview_import();
+            self.import_feedback_message.clear();
+        }
+    }
+
+    /// Dry run of the import: every game that would be added (with what it would be added with) and every one that would be skipped
+    fn import_preview_gui(&mut self, ui: &mut Ui)
+    {
+        ui.label(RichText::new("Nothing has been added yet. Check the changes below, then confirm").strong());
+
+        ui.add_space(5.0);
+
+        egui::ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+            for preview in &self.import_preview {
+                match &preview.result {
+                    Ok(game) => {
+                        ui.label(RichText::new(format!("+ {}", game.name)).color(if self.dark_mode { Color32::GREEN } else { Color32::DARK_GREEN }));
+                        ui.label(RichText::new(describe_import(game, self.settings.rating_scale)).small().weak());
+                    },
+                    Err(reason) => {
+                        ui.label(RichText::new(format!("✖ {} ({})", preview.name, reason)).color(Color32::RED));
+                    },
+                }
+            }
+        });
+
+        ui.add_space(10.0);
+
+        let adding = self.import_preview.iter().filter(|preview| preview.result.is_ok()).count();
+
+        ui.horizontal(|ui| {
+            if ui.add_enabled(adding > 0, Button::new(format!("Confirm: Add {} Game(s)", adding))).clicked() {
+                self.confirm_import();
+            }
+            if ui.button("Back").clicked() {
+                self.import_preview.clear();
+            }
+        });
+    }
+
+    // Games in the log that the source has more play time for, paired with the candidate they match
+    fn games_behind_source(&self) -> Vec<(usize, &ImportCandidate)>
+    {
+        self.import_candidates.iter()
+            .filter(|candidate| candidate.already_in_log)
+            .filter_map(|candidate| search_for_game(&self.game_file_contents, &candidate.name).ok().map(|i| (i, candidate)))
+            .filter(|(i, candidate)| candidate.play_time > self.game_file_contents[*i].play_time)
+            .collect()
+    }
+
+    /// Top up the play time (and last played date) of games already in the log from the latest scan
+    fn sync_play_time(&mut self)
+    {
+        self.error_confirmation = true;
+
+        let updates: Vec<(usize, Duration, Option<String>)> = self.games_behind_source().into_iter()
+            .map(|(i, candidate)| (i, candidate.play_time - self.game_file_contents[i].play_time, candidate.last_played.clone()))
+            .collect();
+
+        let result = self.apply_change("Sync play time", |game_log| {
+            for (i, extra, last_played) in &updates {
+                let game = &mut game_log.game_file_contents[*i];
+                game.play_time += *extra;
+
+                // The new time was played since the latest playthrough was logged, so it goes on that one
+                if let Some(playthrough) = game.playthroughs.last_mut() {
+                    playthrough.play_time += *extra;
+                }
+                if let Some(date) = last_played.as_ref().filter(|date| **date > game.last_playthrough) {
+                    game.last_playthrough = date.clone();
+                }
+            }
+            Ok(())
+        });
+
+        self.import_feedback_message = match result {
+            Ok(_) => {
+                self.error_confirmation = false;
+                format!("Updated play time of {} game(s)", updates.len())
+            },
+            Err(error) => error,
+        };
+    }
+
+    /// Validate every ticked candidate without changing the log
+    fn preview_import(&self) -> Vec<ImportPreview>
+    {
+        let mut previews: Vec<ImportPreview> = Vec::new();
+
+        for candidate in self.import_candidates.iter().filter(|candidate| candidate.selected) {
+            let draft = candidate.to_draft(&self.import_rating, self.settings.rating_scale);
+
+            let result = match validate(&draft) {
+                Ok(game) if search_for_game(&self.game_file_contents, &game.name).is_ok() => Err(String::from("Already in the log")),
+                Ok(game) if previews.iter().any(|preview| preview.result.as_ref().is_ok_and(|added| added.name.eq_ignore_ascii_case(&game.name))) => {
+                    Err(String::from("Listed twice"))
+                },
+                Ok(game) => Ok(game),
+                Err(errors) => Err(errors[0].message.clone()),
+            };
+
+            previews.push(ImportPreview { name: candidate.name.clone(), result });
+        }
+
+        previews
+    }
+
+    /// Add everything the dry run said would be added, then save once
+    fn confirm_import(&mut self)
+    {
+        self.error_confirmation = true;
+
+        let previews = std::mem::take(&mut self.import_preview);
+        let skipped = previews.iter().filter(|preview| preview.result.is_err()).count();
+        let games: Vec<Game> = previews.into_iter().filter_map(|preview| preview.result.ok()).collect();
+        let imported = games.len();
+
+        // One change for the whole import, so it can be undone in one go
+        if let Err(error) = self.apply_change(&format!("Import {} game(s)", imported), |game_log| {
+            game_log.game_file_contents.extend(games);
+            Ok(())
+        }) {
+            self.import_feedback_message = error;
+            return
+        }
+
+        mark_existing(&mut self.import_candidates, &self.game_file_contents); // Imported games now show as already in the log
+
+        self.error_confirmation = false;
+        self.import_feedback_message = if skipped == 0 {
+            format!("Imported {} game(s)", imported)
+        }
+        else {
+            format!("Imported {} game(s), skipped {}", imported, skipped)
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_times() {
+        assert_eq!(parse_clock_time("105:04:09"), Duration::from_secs(105 * 3600 + 4 * 60 + 9));
+        assert_eq!(parse_clock_time("2:30"), Duration::from_secs(9000));
+        assert_eq!(parse_clock_time(" 1 : 00 : 30 "), Duration::from_secs(3630));
+        assert_eq!(parse_clock_time("--"), Duration::ZERO);
+        assert_eq!(parse_clock_time("12"), Duration::ZERO);
+        assert_eq!(parse_clock_time("99999999999999999:00"), Duration::ZERO); // Overflows as seconds
+    }
+
+    #[test]
+    fn paths_starting_with_a_tilde_are_in_the_home_folder() {
+        let home = home_dir().unwrap();
+
+        assert_eq!(expand_home(" ~/.local/share/Steam "), home.join(".local/share/Steam"));
+        assert_eq!(expand_home("~"), home);
+        assert_eq!(expand_home("/games/~backup"), PathBuf::from("/games/~backup"));
+        assert_eq!(expand_home("~someone/games"), PathBuf::from("~someone/games"));
+    }
+
+    #[test]
+    fn long_notes_are_cut_without_splitting_characters() {
+        let candidate = ImportCandidate {
+            name: String::from("Okami"),
+            platform: String::from("PC"),
+            notes: format!("{}ōkami 大神 🐺 is still great", "é".repeat(35)), // Multibyte characters either side of the cut
+            play_time: Duration::from_secs(3600),
+            last_played: Some(String::from("2024-03-09")),
+            rating: Rating::from_points(80),
+            ..ImportCandidate::default()
+        };
+        let game = validate(&candidate.to_draft("", RatingScale::FiveStars)).unwrap();
+
+        let description = describe_import(&game, RatingScale::HundredPoints);
+        let notes = format!("Notes: {}ōkami...", "é".repeat(35));
+        assert_eq!(description, format!("80/100 · 1 playthrough(s) · 1h 00m · PC · {}", notes));
+    }
+}
@@ -0,0 +1,122 @@
+// Crates/Imports
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use anyhow::anyhow;
+use serde_json::Value;
+
+use crate::importers::{iso_to_date, ImportCandidate, Importer};
+
+/// Games from a Playnite library exported to JSON (an array of game objects with Playnite's field names)
+pub struct PlayniteImporter;
+
+impl Importer for PlayniteImporter {
+    fn name(&self) -> &'static str {
+        "Playnite"
+    }
+
+    fn path_hint(&self) -> &'static str {
+        "Exported .json file"
+    }
+
+    fn default_path(&self) -> Option<PathBuf> {
+        None // Exports can be saved anywhere
+    }
+
+    fn read(&self, path: &Path) -> Result<Vec<ImportCandidate>, anyhow::Error> {
+        read_playnite_export(path)
+    }
+}
+
+// Playnite stores platforms and sources either as plain names or as {"Id": .., "Name": ..} objects depending on the export
+fn name_of(value: &Value) -> Option<&str>
+{
+    match value {
+        Value::String(name) => Some(name),
+        Value::Object(_) => value.get("Name").and_then(Value::as_str),
+        _ => None,
+    }
+}
+
+// Playnite's platform names are long ("PC (Windows)", "Sony PlayStation 2"), so tidy up the common ones
+fn tidy_platform(platform: &str) -> String
+{
+    if platform.starts_with("PC (") {
+        return String::from("PC") // "PC (Windows)", "PC (Linux)"...
+    }
+    platform.strip_prefix("Sony ").unwrap_or(platform).to_string()
+}
+
+/// Read a Playnite JSON export. Play time is saved in seconds, last played as an ISO date/time
+pub fn read_playnite_export(path: &Path) -> Result<Vec<ImportCandidate>, anyhow::Error>
+{
+    let text = fs::read_to_string(path)?;
+    let json: Value = serde_json::from_str(&text)?;
+
+    // Either a bare array, or wrapped in an object by some export add-ons
+    let games = json.as_array().or_else(|| json.get("Games").and_then(Value::as_array))
+        .ok_or_else(|| anyhow!("No list of games in {}", path.display()))?;
+
+    let mut candidates: Vec<ImportCandidate> = games.iter()
+        .filter_map(|game| {
+            let name = game.get("Name").and_then(Value::as_str)?.trim();
+            if name.is_empty() {
+                return None
+            }
+
+            let platform = game.get("Platforms").and_then(Value::as_array)
+                .and_then(|platforms| platforms.iter().find_map(name_of))
+                .or_else(|| game.get("Platform").and_then(name_of))
+                .map(tidy_platform)
+                .unwrap_or_default();
+
+            Some(ImportCandidate {
+                name: name.to_string(),
+                platform,
+                storefront: game.get("Source").and_then(name_of).unwrap_or("Playnite").to_string(),
+                play_time: Duration::from_secs(game.get("Playtime").and_then(Value::as_u64).unwrap_or(0)),
+                last_played: game.get("LastActivity").and_then(Value::as_str).and_then(iso_to_date),
+                play_count: game.get("PlayCount").and_then(Value::as_u64).unwrap_or(0) as u32,
+                ..ImportCandidate::default()
+            })
+        })
+        .collect();
+
+    candidates.sort_by_key(|candidate| candidate.name.to_lowercase());
+    Ok(candidates)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_fixture_export() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures/playnite_export.json");
+
+        let candidates = read_playnite_export(&path).unwrap();
+        let names: Vec<&str> = candidates.iter().map(|candidate| candidate.name.as_str()).collect();
+
+        assert_eq!(names, vec!["Hades", "Okami HD", "Shadow of the Colossus"]); // The nameless entry is left out
+
+        assert_eq!(candidates[0].platform, "PC");
+        assert_eq!(candidates[0].storefront, "Epic");
+        assert_eq!(candidates[0].play_time, Duration::from_secs(90_000));
+        assert_eq!(candidates[0].last_played.as_deref(), Some("2024-02-11"));
+        assert_eq!(candidates[0].play_count, 12);
+
+        assert_eq!(candidates[2].platform, "PlayStation 2"); // Plain string platforms and sources work too
+        assert_eq!(candidates[2].storefront, "Playnite");
+        assert_eq!(candidates[2].last_played, None);
+    }
+
+    #[test]
+    fn rejects_files_without_games() {
+        let path = std::env::temp_dir().join(format!("gamelog_playnite_{}.json", std::process::id()));
+        fs::write(&path, r#"{"Version": 1}"#).unwrap();
+
+        assert!(read_playnite_export(&path).is_err());
+
+        fs::remove_file(path).unwrap();
+    }
+}
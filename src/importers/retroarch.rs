@@ -0,0 +1,205 @@
+// Crates/Imports
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use anyhow::anyhow;
+use serde_json::Value;
+
+use crate::importers::{home_dir, iso_to_date, merge_duplicates, parse_clock_time, ImportCandidate, Importer};
+
+const HISTORY_PLAYLIST: &str = "content_history.lpl";
+
+/// Games from RetroArch's playlists, with play time from its runtime logs
+pub struct RetroArchImporter;
+
+impl Importer for RetroArchImporter {
+    fn name(&self) -> &'static str {
+        "RetroArch"
+    }
+
+    fn path_hint(&self) -> &'static str {
+        "RetroArch config folder, e.g. ~/.config/retroarch"
+    }
+
+    fn default_path(&self) -> Option<PathBuf> {
+        let mut roots = Vec::new();
+
+        if let Some(home) = home_dir() {
+            roots.push(home.join(".config/retroarch"));
+            roots.push(home.join(".var/app/org.libretro.RetroArch/config/retroarch")); // Flatpak
+        }
+        if let Some(app_data) = std::env::var_os("APPDATA") {
+            roots.push(PathBuf::from(app_data).join("RetroArch"));
+        }
+
+        roots.into_iter().find(|root| root.join("playlists").is_dir())
+    }
+
+    fn read(&self, path: &Path) -> Result<Vec<ImportCandidate>, anyhow::Error> {
+        read_retroarch(path)
+    }
+}
+
+/// Turn a database name ("Nintendo - Super Nintendo Entertainment System.lpl") into a platform name
+fn platform_from_database(database: &str) -> String
+{
+    let system = database.trim_end_matches(".lpl");
+    let system = system.split_once(" - ").map(|(_, system)| system).unwrap_or(system);
+
+    // The long names most people shorten
+    match system {
+        "Nintendo Entertainment System" => String::from("NES"),
+        "Super Nintendo Entertainment System" => String::from("SNES"),
+        "Nintendo 64" => String::from("N64"),
+        "Game Boy Advance" => String::from("GBA"),
+        "Mega Drive - Genesis" => String::from("Mega Drive"),
+        other => other.to_string(),
+    }
+}
+
+/// ROM names carry region and version tags, e.g. "Super Metroid (USA, Europe) [!]" -> "Super Metroid"
+fn clean_title(label: &str) -> String
+{
+    let end = [" (", " ["].iter().filter_map(|tag| label.find(tag)).min().unwrap_or(label.len());
+
+    label[..end].trim().to_string()
+}
+
+// File name without extension, which is what RetroArch names runtime logs after
+fn content_stem(path: &str) -> String
+{
+    let file_name = path.rsplit(['/', '\\']).next().unwrap_or(path);
+    let file_name = file_name.split('#').next().unwrap_or(file_name); // Files inside archives are written as "game.zip#game.sfc"
+    file_name.rsplit_once('.').map(|(stem, _)| stem).unwrap_or(file_name).to_string()
+}
+
+/// Total runtime and latest last played date per content file, from every .lrtl runtime log.
+///
+/// With per-core logging each core gets its own folder, so the same game can have a log for every core it was run with
+fn runtime_logs(logs_dir: &Path) -> HashMap<String, (Duration, Option<String>)>
+{
+    let mut runtimes: HashMap<String, (Duration, Option<String>)> = HashMap::new();
+    let mut folders = vec![logs_dir.to_path_buf()];
+
+    while let Some(folder) = folders.pop() {
+        let Ok(entries) = fs::read_dir(&folder) else { continue };
+
+        for path in entries.filter_map(|entry| entry.ok()).map(|entry| entry.path()) {
+            if path.is_dir() {
+                folders.push(path); // Core folders
+                continue;
+            }
+            if path.extension().is_none_or(|extension| extension != "lrtl") {
+                continue;
+            }
+
+            let Ok(text) = fs::read_to_string(&path) else { continue };
+            let Ok(log) = serde_json::from_str::<Value>(&text) else { continue };
+
+            let runtime = parse_clock_time(log.get("runtime").and_then(Value::as_str).unwrap_or_default());
+            let last_played = log.get("last_played").and_then(Value::as_str).and_then(iso_to_date);
+
+            let stem = path.file_stem().map(|stem| stem.to_string_lossy().to_string()).unwrap_or_default();
+            let entry = runtimes.entry(stem).or_default();
+            entry.0 += runtime;
+            entry.1 = entry.1.take().max(last_played);
+        }
+    }
+
+    runtimes
+}
+
+/// Read every game in RetroArch's playlists (content history included), adding play time from the runtime logs.
+///
+/// Scanning again later picks up ROMs added since, and play time for games already in the log can be synced from the same scan
+pub fn read_retroarch(config_dir: &Path) -> Result<Vec<ImportCandidate>, anyhow::Error>
+{
+    let playlists_dir = config_dir.join("playlists");
+    let entries = fs::read_dir(&playlists_dir).map_err(|_| anyhow!("No playlists folder in {}", config_dir.display()))?;
+
+    let mut playlists: Vec<PathBuf> = entries.filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "lpl"))
+        .collect();
+
+    // System playlists come first so their platform wins over the history's (which usually has none)
+    playlists.sort_by_key(|path| (path.ends_with(HISTORY_PLAYLIST), path.clone()));
+    let old_history = config_dir.join(HISTORY_PLAYLIST); // Older versions keep the history next to the config file
+    if old_history.is_file() {
+        playlists.push(old_history);
+    }
+
+    let runtimes = runtime_logs(&playlists_dir.join("logs"));
+    let mut seen_files: Vec<String> = Vec::new();
+    let mut candidates = Vec::new();
+
+    for playlist in playlists {
+        let Ok(text) = fs::read_to_string(&playlist) else { continue };
+        let Ok(json) = serde_json::from_str::<Value>(&text) else { continue }; // Very old playlists weren't JSON
+
+        let playlist_name = playlist.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+
+        for item in json.get("items").and_then(Value::as_array).into_iter().flatten() {
+            let content_path = item.get("path").and_then(Value::as_str).unwrap_or_default();
+            let stem = content_stem(content_path);
+
+            if content_path.is_empty() || seen_files.contains(&stem) {
+                continue;
+            }
+
+            let label = item.get("label").and_then(Value::as_str).filter(|label| !label.trim().is_empty()).unwrap_or(&stem);
+            let database = item.get("db_name").and_then(Value::as_str).filter(|database| !database.is_empty())
+                .or(Some(playlist_name.as_str()).filter(|name| *name != HISTORY_PLAYLIST));
+
+            let (play_time, last_played) = runtimes.get(&stem).cloned().unwrap_or_default();
+
+            candidates.push(ImportCandidate {
+                name: clean_title(label),
+                platform: database.map(platform_from_database).unwrap_or_default(),
+                storefront: String::from("RetroArch"),
+                play_time,
+                last_played,
+                ..ImportCandidate::default()
+            });
+            seen_files.push(stem);
+        }
+    }
+
+    // Several ROMs of one game (different regions, discs...) become one game with all their time added up
+    let mut candidates = merge_duplicates(candidates.into_iter().filter(|candidate| !candidate.name.is_empty()).collect());
+    candidates.sort_by_key(|candidate| candidate.name.to_lowercase());
+    Ok(candidates)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_fixture_playlists_and_runtime_logs() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures/retroarch");
+
+        let candidates = read_retroarch(&path).unwrap();
+        let names: Vec<&str> = candidates.iter().map(|candidate| candidate.name.as_str()).collect();
+
+        assert_eq!(names, vec!["Castlevania - Symphony of the Night", "Super Metroid", "Tetris"]);
+
+        let super_metroid = &candidates[1];
+        assert_eq!(super_metroid.platform, "SNES");
+        assert_eq!(super_metroid.storefront, "RetroArch");
+        assert_eq!(super_metroid.play_time, Duration::from_secs(2 * 3600 + 15 * 60 + 30 * 60)); // Logs from two cores
+        assert_eq!(super_metroid.last_played.as_deref(), Some("2024-04-02"));
+
+        assert_eq!(candidates[0].platform, "PlayStation"); // Platform from the system playlist, not the history
+        assert_eq!(candidates[2].platform, "Game Boy"); // Only in the history, which has its database name
+        assert_eq!(candidates[2].play_time, Duration::ZERO);
+    }
+
+    #[test]
+    fn cleans_rom_names() {
+        assert_eq!(clean_title("Super Metroid (Japan, USA) (En,Ja) [!]"), "Super Metroid");
+        assert_eq!(clean_title("Tetris"), "Tetris");
+        assert_eq!(content_stem("/roms/gb/Tetris (World).zip#Tetris (World).gb"), "Tetris (World)");
+    }
+}
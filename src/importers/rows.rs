@@ -0,0 +1,67 @@
+// Crates/Imports
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use anyhow::anyhow;
+use serde_json::Value;
+
+/// One row of a site's export, looked up by column name.
+///
+/// Sites rename and reorder their columns now and then, so columns are found by name (ignoring case) rather than position
+#[derive(Debug, Default)]
+pub struct Row(HashMap<String, String>);
+
+impl Row {
+    /// The first of the given columns that has something in it (Empty if none do)
+    pub fn get(&self, columns: &[&str]) -> &str
+    {
+        columns.iter()
+            .filter_map(|column| self.0.get(&column.to_lowercase()))
+            .map(|value| value.trim())
+            .find(|value| !value.is_empty())
+            .unwrap_or_default()
+    }
+}
+
+/// Read an export as rows. CSV files need a header line, JSON files need to be an array of objects
+pub fn read_rows(path: &Path) -> Result<Vec<Row>, anyhow::Error>
+{
+    let text = fs::read_to_string(path)?;
+    let text = text.trim_start_matches('\u{feff}'); // Spreadsheet programs like to add a byte order mark
+
+    if path.extension().is_some_and(|extension| extension.eq_ignore_ascii_case("json")) {
+        return json_rows(text)
+    }
+
+    let mut reader = csv::ReaderBuilder::new().flexible(true).from_reader(text.as_bytes());
+    let headers: Vec<String> = reader.headers()?.iter().map(|header| header.trim().to_lowercase()).collect();
+
+    let mut rows = Vec::new();
+    for record in reader.records() {
+        let record = record?;
+        rows.push(Row(headers.iter().cloned().zip(record.iter().map(str::to_string)).collect()));
+    }
+
+    Ok(rows)
+}
+
+fn json_rows(text: &str) -> Result<Vec<Row>, anyhow::Error>
+{
+    let json: Value = serde_json::from_str(text)?;
+    let objects = json.as_array().ok_or_else(|| anyhow!("Expected a list of games"))?;
+
+    Ok(objects.iter()
+        .filter_map(Value::as_object)
+        .map(|object| Row(object.iter()
+            .map(|(key, value)| {
+                // Nested values are kept as JSON text, the same way CSV exports embed them
+                let value = match value {
+                    Value::String(text) => text.clone(),
+                    Value::Null => String::new(),
+                    other => other.to_string(),
+                };
+                (key.trim().to_lowercase(), value)
+            })
+            .collect()))
+        .collect())
+}
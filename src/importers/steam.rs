@@ -0,0 +1,360 @@
+// Crates/Imports
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use anyhow::anyhow;
+
+use crate::importers::{home_dir, unix_to_date, vdf::{parse_binary_vdf, parse_vdf, read_c_string, read_u32, read_u64, Vdf}, ImportCandidate, Importer};
+
+// Steam's own tools show up as installed apps but aren't games
+const NOT_GAMES: [&str; 3] = ["Proton", "Steam Linux Runtime", "Steamworks Common Redistributables"];
+
+// appinfo.vdf versions. 28 added a second checksum to each app, 29 moved the keys into a table at the end of the file
+const APP_INFO_V27: u32 = 0x07564427;
+const APP_INFO_V28: u32 = 0x07564428;
+const APP_INFO_V29: u32 = 0x07564429;
+
+/// Installed games, and owned games that have been played, from a local Steam folder
+pub struct SteamImporter;
+
+impl Importer for SteamImporter {
+    fn name(&self) -> &'static str {
+        "Steam"
+    }
+
+    fn path_hint(&self) -> &'static str {
+        "Steam folder, e.g. ~/.local/share/Steam"
+    }
+
+    fn default_path(&self) -> Option<PathBuf> {
+        find_steam_root()
+    }
+
+    fn read(&self, path: &Path) -> Result<Vec<ImportCandidate>, anyhow::Error> {
+        read_steam_library(path)
+    }
+}
+
+/// Where Steam usually lives. The first one that exists is used unless the user enters their own path
+pub fn default_steam_roots() -> Vec<PathBuf>
+{
+    let mut roots = Vec::new();
+
+    if let Some(home) = home_dir() {
+        roots.push(home.join(".steam/steam"));
+        roots.push(home.join(".local/share/Steam"));
+        roots.push(home.join(".var/app/com.valvesoftware.Steam/.local/share/Steam")); // Flatpak
+        roots.push(home.join("Library/Application Support/Steam")); // macOS
+    }
+    roots.push(PathBuf::from("C:\\Program Files (x86)\\Steam"));
+
+    roots
+}
+
+pub fn find_steam_root() -> Option<PathBuf>
+{
+    default_steam_roots().into_iter().find(|root| root.join("steamapps").is_dir())
+}
+
+// Every library folder listed in libraryfolders.vdf, plus the Steam folder itself
+fn library_folders(steam_root: &Path) -> Vec<PathBuf>
+{
+    let mut folders = vec![steam_root.to_path_buf()];
+
+    let file = [steam_root.join("steamapps/libraryfolders.vdf"), steam_root.join("config/libraryfolders.vdf")]
+        .into_iter()
+        .find_map(|path| fs::read_to_string(path).ok());
+
+    if let Some(vdf) = file.and_then(|text| parse_vdf(&text).ok()) {
+        let listed = vdf.get("libraryfolders").or_else(|| vdf.get("LibraryFolders"));
+
+        for (key, value) in listed.map(|listed| listed.entries()).unwrap_or_default() {
+            if !key.chars().all(|c| c.is_ascii_digit()) {
+                continue; // Skips things like "contentstatsid"
+            }
+
+            // Newer files have a block with a "path" key, older ones just have the path as the value
+            let path = value.get("path").and_then(Vdf::as_str).or(value.as_str());
+
+            if let Some(path) = path.map(PathBuf::from).filter(|path| !folders.contains(path)) {
+                folders.push(path);
+            }
+        }
+    }
+
+    folders
+}
+
+/// Per app play time (minutes) and last played time from every user's localconfig.vdf
+fn local_play_data(steam_root: &Path) -> HashMap<String, (u64, i64)>
+{
+    let mut play_data: HashMap<String, (u64, i64)> = HashMap::new();
+
+    let Ok(users) = fs::read_dir(steam_root.join("userdata")) else { return play_data };
+
+    for user in users.filter_map(|user| user.ok()) {
+        let Ok(text) = fs::read_to_string(user.path().join("config/localconfig.vdf")) else { continue };
+        let Ok(vdf) = parse_vdf(&text) else { continue };
+
+        let apps = vdf.path(&["UserLocalConfigStore", "Software", "Valve", "Steam", "apps"]);
+
+        for (app_id, app) in apps.map(|apps| apps.entries()).unwrap_or_default() {
+            let minutes = app.get("Playtime").and_then(Vdf::as_str).and_then(|value| value.parse().ok()).unwrap_or(0);
+            let last_played = app.get("LastPlayed").and_then(Vdf::as_str).and_then(|value| value.parse().ok()).unwrap_or(0);
+
+            // Several accounts on one computer can have played the same game, so keep the biggest numbers
+            let entry = play_data.entry(app_id.clone()).or_default();
+            entry.0 = entry.0.max(minutes);
+            entry.1 = entry.1.max(last_played);
+        }
+    }
+
+    play_data
+}
+
+/// Names of the games in Steam's binary app cache (appcache/appinfo.vdf), by app id. Tools, DLC etc. are left out
+pub fn read_app_info(bytes: &[u8]) -> Result<HashMap<String, String>, anyhow::Error>
+{
+    let mut position = 0;
+    let version = read_u32(bytes, &mut position)?;
+    read_u32(bytes, &mut position)?; // Universe
+
+    let keys = match version {
+        APP_INFO_V29 => {
+            let mut table_position = read_u64(bytes, &mut position)? as usize;
+            let count = read_u32(bytes, &mut table_position)?;
+            Some((0..count).map(|_| read_c_string(bytes, &mut table_position)).collect::<Result<Vec<String>, _>>()?)
+        },
+        APP_INFO_V27 | APP_INFO_V28 => None,
+        other => return Err(anyhow!("Unknown appinfo.vdf version {:#x}", other)),
+    };
+
+    // State, last updated, access token, checksum and change number come before each app's data (plus a second checksum from 28 on)
+    let header_size = if version == APP_INFO_V27 { 40 } else { 60 };
+    let mut names = HashMap::new();
+
+    loop {
+        let app_id = read_u32(bytes, &mut position)?;
+        if app_id == 0 {
+            break; // End of the list
+        }
+        let size = read_u32(bytes, &mut position)? as usize;
+        let next_app = position + size;
+
+        let mut data_position = position + header_size;
+        let info = parse_binary_vdf(bytes, &mut data_position, keys.as_deref())?;
+        let common = info.path(&["appinfo", "common"]);
+
+        if let Some(name) = common.and_then(|common| common.get("name")).and_then(Vdf::as_str)
+            && common.and_then(|common| common.get("type")).and_then(Vdf::as_str).is_some_and(|app_type| app_type.eq_ignore_ascii_case("game"))
+        {
+            names.insert(app_id.to_string(), name.to_string());
+        }
+
+        position = next_app;
+    }
+
+    Ok(names)
+}
+
+/// Read the games installed through Steam (from the appmanifest files in every library folder),
+/// adding play time and last played dates from localconfig.vdf where Steam has saved them.
+///
+/// Owned games that aren't installed have no manifest, so ones with play time in localconfig.vdf are named from
+/// Steam's app cache instead. Owned games that were never played aren't saved anywhere readable, so they're not found
+pub fn read_steam_library(steam_root: &Path) -> Result<Vec<ImportCandidate>, anyhow::Error>
+{
+    if !steam_root.join("steamapps").is_dir() {
+        return Err(anyhow!("No steamapps folder in {}", steam_root.display()))
+    }
+
+    let play_data = local_play_data(steam_root);
+    let mut candidates: Vec<ImportCandidate> = Vec::new();
+    let mut seen_ids: Vec<String> = Vec::new();
+
+    for library in library_folders(steam_root) {
+        let Ok(entries) = fs::read_dir(library.join("steamapps")) else { continue }; // Drive not plugged in etc.
+
+        for entry in entries.filter_map(|entry| entry.ok()) {
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            if !(file_name.starts_with("appmanifest_") && file_name.ends_with(".acf")) {
+                continue;
+            }
+
+            let Ok(text) = fs::read_to_string(entry.path()) else { continue };
+            let Ok(manifest) = parse_vdf(&text) else { continue };
+            let Some(app) = manifest.get("AppState") else { continue };
+
+            let app_id = app.get("appid").and_then(Vdf::as_str).unwrap_or_default().to_string();
+            let Some(name) = app.get("name").and_then(Vdf::as_str) else { continue };
+
+            if seen_ids.contains(&app_id) || NOT_GAMES.iter().any(|tool| name.starts_with(tool)) {
+                continue;
+            }
+
+            let (minutes, config_last_played) = play_data.get(&app_id).copied().unwrap_or_default();
+            let manifest_last_played = app.get("LastPlayed").and_then(Vdf::as_str).and_then(|value| value.parse().ok()).unwrap_or(0);
+            let last_played = config_last_played.max(manifest_last_played);
+
+            candidates.push(ImportCandidate {
+                name: name.to_string(),
+                platform: String::from("PC"),
+                storefront: String::from("Steam"),
+                play_time: Duration::from_secs(minutes.saturating_mul(60)),
+                last_played: if last_played > 0 { unix_to_date(last_played) } else { None },
+                ..ImportCandidate::default()
+            });
+            seen_ids.push(app_id);
+        }
+    }
+
+    // Played but not installed (A missing or unreadable cache just means only installed games are found)
+    let app_names = fs::read(steam_root.join("appcache/appinfo.vdf")).ok().and_then(|bytes| read_app_info(&bytes).ok()).unwrap_or_default();
+
+    for (app_id, (minutes, last_played)) in &play_data {
+        let Some(name) = app_names.get(app_id) else { continue };
+        if *minutes == 0 || seen_ids.contains(app_id) || NOT_GAMES.iter().any(|tool| name.starts_with(tool)) {
+            continue;
+        }
+
+        candidates.push(ImportCandidate {
+            name: name.clone(),
+            platform: String::from("PC"),
+            storefront: String::from("Steam"),
+            play_time: Duration::from_secs(minutes.saturating_mul(60)),
+            last_played: if *last_played > 0 { unix_to_date(*last_played) } else { None },
+            ..ImportCandidate::default()
+        });
+    }
+
+    candidates.sort_by_key(|candidate| candidate.name.to_lowercase());
+    Ok(candidates)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // appinfo.vdf (version 29) holding just a name and type for each app
+    fn fake_app_info(apps: &[(u32, &str, &str)]) -> Vec<u8> {
+        let keys = ["appinfo", "common", "name", "type"];
+        let mut bytes = Vec::new();
+        bytes.extend(APP_INFO_V29.to_le_bytes());
+        bytes.extend(1u32.to_le_bytes()); // Universe
+        bytes.extend(0u64.to_le_bytes()); // Where the key table starts, filled in at the end
+
+        for (app_id, name, app_type) in apps {
+            let mut data = vec![0; 60]; // Header
+            data.push(0x00);
+            data.extend(0u32.to_le_bytes());
+            data.push(0x00);
+            data.extend(1u32.to_le_bytes());
+            for (key, value) in [(2u32, name), (3, app_type)] {
+                data.push(0x01);
+                data.extend(key.to_le_bytes());
+                data.extend(value.as_bytes());
+                data.push(0);
+            }
+            data.extend([0x08, 0x08, 0x08]);
+
+            bytes.extend(app_id.to_le_bytes());
+            bytes.extend((data.len() as u32).to_le_bytes());
+            bytes.extend(data);
+        }
+        bytes.extend(0u32.to_le_bytes());
+
+        let table_start = bytes.len() as u64;
+        bytes[8..16].copy_from_slice(&table_start.to_le_bytes());
+        bytes.extend((keys.len() as u32).to_le_bytes());
+        for key in keys {
+            bytes.extend(key.as_bytes());
+            bytes.push(0);
+        }
+
+        bytes
+    }
+
+    // Lay out a small Steam install with two library folders in the temp folder
+    fn fake_steam(test_name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("gamelog_fake_steam_{}_{}", test_name, std::process::id()));
+        let second_library = root.join("second_library");
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("steamapps")).unwrap();
+        fs::create_dir_all(second_library.join("steamapps")).unwrap();
+        fs::create_dir_all(root.join("userdata/1234/config")).unwrap();
+        fs::create_dir_all(root.join("appcache")).unwrap();
+
+        fs::write(root.join("steamapps/libraryfolders.vdf"), format!(r#"
+"libraryfolders"
+{{
+    "contentstatsid"    "-1"
+    "0"
+    {{
+        "path"      "{}"
+        "apps"      {{ "504230" "1000" }}
+    }}
+    "1"
+    {{
+        "path"      "{}"
+    }}
+}}"#, root.display(), second_library.display())).unwrap();
+
+        fs::write(root.join("steamapps/appmanifest_504230.acf"), r#"
+"AppState"
+{
+    "appid"     "504230"
+    "name"      "Celeste"
+    "LastPlayed"    "1700000000"
+}"#).unwrap();
+
+        fs::write(root.join("steamapps/appmanifest_1493710.acf"), r#"
+"AppState" { "appid" "1493710" "name" "Proton Experimental" }"#).unwrap();
+
+        fs::write(second_library.join("steamapps/appmanifest_367520.acf"), r#"
+"AppState"
+{
+    "appid"     "367520"
+    "name"      "Hollow Knight"
+}"#).unwrap();
+
+        fs::write(root.join("userdata/1234/config/localconfig.vdf"), r#"
+"UserLocalConfigStore"
+{
+    "Software" { "Valve" { "Steam" { "apps" {
+        "367520" { "LastPlayed" "1710000000" "Playtime" "125" }
+        "620" { "LastPlayed" "1600000000" "Playtime" "600" }
+        "400" { "Playtime" "0" }
+        "228980" { "Playtime" "5" }
+    } } } }
+}"#).unwrap();
+
+        // Portal 2 is owned and played but not installed, Portal was never played
+        fs::write(root.join("appcache/appinfo.vdf"), fake_app_info(&[
+            (367520, "Hollow Knight", "Game"),
+            (620, "Portal 2", "Game"),
+            (400, "Portal", "Game"),
+            (228980, "Steamworks Common Redistributables", "Tool"),
+        ])).unwrap();
+
+        root
+    }
+
+    #[test]
+    fn reads_games_from_every_library_with_play_data() {
+        let root = fake_steam("library");
+
+        let candidates = read_steam_library(&root).unwrap();
+        let names: Vec<&str> = candidates.iter().map(|candidate| candidate.name.as_str()).collect();
+
+        assert_eq!(names, vec!["Celeste", "Hollow Knight", "Portal 2"]); // Proton, tools and unplayed games are left out
+        assert_eq!(candidates[0].last_played.as_deref(), Some("2023-11-14"));
+        assert_eq!(candidates[1].play_time, Duration::from_secs(125 * 60));
+        assert_eq!(candidates[1].last_played.as_deref(), Some("2024-03-09"));
+        assert_eq!(candidates[1].storefront, "Steam");
+        assert_eq!(candidates[2].play_time, Duration::from_secs(600 * 60));
+        assert_eq!(candidates[2].last_played.as_deref(), Some("2020-09-13"));
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
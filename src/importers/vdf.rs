@@ -0,0 +1,272 @@
+// Crates/Imports
+use anyhow::{anyhow, bail};
+
+/// A value from one of Steam's KeyValues (.vdf/.acf) text files.
+///
+/// The format is just quoted keys followed by either a quoted value or a `{ }` block of more keys
+#[derive(Clone, Debug, PartialEq)]
+pub enum Vdf {
+    Value(String),
+    Object(Vec<(String, Vdf)>),
+}
+
+impl Vdf {
+    /// Look up a key inside an object. Steam isn't consistent with capitals ("apps" vs "Apps") so case is ignored
+    pub fn get(&self, key: &str) -> Option<&Vdf>
+    {
+        match self {
+            Vdf::Object(entries) => entries.iter().find(|(name, _)| name.eq_ignore_ascii_case(key)).map(|(_, value)| value),
+            Vdf::Value(_) => None,
+        }
+    }
+
+    /// Follow a chain of keys, e.g. ["Software", "Valve", "Steam", "apps"]
+    pub fn path(&self, keys: &[&str]) -> Option<&Vdf>
+    {
+        keys.iter().try_fold(self, |value, key| value.get(key))
+    }
+
+    pub fn as_str(&self) -> Option<&str>
+    {
+        match self {
+            Vdf::Value(value) => Some(value),
+            Vdf::Object(_) => None,
+        }
+    }
+
+    /// Every key/value pair of an object (Empty for plain values)
+    pub fn entries(&self) -> &[(String, Vdf)]
+    {
+        match self {
+            Vdf::Object(entries) => entries,
+            Vdf::Value(_) => &[],
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    Text(String),
+    Open,
+    Close,
+}
+
+fn tokenise(text: &str) -> Result<Vec<Token>, anyhow::Error>
+{
+    let mut tokens = Vec::new();
+    let mut chars = text.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        match c {
+            '{' => { chars.next(); tokens.push(Token::Open); },
+            '}' => { chars.next(); tokens.push(Token::Close); },
+            '"' => {
+                chars.next();
+                let mut value = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() { // Escaped characters
+                            Some('n') => value.push('\n'),
+                            Some('t') => value.push('\t'),
+                            Some(other) => value.push(other),
+                            None => bail!("File ends inside a string"),
+                        },
+                        Some(other) => value.push(other),
+                        None => bail!("File ends inside a string"),
+                    }
+                }
+                tokens.push(Token::Text(value));
+            },
+            '/' => { // Comments run to the end of the line
+                while chars.next_if(|c| *c != '\n').is_some() {}
+            },
+            '[' => { // Platform conditions like [$WIN32] don't matter here so they're skipped
+                while chars.next_if(|c| *c != ']').is_some() {}
+                chars.next();
+            },
+            c if c.is_whitespace() => { chars.next(); },
+            _ => { // Unquoted values
+                let mut value = String::new();
+                while let Some(c) = chars.next_if(|c| !c.is_whitespace() && !matches!(c, '{' | '}' | '"')) {
+                    value.push(c);
+                }
+                tokens.push(Token::Text(value));
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// Parse a whole VDF file. The file's top level keys become the entries of the returned object
+pub fn parse_vdf(text: &str) -> Result<Vdf, anyhow::Error>
+{
+    let tokens = tokenise(text)?;
+    let mut position = 0;
+    let root = parse_entries(&tokens, &mut position)?;
+
+    if position < tokens.len() {
+        return Err(anyhow!("Unexpected '}}' in file"))
+    }
+
+    Ok(root)
+}
+
+// Binary KeyValues value types
+const BINARY_OBJECT: u8 = 0x00;
+const BINARY_STRING: u8 = 0x01;
+const BINARY_INT32: u8 = 0x02;
+const BINARY_FLOAT: u8 = 0x03;
+const BINARY_POINTER: u8 = 0x04;
+const BINARY_COLOR: u8 = 0x06;
+const BINARY_UINT64: u8 = 0x07;
+const BINARY_END: u8 = 0x08;
+const BINARY_INT64: u8 = 0x0A;
+const BINARY_END_ALT: u8 = 0x0B; // Some files end blocks with this instead
+
+pub fn read_u32(bytes: &[u8], position: &mut usize) -> Result<u32, anyhow::Error>
+{
+    let value = bytes.get(*position..*position + 4).ok_or_else(|| anyhow!("File ends early"))?;
+    *position += 4;
+    Ok(u32::from_le_bytes(value.try_into()?))
+}
+
+pub fn read_u64(bytes: &[u8], position: &mut usize) -> Result<u64, anyhow::Error>
+{
+    let value = bytes.get(*position..*position + 8).ok_or_else(|| anyhow!("File ends early"))?;
+    *position += 8;
+    Ok(u64::from_le_bytes(value.try_into()?))
+}
+
+// Null terminated UTF-8 string
+pub fn read_c_string(bytes: &[u8], position: &mut usize) -> Result<String, anyhow::Error>
+{
+    let length = bytes.get(*position..).and_then(|rest| rest.iter().position(|byte| *byte == 0)).ok_or_else(|| anyhow!("File ends inside a string"))?;
+    let text = String::from_utf8_lossy(&bytes[*position..*position + length]).into_owned();
+    *position += length + 1;
+    Ok(text)
+}
+
+/// Parse the entries of one block of Steam's binary KeyValues format (used by appinfo.vdf), up to the byte that ends it.
+///
+/// Newer files keep every key once in a table at the end of the file and only store indexes into it, so `keys` is
+/// that table if there is one. Numbers come back as text, the same as in the text format
+pub fn parse_binary_vdf(bytes: &[u8], position: &mut usize, keys: Option<&[String]>) -> Result<Vdf, anyhow::Error>
+{
+    let mut entries = Vec::new();
+
+    loop {
+        let value_type = *bytes.get(*position).ok_or_else(|| anyhow!("File ends inside a block"))?;
+        *position += 1;
+
+        if value_type == BINARY_END || value_type == BINARY_END_ALT {
+            break;
+        }
+
+        let key = match keys {
+            Some(keys) => {
+                let index = read_u32(bytes, position)? as usize;
+                keys.get(index).cloned().ok_or_else(|| anyhow!("Key {} isn't in the key table", index))?
+            },
+            None => read_c_string(bytes, position)?,
+        };
+
+        let value = match value_type {
+            BINARY_OBJECT => parse_binary_vdf(bytes, position, keys)?,
+            BINARY_STRING => Vdf::Value(read_c_string(bytes, position)?),
+            BINARY_INT32 => Vdf::Value((read_u32(bytes, position)? as i32).to_string()),
+            BINARY_FLOAT => Vdf::Value(f32::from_bits(read_u32(bytes, position)?).to_string()),
+            BINARY_POINTER | BINARY_COLOR => Vdf::Value(read_u32(bytes, position)?.to_string()),
+            BINARY_UINT64 => Vdf::Value(read_u64(bytes, position)?.to_string()),
+            BINARY_INT64 => Vdf::Value((read_u64(bytes, position)? as i64).to_string()),
+            other => bail!("Unknown value type {:#04x} for key \"{}\"", other, key),
+        };
+
+        entries.push((key, value));
+    }
+
+    Ok(Vdf::Object(entries))
+}
+
+fn parse_entries(tokens: &[Token], position: &mut usize) -> Result<Vdf, anyhow::Error>
+{
+    let mut entries = Vec::new();
+
+    while let Some(token) = tokens.get(*position) {
+        let key = match token {
+            Token::Text(key) => key.clone(),
+            Token::Close => break, // End of this block, the caller deals with the brace
+            Token::Open => bail!("Expected a key but found '{{'"),
+        };
+        *position += 1;
+
+        let value = match tokens.get(*position) {
+            Some(Token::Text(value)) => {
+                *position += 1;
+                Vdf::Value(value.clone())
+            },
+            Some(Token::Open) => {
+                *position += 1;
+                let block = parse_entries(tokens, position)?;
+                match tokens.get(*position) {
+                    Some(Token::Close) => *position += 1,
+                    _ => bail!("Block \"{}\" is never closed", key),
+                }
+                block
+            },
+            _ => bail!("Key \"{}\" has no value", key),
+        };
+
+        entries.push((key, value));
+    }
+
+    Ok(Vdf::Object(entries))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_nested_blocks_comments_and_escapes() {
+        let vdf = parse_vdf("// comment\n\"a\" { \"b\" \"say \\\"hi\\\"\" [$WIN32] \"C\" { \"d\" \"1\" } }").unwrap();
+
+        assert_eq!(vdf.path(&["a", "b"]).and_then(Vdf::as_str), Some("say \"hi\""));
+        assert_eq!(vdf.path(&["A", "c", "D"]).and_then(Vdf::as_str), Some("1"));
+        assert!(parse_vdf("\"a\" { \"b\" \"c\"").is_err());
+    }
+
+    #[test]
+    fn parses_binary_blocks_with_and_without_a_key_table() {
+        // "common" { "name" "Celeste" "appid" 504230 }
+        let mut bytes = vec![BINARY_OBJECT];
+        bytes.extend(b"common\0");
+        bytes.push(BINARY_STRING);
+        bytes.extend(b"name\0Celeste\0");
+        bytes.push(BINARY_INT32);
+        bytes.extend(b"appid\0");
+        bytes.extend(504230u32.to_le_bytes());
+        bytes.extend([BINARY_END, BINARY_END]);
+
+        let mut position = 0;
+        let vdf = parse_binary_vdf(&bytes, &mut position, None).unwrap();
+        assert_eq!(vdf.path(&["common", "name"]).and_then(Vdf::as_str), Some("Celeste"));
+        assert_eq!(vdf.path(&["common", "appid"]).and_then(Vdf::as_str), Some("504230"));
+        assert_eq!(position, bytes.len());
+
+        // Same block, with keys looked up in a table
+        let keys = vec![String::from("common"), String::from("name")];
+        let mut bytes = vec![BINARY_OBJECT];
+        bytes.extend(0u32.to_le_bytes());
+        bytes.push(BINARY_STRING);
+        bytes.extend(1u32.to_le_bytes());
+        bytes.extend(b"Celeste\0");
+        bytes.extend([BINARY_END, BINARY_END]);
+
+        let vdf = parse_binary_vdf(&bytes, &mut 0, Some(&keys)).unwrap();
+        assert_eq!(vdf.path(&["common", "name"]).and_then(Vdf::as_str), Some("Celeste"));
+
+        assert!(parse_binary_vdf(&bytes[..bytes.len() - 1], &mut 0, Some(&keys)).is_err()); // Never closed
+    }
+}
@@ -0,0 +1,209 @@
+// Crates/Imports
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::PathBuf;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use eframe::egui::{Color32, RichText, Ui};
+
+use crate::{app_setup::GameLog, storage::open_storage};
+
+pub const LOCK_FILE: &str = "GameLog.lock"; // Held by the window for as long as it's open, so a second window only reads the log
+pub const WRITE_LOCK_FILE: &str = "GameLog.write.lock"; // Held just while the log is being written, by the window or a command
+pub const CLI_LOCK_TIMEOUT: Duration = Duration::from_secs(5); // How long commands wait for another command (or the window) to finish saving
+pub const SAVE_LOCK_TIMEOUT: Duration = Duration::from_secs(2); // Same for the window, kept short as the window waits with it
+const RETRY_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Who has the log locked, as written in the lock file
+#[derive(Clone, Debug, PartialEq)]
+pub struct LockHolder {
+    pub pid: u32,
+    pub host: String,
+}
+
+impl LockHolder {
+    fn parse(text: &str) -> Option<LockHolder>
+    {
+        let mut lines = text.lines();
+        let pid = lines.next()?.trim().parse().ok()?;
+        let host = lines.next()?.trim().to_string();
+        Some(LockHolder { pid, host })
+    }
+}
+
+impl fmt::Display for LockHolder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "process {} on {}", self.pid, self.host)
+    }
+}
+
+/// Why the lock couldn't be taken
+#[derive(Debug, PartialEq)]
+pub enum LockError {
+    Held(Option<LockHolder>), // None if the lock file couldn't be read (It may still be being written)
+    Io(String),
+}
+
+impl fmt::Display for LockError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LockError::Held(Some(holder)) => write!(f, "The game log is in use by another copy of the app ({})", holder),
+            LockError::Held(None) => write!(f, "The game log is in use by another copy of the app"),
+            LockError::Io(error) => write!(f, "Error Locking the Game Log: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for LockError {}
+
+/// Name of this computer, shown with the process holding a lock
+pub fn host_name() -> String
+{
+    fs::read_to_string("/etc/hostname").ok()
+        .or_else(|| std::env::var("COMPUTERNAME").ok())
+        .or_else(|| std::env::var("HOSTNAME").ok())
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| String::from("unknown"))
+}
+
+/// An OS advisory lock (flock/LockFileEx) on a lock file next to the log. Let go when dropped, and by the OS if the process crashes,
+/// so a lock is never left behind. The file itself stays, holding who last had the lock
+#[derive(Debug)]
+pub struct InstanceLock {
+    file: File,
+}
+
+impl InstanceLock {
+    /// Take the lock if nobody has it
+    pub fn acquire(path: impl Into<PathBuf>) -> Result<InstanceLock, LockError>
+    {
+        let io_error = |error: std::io::Error| LockError::Io(error.to_string());
+
+        let mut file = OpenOptions::new().read(true).write(true).create(true).truncate(false).open(path.into()).map_err(io_error)?;
+
+        match file.try_lock() {
+            Ok(()) => {
+                let contents = format!("{}\n{}\n", std::process::id(), host_name());
+                file.set_len(0).map_err(io_error)?;
+                file.write_all(contents.as_bytes()).map_err(io_error)?;
+                Ok(InstanceLock { file })
+            },
+            Err(std::fs::TryLockError::WouldBlock) => {
+                let mut text = String::new();
+                let holder = file.read_to_string(&mut text).ok().and_then(|_| LockHolder::parse(&text));
+                Err(LockError::Held(holder))
+            },
+            Err(std::fs::TryLockError::Error(error)) => Err(io_error(error)),
+        }
+    }
+
+    /// Keep trying for the lock until `timeout` runs out (For waiting on another copy to finish saving)
+    pub fn wait_for(path: impl Into<PathBuf>, timeout: Duration) -> Result<InstanceLock, LockError>
+    {
+        let path = path.into();
+        let started = Instant::now();
+
+        loop {
+            match Self::acquire(path.clone()) {
+                Err(LockError::Held(_)) if started.elapsed() < timeout => thread::sleep(RETRY_INTERVAL),
+                result => return result,
+            }
+        }
+    }
+}
+
+impl Drop for InstanceLock {
+    fn drop(&mut self) {
+        // Nobody holds it any more (Closing the file lets the lock go)
+        let _ = self.file.set_len(0);
+        let _ = self.file.seek(SeekFrom::Start(0));
+    }
+}
+
+// Read only mode, for when another copy of the app has the log (or it couldn't be opened)
+impl GameLog {
+    /// True while another copy of the app holds the lock or the storage couldn't be opened, so this one only shows the log
+    pub fn read_only(&self) -> bool {
+        self.instance_lock.is_none() || !self.storage_error.is_empty()
+    }
+
+    // Load the log again after coming out of read only
+    fn reload_log(&mut self) {
+        match self.storage.load() {
+            Ok(games) => {
+                self.game_file_contents = games.clone();
+                self.synced_games = games;
+                self.refresh_search();
+            },
+            Err(error) => println!("Error Reading the Game Log: {}", error),
+        }
+    }
+
+    /// Top bar warning shown while read only, with a way to take over once the other copy is closed
+    pub fn read_only_banner(&mut self, ui: &mut Ui) {
+        if !self.read_only() {
+            return;
+        }
+
+        // Nothing can be shown until the log opens, so this comes before waiting on another copy
+        if !self.storage_error.is_empty() {
+            if ui.button("Try Again").on_hover_text("Open the game log again").clicked() {
+                match open_storage(self.settings.storage_backend) {
+                    Ok(storage) => {
+                        self.storage = storage;
+                        self.storage_error.clear();
+                        self.reload_log();
+                    },
+                    Err(error) => self.storage_error = format!("Error Opening the {} Game Log: {}", self.settings.storage_backend, error),
+                }
+            }
+
+            ui.label(RichText::new(format!("Read Only: {}", self.storage_error)).color(Color32::RED).strong());
+            return;
+        }
+
+        if ui.button("Try Again").on_hover_text("Start editing if the other copy has been closed").clicked() {
+            match InstanceLock::acquire(LOCK_FILE) {
+                Ok(lock) => {
+                    self.instance_lock = Some(lock);
+                    self.lock_error.clear();
+                    self.reload_log(); // Pick up whatever the other copy saved before it closed
+                },
+                Err(error) => self.lock_error = error.to_string(),
+            }
+        }
+
+        ui.label(RichText::new("Read Only").color(Color32::RED).strong()).on_hover_text(&self.lock_error);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locks_are_taken_once_and_let_go_when_dropped() {
+        let dir = std::env::temp_dir().join(format!("gamelog-lock-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(LOCK_FILE);
+
+        let lock = InstanceLock::acquire(&path).unwrap();
+        assert_eq!(InstanceLock::acquire(&path).unwrap_err(), LockError::Held(Some(LockHolder { pid: std::process::id(), host: host_name() })));
+        assert!(InstanceLock::wait_for(&path, Duration::from_millis(200)).is_err());
+        drop(lock);
+        assert!(InstanceLock::acquire(&path).is_ok());
+
+        // Left behind by a process that crashed, the OS has already let go of its lock
+        fs::write(&path, format!("4243\n{}\n", host_name())).unwrap();
+        assert!(InstanceLock::acquire(&path).is_ok());
+
+        // The window's lock doesn't stop a command saving
+        let _window = InstanceLock::acquire(dir.join(LOCK_FILE)).unwrap();
+        assert!(InstanceLock::wait_for(dir.join(WRITE_LOCK_FILE), CLI_LOCK_TIMEOUT).is_ok());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
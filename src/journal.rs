@@ -0,0 +1,416 @@
+// Crates/Imports
+use std::collections::HashSet;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+use std::time::Duration;
+use chrono::Local;
+use eframe::egui::{self, Button, RichText, ScrollArea, Ui};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{app_setup::GameLog, enums::{Rating, RatingScale}, instance_lock::{InstanceLock, SAVE_LOCK_TIMEOUT, WRITE_LOCK_FILE}, json_file_operations::{search_for_game, Game}, notes::{redact_spoilers, shorten, SPOILER_REDACTED}, playtime::format_duration};
+
+pub const JOURNAL_FILE: &str = "GameLogJournal.jsonl"; // One change per line, only ever added to (Kept apart from the log, so it's the same whatever the storage backend. Backups take a copy of it)
+pub const ADDED: &str = "added"; // `field` of a game being added to the log
+pub const REMOVED: &str = "removed"; // `old` has the whole game as it was, so nothing is lost from the history
+const VALUE_LENGTH: usize = 60; // Longer values (Mostly notes) are cut short in the History tab
+
+/// One field of one game changing
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct JournalEntry {
+    pub time: String, // Local time, YYYY-MM-DD HH:MM:SS
+    pub game: String, // The game's name after the change
+    pub field: String, // As it's called in the saved log (Or ADDED/REMOVED)
+    #[serde(default)]
+    pub old: Value,
+    #[serde(default)]
+    pub new: Value,
+}
+
+/// What changed between two versions of the log, one entry per changed field.
+///
+/// Games are matched by name, or as a rename when exactly one game's name is gone and exactly one new one has appeared
+pub fn journal_changes(before: &[Game], after: &[Game], time: &str) -> Vec<JournalEntry>
+{
+    let before_names: HashSet<&str> = before.iter().map(|game| game.name.as_str()).collect();
+    let after_names: HashSet<&str> = after.iter().map(|game| game.name.as_str()).collect();
+    let removed: Vec<&Game> = before.iter().filter(|game| !after_names.contains(game.name.as_str())).collect();
+    let added: Vec<&Game> = after.iter().filter(|game| !before_names.contains(game.name.as_str())).collect();
+    let mut entries = Vec::new();
+
+    // One game going while another comes is a rename. With more than one there's no telling which became which, so they're removed and added
+    let renamed = match (&removed[..], &added[..]) {
+        ([old_game], [new_game]) => Some((old_game.name.as_str(), *new_game)),
+        _ => None,
+    };
+
+    let entry = |game: &str, field: &str, old: Value, new: Value| JournalEntry {
+        time: time.to_string(),
+        game: game.to_string(),
+        field: field.to_string(),
+        old,
+        new,
+    };
+
+    for old_game in before {
+        let new_game = after.iter().find(|game| game.name == old_game.name)
+            .or_else(|| renamed.filter(|(old_name, _)| *old_name == old_game.name).map(|(_, new_game)| new_game));
+
+        let Some(new_game) = new_game else {
+            entries.push(entry(&old_game.name, REMOVED, serde_json::to_value(old_game).unwrap_or_default(), Value::Null));
+            continue;
+        };
+        if new_game == old_game {
+            continue;
+        }
+
+        let (Ok(Value::Object(old_fields)), Ok(Value::Object(new_fields))) = (serde_json::to_value(old_game), serde_json::to_value(new_game)) else { continue };
+
+        // Name first, so the History tab can follow a game back through its renames
+        let mut fields: Vec<&String> = new_fields.keys().collect();
+        fields.sort_by_key(|field| *field != "name");
+
+        for field in fields {
+            let old_value = old_fields.get(field).cloned().unwrap_or_default();
+            if old_value != new_fields[field] {
+                entries.push(entry(&new_game.name, field, old_value, new_fields[field].clone()));
+            }
+        }
+    }
+
+    for game in added.into_iter().filter(|game| renamed.is_none_or(|(_, new_game)| new_game.name != game.name)) {
+        entries.push(entry(&game.name, ADDED, Value::Null, Value::Null));
+    }
+
+    entries
+}
+
+/// Every cover file a game has had according to the journal, so reverting the cover of a game never points at a deleted file
+pub fn journal_covers(journal: &[JournalEntry]) -> Vec<String>
+{
+    journal.iter()
+        .filter(|entry| entry.field == "cover")
+        .flat_map(|entry| [&entry.old, &entry.new])
+        .filter_map(|value| value.as_str().filter(|cover| !cover.is_empty()).map(String::from))
+        .collect()
+}
+
+/// Entries for changes made just now
+pub fn journal_changes_now(before: &[Game], after: &[Game]) -> Vec<JournalEntry>
+{
+    journal_changes(before, after, &Local::now().format("%Y-%m-%d %H:%M:%S").to_string())
+}
+
+// One line per entry
+fn journal_lines(entries: &[JournalEntry]) -> Result<String, anyhow::Error>
+{
+    let mut lines = String::new();
+    for entry in entries {
+        lines.push_str(&serde_json::to_string(entry)?);
+        lines.push('\n');
+    }
+    Ok(lines)
+}
+
+/// Add entries to the end of the journal
+pub fn append_journal(path: &Path, entries: &[JournalEntry]) -> Result<(), anyhow::Error>
+{
+    if entries.is_empty() {
+        return Ok(());
+    }
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    file.write_all(journal_lines(entries)?.as_bytes())?;
+
+    Ok(())
+}
+
+/// Entries from another copy of the journal (A backup's) added into this one in time order. Entries it already has are skipped
+pub fn merge_journals(journal: &[JournalEntry], other: Vec<JournalEntry>) -> Vec<JournalEntry>
+{
+    let mut merged = journal.to_vec();
+    merged.extend(other.into_iter().filter(|entry| !journal.contains(entry)));
+    merged.sort_by(|a, b| a.time.cmp(&b.time)); // Stable, so changes saved together stay in order
+    merged
+}
+
+/// Merge another copy of the journal (A backup's, or one copied from another computer) into the one at `path`.
+///
+/// The only time a journal is rewritten rather than added to, and only if the other copy had anything new. Returns the merged journal
+pub fn merge_journal_file(path: &Path, other: &Path) -> Result<Vec<JournalEntry>, anyhow::Error>
+{
+    let journal = read_journal(path);
+    let merged = merge_journals(&journal, read_journal(other));
+
+    if merged.len() != journal.len() {
+        fs::write(path, journal_lines(&merged)?)?;
+    }
+    Ok(merged)
+}
+
+/// Every entry in the journal, oldest first (Lines that can't be read are skipped)
+pub fn read_journal(path: &Path) -> Vec<JournalEntry>
+{
+    let Ok(file) = File::open(path) else { return Vec::new() };
+
+    BufReader::new(file).lines()
+        .map_while(Result::ok)
+        .filter_map(|line| serde_json::from_str(&line).ok())
+        .collect()
+}
+
+/// A game's changes, newest first. Follows the game back through any renames, stopping where it was added
+pub fn game_history<'a>(journal: &'a [JournalEntry], name: &str) -> Vec<&'a JournalEntry>
+{
+    let mut name = name.to_lowercase();
+    let mut history = Vec::new();
+
+    for entry in journal.iter().rev() {
+        if entry.game.to_lowercase() != name {
+            continue;
+        }
+        history.push(entry);
+
+        if entry.field == ADDED {
+            break; // Anything older was another game that had this name
+        }
+        if entry.field == "name" && let Some(old_name) = entry.old.as_str() {
+            name = old_name.to_lowercase();
+        }
+    }
+
+    history
+}
+
+/// A game with one field put back to an older value
+pub fn with_field(game: &Game, field: &str, value: &Value) -> Result<Game, String>
+{
+    let mut fields = serde_json::to_value(game).map_err(|error| error.to_string())?;
+    fields[field] = value.clone();
+    serde_json::from_value(fields).map_err(|_| format!("{} can't be set back to that", field_label(field)))
+}
+
+/// "release_year" as "Release year"
+pub fn field_label(field: &str) -> String
+{
+    let label = field.replace('_', " ");
+    let mut letters = label.chars();
+    letters.next().map(|first| first.to_uppercase().chain(letters).collect()).unwrap_or_default()
+}
+
+// A saved value as it's shown in the History tab
+fn value_text(field: &str, value: &Value, rating_scale: RatingScale) -> String
+{
+    let text = match value {
+        Value::Null => String::from("None"),
+        Value::String(text) if text.is_empty() => String::from("Empty"),
+        Value::String(text) if field == "notes" => redact_spoilers(text, SPOILER_REDACTED),
+        Value::String(text) => text.clone(),
+        Value::Number(number) if field == "rating" => number.as_u64()
+            .and_then(|points| Rating::from_points(points.try_into().ok()?))
+            .map(|rating| rating_scale.display(rating))
+            .unwrap_or_else(|| number.to_string()),
+        Value::Number(number) if field == "play_time" => format_duration(Duration::from_secs(number.as_u64().unwrap_or_default())),
+        Value::Array(items) if items.is_empty() => String::from("None"),
+        Value::Array(items) if items.iter().all(Value::is_string) => items.iter().filter_map(Value::as_str).collect::<Vec<&str>>().join(", "),
+        Value::Array(items) => format!("{} entries", items.len()),
+        other => other.to_string(),
+    };
+
+    shorten(&text, VALUE_LENGTH)
+}
+
+// History tab of the 'Editing' window
+impl GameLog {
+    /// Write down what a change did, so it shows in the History tab
+    pub fn record_journal(&mut self, before: &[Game]) {
+        let entries = journal_changes_now(before, &self.game_file_contents);
+
+        // Running sessions are kept by name, so they have to follow renames too
+        for entry in entries.iter().filter(|entry| entry.field == "name") {
+            if let (Value::String(from), Value::String(to)) = (&entry.old, &entry.new) {
+                self.follow_rename(from, to);
+            }
+        }
+
+        if let Err(error) = append_journal(Path::new(JOURNAL_FILE), &entries) {
+            println!("Error Saving the Change History: {}", error);
+        }
+        self.journal.extend(entries);
+    }
+
+    /// Merge a restored backup's copy of the journal back in, so the history comes back with the log (e.g. on another computer, or after the journal was lost).
+    ///
+    /// Merged with the file rather than what's in memory, so changes logged from the command line since the window opened aren't lost
+    pub fn restore_journal(&mut self, backup_journal: &Path) -> Result<(), anyhow::Error> {
+        let _write_lock = InstanceLock::wait_for(WRITE_LOCK_FILE, SAVE_LOCK_TIMEOUT)?;
+        self.journal = merge_journal_file(Path::new(JOURNAL_FILE), backup_journal)?;
+        Ok(())
+    }
+
+    /// Read the journal again after the log was changed outside the app (Commands add to the journal when they change the log)
+    pub fn reload_journal(&mut self) {
+        self.journal = read_journal(Path::new(JOURNAL_FILE));
+    }
+
+    /// Put one field of a game back how it was before a change. Can be undone, and is added to the history like any other change
+    pub fn revert_journal_entry(&mut self, index: usize, entry: &JournalEntry) -> Result<(), String> {
+        if entry.field == ADDED || entry.field == REMOVED {
+            return Err(String::from("Only changes to a game's details can be reverted"));
+        }
+
+        let game = &self.game_file_contents[index];
+        let reverted = with_field(game, &entry.field, &entry.old)?;
+
+        if entry.field == "name" && search_for_game(&self.game_file_contents, &reverted.name).is_ok_and(|other| other != index) {
+            return Err(format!("Another game is already called {}", reverted.name));
+        }
+
+        let description = format!("Revert {} of {}", field_label(&entry.field).to_lowercase(), game.name);
+        self.apply_change(&description, |game_log| {
+            // Keep the edit window on the game if its name is put back
+            if game_log.editing_search_game_name.eq_ignore_ascii_case(&game_log.game_file_contents[index].name) {
+                game_log.editing_search_game_name = reverted.name.clone();
+            }
+            game_log.game_file_contents[index] = reverted;
+            Ok(())
+        })
+    }
+
+    pub fn game_history_gui(&mut self, ui: &mut Ui, index: usize) {
+        let rating_scale = self.settings.rating_scale;
+        let game = &self.game_file_contents[index];
+        let history: Vec<JournalEntry> = game_history(&self.journal, &game.name).into_iter().cloned().collect();
+        let mut reverting = None;
+
+        if history.is_empty() {
+            ui.label(RichText::new("No changes recorded yet").italics());
+        }
+
+        ScrollArea::vertical().id_salt("game_history").max_height(350.0).show(ui, |ui| {
+            for entry in &history {
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new(&entry.time).weak());
+
+                    match entry.field.as_str() {
+                        ADDED => { ui.label(format!("Added {} to the log", entry.game)); },
+                        REMOVED => { ui.label(format!("Removed {} from the log", entry.game)); },
+                        field => {
+                            ui.label(format!("{}: {} -> {}", field_label(field), value_text(field, &entry.old, rating_scale), value_text(field, &entry.new, rating_scale)));
+
+                            // Nothing to do if the field already has the old value
+                            let current = serde_json::to_value(game).ok().and_then(|fields| fields.get(field).cloned()).unwrap_or_default();
+                            if ui.add_enabled(current != entry.old, Button::new("Revert")).on_hover_text("Put this back how it was before the change").clicked() {
+                                reverting = Some(entry.clone());
+                            }
+                        },
+                    }
+                });
+            }
+        });
+
+        if let Some(entry) = reverting {
+            self.history_feedback = match self.revert_journal_entry(index, &entry) {
+                Ok(()) => format!("Reverted {}", field_label(&entry.field).to_lowercase()),
+                Err(error) => error,
+            };
+        }
+
+        if !self.history_feedback.is_empty() {
+            let color = if self.history_feedback.starts_with("Reverted") {
+                if self.dark_mode { egui::Color32::GREEN } else { egui::Color32::DARK_GREEN }
+            }
+            else {
+                egui::Color32::RED
+            };
+            ui.label(RichText::new(&self.history_feedback).color(color));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn game(name: &str, points: u8, notes: &str) -> Game {
+        serde_json::from_str(&format!(r#"{{"name": "{}", "rating": {}, "times_played": 1, "last_playthrough": "", "notes": "{}"}}"#, name, points, notes)).unwrap()
+    }
+
+    #[test]
+    fn history_follows_renames_and_reverts_fields() {
+        let first = vec![game("Celeste", 80, "")];
+        let renamed = vec![game("Celeste Classic", 100, "PICO-8 version")];
+        let added = vec![game("Celeste Classic", 100, "PICO-8 version"), game("Celeste", 90, "")];
+
+        let mut journal = journal_changes(&[], &first, "2025-01-01 10:00:00");
+        journal.extend(journal_changes(&first, &renamed, "2025-01-02 10:00:00"));
+        journal.extend(journal_changes(&renamed, &added, "2025-01-03 10:00:00"));
+
+        let fields: Vec<&str> = journal.iter().map(|entry| entry.field.as_str()).collect();
+        assert_eq!(fields, [ADDED, "name", "notes", "rating", ADDED]);
+
+        // The renamed game's history goes back to when it was "Celeste", and the new "Celeste" has its own
+        let history: Vec<&str> = game_history(&journal, "celeste classic").iter().map(|entry| entry.field.as_str()).collect();
+        assert_eq!(history, ["rating", "notes", "name", ADDED]);
+        assert_eq!(game_history(&journal, "Celeste").len(), 1);
+
+        let rating_change = journal.iter().find(|entry| entry.field == "rating").unwrap();
+        assert_eq!(with_field(&renamed[0], "rating", &rating_change.old).unwrap(), game("Celeste Classic", 80, "PICO-8 version"));
+        assert!(with_field(&renamed[0], "rating", &Value::from("lots")).is_err());
+    }
+
+    #[test]
+    fn renames_are_only_paired_when_there_is_one() {
+        let before = vec![game("Celeste", 80, ""), game("Hades", 100, ""), game("Tunic", 80, "")];
+
+        // Two games removed and a different one added in the same place isn't a rename of either
+        let after = vec![game("Outer Wilds", 100, "")];
+        let entries = journal_changes(&before, &after, "");
+        assert_eq!(entries.iter().map(|entry| (entry.game.as_str(), entry.field.as_str())).collect::<Vec<_>>(),
+            [("Celeste", REMOVED), ("Hades", REMOVED), ("Tunic", REMOVED), ("Outer Wilds", ADDED)]);
+        assert_eq!(serde_json::from_value::<Game>(entries[1].old.clone()).unwrap(), before[1]); // Removed games are kept whole
+
+        // One going and one coming is, wherever it ends up in the list
+        let after = vec![game("Hades", 100, ""), game("Tunic", 80, ""), game("Celeste Classic", 80, "")];
+        let entries = journal_changes(&before, &after, "");
+        assert_eq!(entries.len(), 1);
+        assert_eq!((entries[0].game.as_str(), entries[0].field.as_str()), ("Celeste Classic", "name"));
+    }
+
+    #[test]
+    fn backup_journals_merge_in_time_order() {
+        let entry = |time: &str, game: &str| JournalEntry { time: time.to_string(), game: game.to_string(), field: String::from(ADDED), old: Value::Null, new: Value::Null };
+        let current = vec![entry("2025-01-01 10:00:00", "Celeste"), entry("2025-03-01 10:00:00", "Tunic")];
+        let backup = vec![entry("2025-01-01 10:00:00", "Celeste"), entry("2025-02-01 10:00:00", "Hades")];
+
+        let merged = merge_journals(&current, backup);
+        assert_eq!(merged.iter().map(|entry| entry.game.as_str()).collect::<Vec<&str>>(), ["Celeste", "Hades", "Tunic"]);
+        assert_eq!(merge_journals(&merged, current.clone()), merged); // Nothing new
+
+        // Merging files keeps whatever was already in the file, even if it was added after the other copy was taken
+        let dir = std::env::temp_dir().join(format!("gamelog_merge_journal_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        append_journal(&dir.join("journal.jsonl"), &current).unwrap();
+        append_journal(&dir.join("backup.jsonl"), &merged[..2]).unwrap();
+
+        assert_eq!(merge_journal_file(&dir.join("journal.jsonl"), &dir.join("backup.jsonl")).unwrap(), merged);
+        assert_eq!(read_journal(&dir.join("journal.jsonl")), merged);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn covers_from_every_change_are_kept() {
+        let mut first = game("Celeste", 80, "");
+        first.cover = String::from("celeste-1.png");
+        let mut second = first.clone();
+        second.cover = String::from("celeste-2.png");
+        let mut removed = second.clone();
+        removed.cover.clear();
+
+        let mut journal = journal_changes(std::slice::from_ref(&first), std::slice::from_ref(&second), "2025-01-01 10:00:00");
+        journal.extend(journal_changes(&[second], &[removed], "2025-01-02 10:00:00"));
+
+        assert_eq!(journal_covers(&journal), ["celeste-1.png", "celeste-2.png", "celeste-2.png"]);
+    }
+}
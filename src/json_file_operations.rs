@@ -5,58 +5,82 @@ use std::io::{BufReader, Write};
 use std::fs::File;
 use serde::{Deserialize, Serialize};
 use anyhow::anyhow; // So i can have easy error handling with anyhow
-use crate::enums::Rating;
+use crate::{enums::{GameStatus, Rating}, platforms::played_on, notes::redact_spoilers, playtime::duration_seconds, tags::has_tag};
 use std::result::Result;
+use std::time::Duration;
 
-#[derive(Serialize, Deserialize, Clone)]
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
 pub struct Game {
     pub name: String,
     pub rating: Rating,
-    pub times_played: u8,
+    pub times_played: u32, // Was a u8 until it overflowed for roguelikes, old logs read in as they are
     pub last_playthrough: String,
-    pub notes: String
+    pub notes: String,
+    #[serde(default)] // Logs saved before tags existed don't have this field
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub platform: String, // Main platform the game is played on (Empty if not set)
+    #[serde(default)]
+    pub storefront: String, // Where it was bought (Steam, GOG, eShop...)
+    #[serde(default)]
+    pub playthroughs: Vec<Playthrough>, // Only runs logged since playthroughs were added, so this can be shorter than times_played
+    #[serde(default, with = "duration_seconds")]
+    pub play_time: Duration, // Total time played across every run (Saved as seconds)
+    #[serde(default)]
+    pub executables: Vec<String>, // Executable names/paths the process watcher looks for
+    #[serde(default)]
+    pub status: Option<GameStatus>,
+    #[serde(default)]
+    pub release_year: Option<i32>, // Release details are filled in from a metadata lookup
+    #[serde(default)]
+    pub developer: String,
+    #[serde(default)]
+    pub genres: Vec<String>,
+    #[serde(default)]
+    pub cover: String, // Thumbnail file name in the covers folder (Empty for no cover)
 }
 
-// Read a parse JSON from text file into a vector 
-pub fn reading_json() -> Vec<Game> // Result is wrapped around incase there is an error returned
-{
-    let file = File::open("GameLog.Json"); // Gets file contents
-
-    let reader = match file {
-        Ok(games) => BufReader::new(games),
-        Err(_) => return Vec::new()
-    };
+/// A single run through a game
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct Playthrough {
+    pub date: String,
+    #[serde(default)]
+    pub platform: String,
+    #[serde(default)]
+    pub storefront: String,
+    #[serde(default, with = "duration_seconds")]
+    pub play_time: Duration, // Time recorded by the process watcher for this run
+    #[serde(default)]
+    pub note: String, // Anything worth remembering about this run
+}
 
-    let game = serde_json::from_reader(reader); // Adds every JSON entry in the text file to a vector
+const TRASH_FILE: &str = "GameLogTrash.Json"; // Games moved to the trash, kept until they're deleted for good
 
-    // Custom try catch statement using match. If an error is found, an empty vector will be produced, if not the returned vector held in the 'game' variable will be produced.
-    // This is to make sure the program will run if an empty list is found (it won't if line 22 just propagates errors with ?)
-    match game {
-        Ok(games) => games, // Either return the parsed Json List, or an error 
-        Err(_e) => Vec::new()
+// Read the trash the same way as the log (Empty if there isn't one yet)
+pub fn reading_trash() -> Vec<Game>
+{
+    match File::open(TRASH_FILE) {
+        Ok(file) => serde_json::from_reader(BufReader::new(file)).unwrap_or_default(),
+        Err(_) => Vec::new(),
     }
 }
 
-// Create a Game and add it's Json data to the text file
-pub fn save_to_file(game_log: &Vec<Game>) -> Result<(), Box<dyn std::error::Error>>
+pub fn save_trash(trash: &[Game]) -> Result<(), Box<dyn std::error::Error>>
 {
-    // Serialising a the game_log into JSON and overwriting the previous file with this new data. It's literally the same with the new data added
-
-     let new_json = serde_json::to_string_pretty(&game_log)?;
+    let new_json = serde_json::to_string_pretty(trash)?;
 
-    // Write back to file (overwrite)
     let mut file = OpenOptions::new()
-        .create(true)// If file doesn't exist, Create it
+        .create(true)
         .write(true)
         .truncate(true)
-        .open("GameLog.Json")?;
+        .open(TRASH_FILE)?;
 
     file.write_all(new_json.as_bytes())?;
 
     Ok(())
 }
 
-pub fn search_for_game(game_log: &Vec<Game>, query: &str) -> Result<usize, anyhow::Error>
+pub fn search_for_game(game_log: &[Game], query: &str) -> Result<usize, anyhow::Error>
 {
     let query_lower = query.to_lowercase(); // lowercase so it wont be case sensitive to users
     for (i, game) in game_log.iter().enumerate() {
@@ -66,3 +90,76 @@ pub fn search_for_game(game_log: &Vec<Game>, query: &str) -> Result<usize, anyho
     }
     Err(anyhow!("No Game")) // If no game name is found, return error
 }
+
+/// A search typed into the main window, split into its parts. Every part has to match a game for it to be found
+#[derive(Default, Debug, PartialEq)]
+pub struct SearchQuery {
+    pub name: String, // Lowercase, matched exactly (Empty for any name)
+    pub tags: Vec<String>,
+    pub platforms: Vec<String>,
+    pub statuses: Vec<GameStatus>,
+    pub notes: Vec<String>, // Lowercase words the notes have to contain
+    pub spoilers: bool, // Look in spoilers too when searching notes
+}
+
+impl SearchQuery {
+    /// Read a query that can mix a game's name with `tag:`, `platform:`, `status:` and `note:` filters, e.g. "tag:rpg platform:Switch" or "tag:rpg Minecraft".
+    ///
+    /// Tags and platforms with spaces in their name can be written with underscores (tag:Open_World, platform:Steam_Deck).
+    /// Spoilers in notes are skipped unless the query has `spoilers:show` in it
+    pub fn parse(query: &str) -> SearchQuery
+    {
+        let mut search = SearchQuery::default();
+        let mut name_words = Vec::new();
+
+        for word in query.split_whitespace() {
+            if let Some(tag) = word.strip_prefix("tag:").filter(|tag| !tag.is_empty()) {
+                search.tags.push(tag.replace('_', " "));
+            }
+            else if let Some(platform) = word.strip_prefix("platform:").filter(|platform| !platform.is_empty()) {
+                search.platforms.push(platform.replace('_', " "));
+            }
+            else if let Some(status) = word.strip_prefix("status:").and_then(GameStatus::parse) {
+                search.statuses.push(status);
+            }
+            else if let Some(note) = word.strip_prefix("note:").filter(|note| !note.is_empty()) {
+                search.notes.push(note.to_lowercase());
+            }
+            else if word.eq_ignore_ascii_case("spoilers:show") {
+                search.spoilers = true;
+            }
+            else {
+                name_words.push(word);
+            }
+        }
+
+        search.name = name_words.join(" ").to_lowercase();
+        search
+    }
+
+    pub fn matches(&self, game: &Game) -> bool
+    {
+        (self.name.is_empty() || game.name.to_lowercase() == self.name)
+            && self.tags.iter().all(|tag| has_tag(game, tag))
+            && self.platforms.iter().all(|platform| played_on(game, platform))
+            && self.statuses.iter().all(|status| game.status == Some(*status))
+            && (self.notes.is_empty() || {
+                let notes = if self.spoilers { game.notes.clone() } else { redact_spoilers(&game.notes, " ") };
+                let notes = notes.to_lowercase();
+                self.notes.iter().all(|note| notes.contains(note.as_str()))
+            })
+    }
+}
+
+/// Search the log with a `SearchQuery`. If a name is given it has to match exactly (ignoring case) like `search_for_game`.
+/// Returns the index of every game found
+pub fn search_games(game_log: &[Game], query: &str) -> Vec<usize>
+{
+    let search = SearchQuery::parse(query);
+
+    game_log.iter()
+        .enumerate()
+        .filter(|(_, game)| search.matches(game))
+        .map(|(i, _)| i)
+        .collect()
+}
@@ -5,10 +5,39 @@ pub mod adding;
 pub mod editing;
 pub mod removing;
 pub mod clock;
+pub mod validation;
+pub mod settings;
+pub mod star_rating;
+pub mod tags;
+pub mod export;
+pub mod platforms;
+pub mod statistics;
+pub mod playtime;
+pub mod process_watcher;
+pub mod importers;
+pub mod status;
+pub mod metadata;
+pub mod covers;
+pub mod game_views;
+pub mod changes;
+pub mod command_palette;
+pub mod cli;
+pub mod storage;
+pub mod outside_changes;
+pub mod instance_lock;
+pub mod backups;
+pub mod journal;
+pub mod notes;
 use eframe::{egui::{self, ViewportBuilder}, run_native, App, NativeOptions};
 use crate::app_setup::GameLog;
 
 fn main() -> Result<(), eframe::Error> {
+    // Commands like `my_gui_app log Celeste` run without opening the window
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if !args.is_empty() {
+        std::process::exit(cli::run(&args));
+    }
+
     let native_options = NativeOptions { viewport: ViewportBuilder::default()
         .with_min_inner_size(egui::Vec2::new(500.0, 500.0)), // Minimum Window Size (Prevents a bunch of wrapping issues)
         ..Default::default() // All other paramaters are set to default
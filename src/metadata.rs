@@ -0,0 +1,405 @@
+// Crates/Imports
+use std::sync::{mpsc::{self, Receiver, TryRecvError}, Arc};
+use std::thread;
+use std::time::{Duration, Instant};
+use anyhow::anyhow;
+use chrono::{DateTime, Datelike};
+use eframe::egui::{Button, Color32, Context, Grid, RichText, TextEdit, Ui, Vec2};
+use serde_json::Value;
+
+use crate::{app_setup::GameLog, settings::{save_settings, Settings}, validation::MAX_NAME_LENGTH};
+
+const TYPING_PAUSE: Duration = Duration::from_millis(500); // Wait for typing to stop before searching
+const MIN_QUERY_LENGTH: usize = 3;
+const RESULT_LIMIT: usize = 8;
+
+/// What a metadata source knows about one game
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GameMetadata {
+    pub title: String,
+    pub release_year: Option<i32>,
+    pub developer: String,
+    pub genres: Vec<String>,
+    pub platforms: Vec<String>,
+    pub cover_url: Option<String>,
+}
+
+impl GameMetadata {
+    /// "Super Metroid (1994) - Nintendo R&D1" for picking between results
+    pub fn summary(&self) -> String
+    {
+        let mut summary = self.title.clone();
+
+        if let Some(year) = self.release_year {
+            summary.push_str(&format!(" ({})", year));
+        }
+        if !self.developer.is_empty() {
+            summary.push_str(&format!(" - {}", self.developer));
+        }
+
+        summary
+    }
+}
+
+/// "1994 · Nintendo R&D1 · Platform, Adventure" (Empty if a game has no details)
+pub fn details_line(release_year: Option<i32>, developer: &str, genres: &[String]) -> String
+{
+    let mut details: Vec<String> = release_year.map(|year| year.to_string()).into_iter().collect();
+    details.extend([developer.to_string(), genres.join(", ")].into_iter().filter(|detail| !detail.is_empty()));
+    details.join(" · ")
+}
+
+/// Somewhere game details can be looked up by title (Searches run on a background thread, hence Send + Sync)
+pub trait MetadataProvider: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    /// Games matching a title, best matches first
+    fn search(&self, title: &str) -> Result<Vec<GameMetadata>, anyhow::Error>;
+}
+
+/// Client for IGDB's v4 API (or anything that answers the same way)
+pub struct IgdbClient {
+    base_url: String,
+    client_id: String,
+    access_token: String,
+    agent: ureq::Agent,
+}
+
+impl IgdbClient {
+    pub fn new(base_url: &str, client_id: &str, access_token: &str) -> Self
+    {
+        Self {
+            base_url: base_url.trim().trim_end_matches('/').to_string(),
+            client_id: client_id.trim().to_string(),
+            access_token: access_token.trim().to_string(),
+            agent: ureq::AgentBuilder::new().timeout(Duration::from_secs(10)).build(),
+        }
+    }
+
+    // IGDB queries are written in its own "Apicalypse" language, sent as the request body
+    fn query(title: &str) -> String
+    {
+        format!(
+            "search \"{}\"; fields name, first_release_date, genres.name, platforms.name, cover.url, cover.image_id, involved_companies.developer, involved_companies.company.name; limit {};",
+            title.replace('\\', "").replace('"', "\\\""),
+            RESULT_LIMIT
+        )
+    }
+}
+
+// Names from a list of {"name": ...} objects
+fn names(value: Option<&Value>) -> Vec<String>
+{
+    value.and_then(Value::as_array).into_iter().flatten()
+        .filter_map(|item| item.get("name").and_then(Value::as_str))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Turn one game from an IGDB response into metadata (None if it has no name)
+fn parse_igdb_game(game: &Value) -> Option<GameMetadata>
+{
+    let title = game.get("name").and_then(Value::as_str)?.to_string();
+
+    let release_year = game.get("first_release_date").and_then(Value::as_i64)
+        .and_then(|seconds| DateTime::from_timestamp(seconds, 0))
+        .map(|date| date.year());
+
+    let developer = game.get("involved_companies").and_then(Value::as_array).into_iter().flatten()
+        .find(|company| company.get("developer").and_then(Value::as_bool).unwrap_or(false))
+        .and_then(|company| company.pointer("/company/name").and_then(Value::as_str))
+        .unwrap_or_default()
+        .to_string();
+
+    // Covers come back as "//images.igdb.com/.../t_thumb/id.jpg", swapped for the bigger size
+    let cover_url = game.get("cover").and_then(|cover| {
+        cover.get("url").and_then(Value::as_str)
+            .map(|url| format!("{}{}", if url.starts_with("//") { "https:" } else { "" }, url.replace("t_thumb", "t_cover_big")))
+            .or_else(|| cover.get("image_id").and_then(Value::as_str)
+                .map(|id| format!("https://images.igdb.com/igdb/image/upload/t_cover_big/{}.jpg", id)))
+    });
+
+    Some(GameMetadata {
+        title,
+        release_year,
+        developer,
+        genres: names(game.get("genres")),
+        platforms: names(game.get("platforms")),
+        cover_url,
+    })
+}
+
+impl MetadataProvider for IgdbClient {
+    fn name(&self) -> &'static str {
+        "IGDB"
+    }
+
+    fn search(&self, title: &str) -> Result<Vec<GameMetadata>, anyhow::Error> {
+        let response = self.agent.post(&format!("{}/games", self.base_url))
+            .set("Client-ID", &self.client_id)
+            .set("Authorization", &format!("Bearer {}", self.access_token))
+            .set("Accept", "application/json")
+            .send_string(&Self::query(title))
+            .map_err(|error| match error {
+                ureq::Error::Status(401 | 403, _) => anyhow!("The metadata server didn't accept the client ID/token"),
+                ureq::Error::Status(code, _) => anyhow!("The metadata server answered with error {}", code),
+                other => anyhow!("Couldn't reach the metadata server ({})", other),
+            })?;
+
+        let json: Value = response.into_json()?;
+        let games = json.as_array().ok_or_else(|| anyhow!("Unexpected answer from the metadata server"))?;
+
+        Ok(games.iter().filter_map(parse_igdb_game).collect())
+    }
+}
+
+/// The provider set up in the settings (None until a client ID has been entered)
+pub fn provider_from_settings(settings: &Settings) -> Option<Arc<dyn MetadataProvider>>
+{
+    if settings.metadata_client_id.trim().is_empty() || settings.metadata_url.trim().is_empty() {
+        return None
+    }
+
+    Some(Arc::new(IgdbClient::new(&settings.metadata_url, &settings.metadata_client_id, &settings.metadata_token)))
+}
+
+/// Looks a name up as it's typed, waiting for a pause in typing and doing the search off the GUI thread
+#[derive(Default)]
+pub struct MetadataLookup {
+    query: String, // Name the results (or the running search) are for
+    typed_at: Option<Instant>, // When the name last changed, None once it has been searched
+    pending: Option<Receiver<Result<Vec<GameMetadata>, String>>>,
+    pub results: Vec<GameMetadata>,
+    pub error: String,
+}
+
+impl MetadataLookup {
+    /// Call every frame with the current name. Starts a search once typing stops and collects finished searches
+    pub fn update(&mut self, name: &str, provider: Option<&Arc<dyn MetadataProvider>>, ctx: &Context)
+    {
+        let name = name.trim();
+
+        if name != self.query {
+            self.query = name.to_string();
+            self.typed_at = Some(Instant::now());
+            self.pending = None; // Results for the old name aren't wanted any more
+            self.results.clear();
+            self.error.clear();
+        }
+
+        if let Some(receiver) = &self.pending {
+            match receiver.try_recv() {
+                Ok(Ok(results)) => {
+                    self.results = results;
+                    self.pending = None;
+                },
+                Ok(Err(error)) => {
+                    self.error = error;
+                    self.pending = None;
+                },
+                Err(TryRecvError::Empty) => ctx.request_repaint_after(Duration::from_millis(100)),
+                Err(TryRecvError::Disconnected) => self.pending = None,
+            }
+        }
+
+        let Some(typed_at) = self.typed_at else { return };
+        let Some(provider) = provider.filter(|_| self.query.chars().count() >= MIN_QUERY_LENGTH) else {
+            self.typed_at = None;
+            return
+        };
+
+        if typed_at.elapsed() < TYPING_PAUSE {
+            ctx.request_repaint_after(TYPING_PAUSE - typed_at.elapsed()); // Come back when the pause is over even if nothing else happens
+            return
+        }
+
+        let (sender, receiver) = mpsc::channel();
+        let provider = Arc::clone(provider);
+        let query = self.query.clone();
+
+        thread::spawn(move || {
+            let _ = sender.send(provider.search(&query).map_err(|error| error.to_string()));
+        });
+
+        self.pending = Some(receiver);
+        self.typed_at = None;
+    }
+
+    pub fn is_searching(&self) -> bool
+    {
+        self.pending.is_some() || self.typed_at.is_some()
+    }
+
+    /// A result was picked and its title put in the name box, so don't search for that title again
+    pub fn accept(&mut self, title: &str)
+    {
+        self.query = title.trim().to_string();
+        self.typed_at = None;
+        self.pending = None;
+        self.results.clear();
+        self.error.clear();
+    }
+}
+
+// Metadata GUI Code
+impl GameLog {
+    /// Results for the name being typed in the add window. Picking one fills in the name, platform and details
+    pub fn metadata_suggestions(&mut self, ui: &mut Ui)
+    {
+        self.metadata_lookup.update(&self.add_game_name, self.metadata_provider.as_ref(), ui.ctx());
+
+        if self.metadata_lookup.is_searching() && self.metadata_provider.is_some() && self.add_game_name.trim().chars().count() >= MIN_QUERY_LENGTH {
+            ui.label(RichText::new("Looking up details...").small().weak());
+        }
+
+        if !self.metadata_lookup.error.is_empty() {
+            ui.label(RichText::new(&self.metadata_lookup.error).small().color(Color32::RED));
+        }
+
+        let mut picked = None;
+
+        for (i, result) in self.metadata_lookup.results.iter().enumerate() {
+            if ui.add(Button::new(RichText::new(format!("Fill from: {}", result.summary())).small()).frame(false)).clicked() {
+                picked = Some(i);
+            }
+        }
+
+        if let Some(i) = picked {
+            let metadata = self.metadata_lookup.results[i].clone();
+
+            self.add_game_name = metadata.title.chars().take(MAX_NAME_LENGTH).collect();
+            self.metadata_lookup.accept(&self.add_game_name);
+
+            // Only platforms the user has in their list are picked, the first one that matches
+            if let Some(platform) = self.settings.platforms.iter()
+                .find(|platform| metadata.platforms.iter().any(|name| name.eq_ignore_ascii_case(platform))) {
+                self.add_game_platform = platform.clone();
+            }
+
+            self.add_game_metadata = Some(metadata);
+        }
+
+        if let Some(metadata) = &self.add_game_metadata {
+            let mut clear = false;
+            ui.horizontal(|ui| {
+                ui.label(RichText::new(details_line(metadata.release_year, &metadata.developer, &metadata.genres)).small().italics());
+                clear = ui.small_button("✖").on_hover_text("Don't use these details").clicked();
+            });
+            if clear {
+                self.add_game_metadata = None;
+            }
+        }
+    }
+
+    /// Settings for where details are looked up from
+    pub fn metadata_settings(&mut self, ui: &mut Ui)
+    {
+        let input_size = Vec2::new(250.0, 20.0);
+        let mut changed = false;
+
+        ui.label(RichText::new("Game Details Lookup (IGDB)").strong());
+
+        Grid::new("metadata_settings").num_columns(2).show(ui, |ui| {
+            ui.label("API URL");
+            changed |= ui.add_sized(input_size, TextEdit::singleline(&mut self.settings.metadata_url)).changed();
+            ui.end_row();
+
+            ui.label("Client ID");
+            changed |= ui.add_sized(input_size, TextEdit::singleline(&mut self.settings.metadata_client_id).hint_text("Leave empty to turn lookups off")).changed();
+            ui.end_row();
+
+            ui.label("Access Token");
+            changed |= ui.add_sized(input_size, TextEdit::singleline(&mut self.settings.metadata_token).password(true)).changed();
+            ui.end_row();
+        });
+
+        if changed {
+            self.metadata_provider = provider_from_settings(&self.settings);
+
+            if save_settings(&self.settings).is_err() {
+                println!("Error Saving Settings");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::path::Path;
+    use super::*;
+
+    /// Answer one request with a canned response, handing back what was asked for
+    fn mock_server(status: &str, body: String) -> (String, thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/v4", listener.local_addr().unwrap());
+        let status = status.to_string();
+
+        let handle = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+            let mut request = String::new();
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(length) = line.to_lowercase().strip_prefix("content-length:") {
+                    content_length = length.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+            let mut request_body = vec![0; content_length];
+            reader.read_exact(&mut request_body).unwrap();
+            request.push_str(&String::from_utf8(request_body).unwrap());
+
+            let mut stream = stream;
+            write!(stream, "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}", status, body.len(), body).unwrap();
+
+            request
+        });
+
+        (url, handle)
+    }
+
+    #[test]
+    fn searches_an_igdb_style_api() {
+        let body = std::fs::read_to_string(Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures/igdb_games.json")).unwrap();
+        let (url, server) = mock_server("200 OK", body);
+
+        let results = IgdbClient::new(&url, "my-client", "my-token").search("Super \"Metroid\"").unwrap();
+        let request = server.join().unwrap();
+
+        assert!(request.starts_with("POST /v4/games "));
+        assert!(request.to_lowercase().contains("client-id: my-client"));
+        assert!(request.contains("Bearer my-token"));
+        assert!(request.contains(r#"search "Super \"Metroid\"";"#));
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0], GameMetadata {
+            title: String::from("Super Metroid"),
+            release_year: Some(1994),
+            developer: String::from("Nintendo R&D1"),
+            genres: vec![String::from("Platform"), String::from("Adventure")],
+            platforms: vec![String::from("SNES"), String::from("Switch")],
+            cover_url: Some(String::from("https://images.igdb.com/igdb/image/upload/t_cover_big/co1x7m.jpg")),
+        });
+        assert_eq!(results[1].release_year, None);
+        assert_eq!(results[1].developer, "");
+        assert_eq!(results[1].cover_url.as_deref(), Some("https://images.igdb.com/igdb/image/upload/t_cover_big/abc123.jpg"));
+    }
+
+    #[test]
+    fn rejected_credentials_are_reported() {
+        let (url, server) = mock_server("401 Unauthorized", String::from("{\"message\": \"Authorization Failure\"}"));
+
+        let error = IgdbClient::new(&url, "wrong", "wrong").search("Celeste").unwrap_err();
+        server.join().unwrap();
+
+        assert!(error.to_string().contains("client ID/token"));
+    }
+}
@@ -0,0 +1,396 @@
+// Crates/Imports
+use eframe::egui::{Frame, Id, Label, RichText, ScrollArea, Sense, TextEdit, Ui};
+use unicode_segmentation::UnicodeSegmentation;
+
+const INDENT_WIDTH: f32 = 16.0; // Per level of a nested list
+const SPOILER_START: &str = ":::spoiler"; // On its own line, hides everything up to a line with just ":::"
+const SPOILER_END: &str = ":::";
+pub const SPOILER_REDACTED: &str = "[Spoiler]"; // What spoilers are swapped for in exports and short previews
+
+/// What a block of notes is shown as
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BlockKind {
+    Heading(u8), // 1 is the biggest
+    Bullet,
+    Numbered(u32),
+    Paragraph,
+}
+
+/// A run of text with the same formatting
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Span {
+    pub text: String,
+    pub bold: bool,
+    pub italic: bool,
+    pub code: bool,
+    pub spoiler: bool, // ||Hidden|| until clicked
+    pub link: Option<String>, // Only http(s) links, anything else is left as plain text
+}
+
+/// One heading, list item or paragraph of the notes
+#[derive(Clone, Debug, PartialEq)]
+pub struct Block {
+    pub kind: BlockKind,
+    pub indent: usize, // How deep a list item is nested
+    pub spans: Vec<Span>,
+    pub spoiler: Option<usize>, // Which spoiler block it's in, if any (Numbered from 0 in the order they appear)
+}
+
+/// First line of `text`, cut down to `length` characters with "..." if anything was left off.
+///
+/// Cuts between graphemes, so accents, emoji etc. are never split (Slicing bytes used to panic on them)
+pub fn shorten(text: &str, length: usize) -> String
+{
+    let text = text.trim();
+    let line = text.lines().next().unwrap_or_default().trim_end();
+    let shortened: String = line.graphemes(true).take(length).collect();
+
+    if shortened.len() < text.len() {
+        format!("{}...", shortened)
+    }
+    else {
+        shortened
+    }
+}
+
+// Add text to the last span if it has the same formatting, otherwise start a new one
+fn push_text(spans: &mut Vec<Span>, style: &Span, text: &str)
+{
+    match spans.last_mut() {
+        Some(last) if last.bold == style.bold && last.italic == style.italic && last.code == style.code && last.spoiler == style.spoiler && last.link == style.link => last.text.push_str(text),
+        _ => spans.push(Span { text: text.to_string(), ..style.clone() }),
+    }
+}
+
+// **bold**, *italic*, `code`, ||spoilers|| and [links](https://...). Markers without a partner are left as they are
+fn parse_inline(text: &str) -> Vec<Span>
+{
+    let mut spans = Vec::new();
+    let mut style = Span::default();
+    let mut rest = text;
+
+    while let Some(c) = rest.chars().next() {
+        let after = &rest[c.len_utf8()..];
+
+        if style.code {
+            if c == '`' {
+                style.code = false;
+            }
+            else {
+                push_text(&mut spans, &style, &rest[..c.len_utf8()]);
+            }
+            rest = after;
+            continue;
+        }
+
+        if c == '\\' && let Some(escaped) = after.chars().next() {
+            push_text(&mut spans, &style, &after[..escaped.len_utf8()]);
+            rest = &after[escaped.len_utf8()..];
+        }
+        else if rest.starts_with("||") && (style.spoiler || rest[2..].contains("||")) {
+            style.spoiler = !style.spoiler;
+            rest = &rest[2..];
+        }
+        else if rest.starts_with("**") && (style.bold || rest[2..].contains("**")) {
+            style.bold = !style.bold;
+            rest = &rest[2..];
+        }
+        else if c == '*' && (style.italic || after.contains('*')) {
+            style.italic = !style.italic;
+            rest = after;
+        }
+        else if c == '`' && after.contains('`') {
+            style.code = true;
+            rest = after;
+        }
+        else if c == '['
+            && let Some((label, link_rest)) = after.split_once("](")
+            && let Some((url, link_rest)) = link_rest.split_once(')')
+            && !label.contains(']') {
+            let safe = url.starts_with("https://") || url.starts_with("http://");
+            let link = Span { link: safe.then(|| url.to_string()), ..style.clone() };
+            push_text(&mut spans, &link, label);
+            rest = link_rest;
+        }
+        else {
+            push_text(&mut spans, &style, &rest[..c.len_utf8()]);
+            rest = after;
+        }
+    }
+
+    spans
+}
+
+// "- item", "* item" or "+ item"
+fn bullet_item(line: &str) -> Option<&str>
+{
+    ["- ", "* ", "+ "].iter().find_map(|marker| line.strip_prefix(marker))
+}
+
+// "1. item"
+fn numbered_item(line: &str) -> Option<(u32, &str)>
+{
+    let (number, item) = line.split_once(". ")?;
+    Some((number.parse().ok()?, item))
+}
+
+/// Split notes into blocks. Lines next to each other are one paragraph, and blank lines start a new one
+pub fn parse_markdown(text: &str) -> Vec<Block>
+{
+    let mut blocks: Vec<Block> = Vec::new();
+    let mut paragraph: Vec<&str> = Vec::new();
+    let mut spoiler = None;
+    let mut spoiler_count = 0;
+
+    let end_paragraph = |blocks: &mut Vec<Block>, paragraph: &mut Vec<&str>, spoiler: Option<usize>| {
+        if !paragraph.is_empty() {
+            blocks.push(Block { kind: BlockKind::Paragraph, indent: 0, spans: parse_inline(&paragraph.join(" ")), spoiler });
+            paragraph.clear();
+        }
+    };
+
+    for line in text.lines() {
+        let trimmed = line.trim_start();
+        let indent = (line.len() - trimmed.len()) / 2;
+        let trimmed = trimmed.trim_end();
+
+        if spoiler.is_none() && trimmed.eq_ignore_ascii_case(SPOILER_START) {
+            end_paragraph(&mut blocks, &mut paragraph, spoiler);
+            spoiler = Some(spoiler_count);
+            spoiler_count += 1;
+            continue;
+        }
+        if spoiler.is_some() && trimmed == SPOILER_END {
+            end_paragraph(&mut blocks, &mut paragraph, spoiler);
+            spoiler = None;
+            continue;
+        }
+
+        let hashes = trimmed.chars().take_while(|&c| c == '#').count();
+        let block = if (1..=6).contains(&hashes) && trimmed[hashes..].starts_with(' ') {
+            Some((BlockKind::Heading(hashes as u8), trimmed[hashes..].trim()))
+        }
+        else if let Some(item) = bullet_item(trimmed) {
+            Some((BlockKind::Bullet, item))
+        }
+        else {
+            numbered_item(trimmed).map(|(number, item)| (BlockKind::Numbered(number), item))
+        };
+
+        match block {
+            Some((kind, content)) => {
+                end_paragraph(&mut blocks, &mut paragraph, spoiler);
+                blocks.push(Block { kind, indent, spans: parse_inline(content), spoiler });
+            },
+            None if trimmed.is_empty() => end_paragraph(&mut blocks, &mut paragraph, spoiler),
+            None => paragraph.push(trimmed),
+        }
+    }
+    end_paragraph(&mut blocks, &mut paragraph, spoiler); // An unclosed spoiler block runs to the end
+
+    blocks
+}
+
+/// Notes with every spoiler swapped for `replacement` (Exports, searches and previews, unless spoilers are asked for).
+///
+/// Pairs up `||` across the whole notes rather than per paragraph, so it can hide more than the GUI would but never less
+pub fn redact_spoilers(text: &str, replacement: &str) -> String
+{
+    let mut without_blocks = String::new();
+    let mut in_block = false;
+
+    for line in text.split_inclusive('\n') {
+        let trimmed = line.trim();
+
+        if !in_block && trimmed.eq_ignore_ascii_case(SPOILER_START) {
+            in_block = true;
+            without_blocks.push_str(replacement);
+            if line.ends_with('\n') {
+                without_blocks.push('\n');
+            }
+        }
+        else if in_block {
+            in_block = trimmed != SPOILER_END;
+        }
+        else {
+            without_blocks.push_str(line);
+        }
+    }
+
+    let mut redacted = String::new();
+    let mut rest = without_blocks.as_str();
+
+    while let Some(c) = rest.chars().next() {
+        let after = &rest[c.len_utf8()..];
+
+        if c == '\\' && let Some(escaped) = after.chars().next() {
+            redacted.push_str(&rest[..c.len_utf8() + escaped.len_utf8()]);
+            rest = &after[escaped.len_utf8()..];
+        }
+        else if rest.starts_with("||") && let Some((_, spoiler_rest)) = rest[2..].split_once("||") {
+            redacted.push_str(replacement);
+            rest = spoiler_rest;
+        }
+        else {
+            redacted.push(c);
+            rest = after;
+        }
+    }
+
+    redacted
+}
+
+// Spoilers stay hidden until clicked, and clicking again hides them. Remembered for as long as the app is open
+fn spoiler_toggle(ui: &Ui, id: Id, clicked: bool) -> bool
+{
+    let shown = ui.data(|data| data.get_temp::<bool>(id).unwrap_or(false));
+    if clicked {
+        ui.data_mut(|data| data.insert_temp(id, !shown));
+    }
+    shown != clicked
+}
+
+/// Show notes with their Markdown formatting. Spoilers are covered up until clicked
+pub fn markdown_view(ui: &mut Ui, text: &str)
+{
+    let blocks = parse_markdown(text);
+
+    for (i, block) in blocks.iter().enumerate() {
+        if let Some(spoiler) = block.spoiler {
+            let id = ui.id().with(("spoiler_block", spoiler));
+            let shown = ui.data(|data| data.get_temp::<bool>(id).unwrap_or(false));
+
+            // One button at the top of each spoiler block
+            if i == 0 || blocks[i - 1].spoiler != block.spoiler {
+                let clicked = ui.small_button(if shown { "Hide Spoiler" } else { "Spoiler (Click to Show)" }).clicked();
+                if !spoiler_toggle(ui, id, clicked) {
+                    continue;
+                }
+            }
+            else if !shown {
+                continue;
+            }
+        }
+
+        ui.horizontal_wrapped(|ui| {
+            ui.spacing_mut().item_spacing.x = 0.0;
+            ui.add_space(block.indent as f32 * INDENT_WIDTH);
+
+            match block.kind {
+                BlockKind::Bullet => { ui.label("•  "); },
+                BlockKind::Numbered(number) => { ui.label(format!("{}. ", number)); },
+                _ => (),
+            }
+
+            for (j, span) in block.spans.iter().enumerate() {
+                let mut text = RichText::new(&span.text);
+                if let BlockKind::Heading(level) = block.kind {
+                    text = text.size(match level { 1 => 22.0, 2 => 19.0, _ => 16.0 }).strong();
+                }
+                if span.bold {
+                    text = text.strong();
+                }
+                if span.italic {
+                    text = text.italics();
+                }
+                if span.code {
+                    text = text.code();
+                }
+
+                if span.spoiler {
+                    // Drawn in the background colour until clicked, so it takes up the same room without being readable
+                    let id = ui.id().with(("spoiler", i, j));
+                    let shown = spoiler_toggle(ui, id, false);
+                    let cover = ui.visuals().widgets.inactive.bg_fill;
+                    let text = if shown { text.background_color(ui.visuals().faint_bg_color) } else { text.color(cover).background_color(cover) };
+
+                    let clicked = ui.add(Label::new(text).sense(Sense::click()))
+                        .on_hover_text(if shown { "Click to hide" } else { "Spoiler, click to show" })
+                        .clicked();
+                    spoiler_toggle(ui, id, clicked);
+                    continue;
+                }
+
+                match &span.link {
+                    Some(url) => { ui.hyperlink_to(text, url).on_hover_text(url); },
+                    None => { ui.label(text); },
+                }
+            }
+        });
+
+        if matches!(block.kind, BlockKind::Heading(_) | BlockKind::Paragraph) {
+            ui.add_space(4.0);
+        }
+    }
+}
+
+/// Multi-line notes box, with a tab to see how the Markdown comes out
+pub fn notes_editor(ui: &mut Ui, notes: &mut String, preview: &mut bool, hint: &str, width: f32)
+{
+    ui.vertical(|ui| {
+        ui.set_width(width);
+
+        ui.horizontal(|ui| {
+            ui.selectable_value(preview, false, "Write");
+            ui.selectable_value(preview, true, "Preview");
+            ui.label(RichText::new("Markdown").weak())
+                .on_hover_text("# Heading\n- List item\n1. Numbered item\n**bold**  *italic*  `code`\n[link](https://example.com)\n||spoiler||\n:::spoiler\nSpoiler block\n:::");
+        });
+
+        if *preview {
+            Frame::group(ui.style()).show(ui, |ui| {
+                ui.set_width(width);
+                ScrollArea::vertical().id_salt("notes_preview").max_height(150.0).show(ui, |ui| {
+                    if notes.trim().is_empty() {
+                        ui.label(RichText::new("Nothing to preview").italics());
+                    }
+                    markdown_view(ui, notes);
+                });
+            });
+        }
+        else {
+            ui.add(TextEdit::multiline(notes).desired_rows(5).desired_width(width).hint_text(hint));
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn span(text: &str) -> Span {
+        Span { text: text.to_string(), ..Span::default() }
+    }
+
+    #[test]
+    fn parses_headings_lists_and_links() {
+        let blocks = parse_markdown("# Thoughts\nGreat **boss\nfights**, weak *story*\n\n- [Guide](https://example.com)\n  - [Sketchy](javascript:alert)\n2. Tenth `run`");
+
+        assert_eq!(blocks[0], Block { kind: BlockKind::Heading(1), indent: 0, spans: vec![span("Thoughts")], spoiler: None });
+        assert_eq!(blocks[1].spans, [span("Great "), Span { bold: true, ..span("boss fights") }, span(", weak "), Span { italic: true, ..span("story") }]);
+        assert_eq!(blocks[2].spans, [Span { link: Some(String::from("https://example.com")), ..span("Guide") }]);
+        assert_eq!((blocks[3].kind, blocks[3].indent, &blocks[3].spans), (BlockKind::Bullet, 1, &vec![span("Sketchy")]));
+        assert_eq!(blocks[4].kind, BlockKind::Numbered(2));
+        assert_eq!(parse_inline("2 * 3 = 6")[0], span("2 * 3 = 6")); // Nothing to pair with
+    }
+
+    #[test]
+    fn spoilers_are_marked_and_redacted() {
+        let notes = "Beat it, ||the tower falls|| at the end\n:::spoiler\n# Ending\nIt was a dream\n:::\nGreat music, 2 | 3 pipes";
+
+        let blocks = parse_markdown(notes);
+        assert_eq!(blocks[0].spans[1], Span { spoiler: true, ..span("the tower falls") });
+        assert_eq!(blocks.iter().map(|block| block.spoiler).collect::<Vec<_>>(), [None, Some(0), Some(0), None]);
+
+        assert_eq!(redact_spoilers(notes, SPOILER_REDACTED), "Beat it, [Spoiler] at the end\n[Spoiler]\nGreat music, 2 | 3 pipes");
+        assert_eq!(redact_spoilers("Unclosed\n:::spoiler\nSecret", ""), "Unclosed\n\n");
+    }
+
+    #[test]
+    fn shortens_without_splitting_characters() {
+        assert_eq!(shorten("Pokémon Émeraude", 7), "Pokémon...");
+        assert_eq!(shorten("👨‍👩‍👧 family run", 1), "👨‍👩‍👧...");
+        assert_eq!(shorten("First line\nSecond line", 20), "First line...");
+        assert_eq!(shorten("Short", 10), "Short");
+    }
+}
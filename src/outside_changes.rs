@@ -0,0 +1,300 @@
+// Crates/Imports
+use std::time::{Duration, Instant};
+use eframe::egui::{self, Align2, Context, RichText, ScrollArea};
+
+use crate::{app_setup::GameLog, enums::RatingScale, json_file_operations::Game};
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(2); // How often the stored log is checked for changes made outside the app
+
+/// A game both this window and something else changed, in different ways
+#[derive(Clone, Debug, PartialEq)]
+pub struct MergeConflict {
+    pub name: String,
+    pub mine: Option<Game>, // None if it was deleted
+    pub theirs: Option<Game>,
+    pub keep_mine: bool,
+}
+
+fn find<'a>(games: &'a [Game], name: &str) -> Option<&'a Game>
+{
+    games.iter().find(|game| game.name == name)
+}
+
+/// Games changed on both sides since `base` (The log as it was last saved) that can't be merged without asking
+pub fn merge_conflicts(base: &[Game], mine: &[Game], theirs: &[Game]) -> Vec<MergeConflict>
+{
+    let mut names: Vec<&str> = Vec::new();
+    for game in mine.iter().chain(theirs).chain(base) {
+        if !names.contains(&game.name.as_str()) {
+            names.push(&game.name);
+        }
+    }
+
+    names.into_iter()
+        .filter_map(|name| {
+            let (old, my_game, their_game) = (find(base, name), find(mine, name), find(theirs, name));
+
+            (my_game != their_game && my_game != old && their_game != old).then(|| MergeConflict {
+                name: name.to_string(),
+                mine: my_game.cloned(),
+                theirs: their_game.cloned(),
+                keep_mine: true,
+            })
+        })
+        .collect()
+}
+
+/// Three way merge of two versions of the log that both started from `base`.
+///
+/// A game only one side changed takes that side's version, and `conflicts` picks for the ones both changed.
+/// Their order is kept, with games only added here going in after the game they followed
+pub fn merge_logs(base: &[Game], mine: &[Game], theirs: &[Game], conflicts: &[MergeConflict]) -> Vec<Game>
+{
+    // Which version of a game makes it into the merge (None if it's deleted)
+    let pick = |name: &str| -> Option<Game> {
+        let (old, my_game, their_game) = (find(base, name), find(mine, name), find(theirs, name));
+
+        let kept = if my_game == their_game || their_game == old {
+            my_game
+        }
+        else if my_game == old {
+            their_game
+        }
+        else {
+            match conflicts.iter().find(|conflict| conflict.name == name) {
+                Some(conflict) if !conflict.keep_mine => their_game,
+                _ => my_game,
+            }
+        };
+        kept.cloned()
+    };
+
+    let mut merged: Vec<Game> = theirs.iter().filter_map(|game| pick(&game.name)).collect();
+
+    for (i, game) in mine.iter().enumerate() {
+        if find(theirs, &game.name).is_some() {
+            continue;
+        }
+        let Some(kept) = pick(&game.name) else { continue };
+
+        let position = mine[..i].iter().rev()
+            .find_map(|previous| merged.iter().position(|merged_game| merged_game.name == previous.name))
+            .map_or(0, |position| position + 1);
+        merged.insert(position, kept);
+    }
+
+    merged
+}
+
+// Buttons in the 'Game Log Changed' window
+enum OutsideChoice {
+    Reload,
+    KeepMine,
+    Merge,
+}
+
+// One line describing a side of a conflict
+fn version_summary(game: Option<&Game>, rating_scale: RatingScale) -> String
+{
+    match game {
+        Some(game) => format!("{}, played {} time(s), last {}", rating_scale.display(game.rating), game.times_played, game.last_playthrough),
+        None => String::from("Deleted"),
+    }
+}
+
+// Watching the stored log for changes made outside the app
+impl GameLog {
+    /// Look for outside changes every few seconds, unless one is already waiting to be dealt with
+    pub fn poll_outside_changes(&mut self, ctx: &Context) {
+        if self.outside_change.is_none() && self.last_outside_check.elapsed() >= CHECK_INTERVAL {
+            self.last_outside_check = Instant::now();
+            self.check_outside_changes();
+        }
+
+        ctx.request_repaint_after(CHECK_INTERVAL); // Keep checking even when nobody is touching the app
+    }
+
+    /// Read the log again if something else has changed it. Returns true if the change needs dealing with before anything is saved
+    pub fn check_outside_changes(&mut self) -> bool {
+        if !self.storage.changed_elsewhere().unwrap_or(false) {
+            return false;
+        }
+
+        let theirs = match self.storage.load() {
+            Ok(theirs) => theirs,
+            Err(error) => {
+                println!("Error Reading the Changed Game Log: {}", error);
+                return false;
+            }
+        };
+
+        // Already the same (e.g. the file was saved without changing anything)
+        if theirs == self.game_file_contents {
+            self.synced_games = theirs;
+            return false;
+        }
+
+        // Nothing can be changed while read only, so the other copy's changes are just shown
+        if self.read_only() {
+            self.game_file_contents = theirs.clone();
+            self.synced_games = theirs;
+            self.reload_journal();
+            self.refresh_search();
+            return false;
+        }
+
+        self.merge_conflicts = merge_conflicts(&self.synced_games, &self.game_file_contents, &theirs);
+        self.outside_change = Some(theirs);
+        true
+    }
+
+    /// Whether this window has changes that couldn't be saved because of an outside change
+    pub fn has_unsaved_changes(&self) -> bool {
+        self.game_file_contents != self.synced_games
+    }
+
+    /// Drop this window's log for the changed one. Can be undone
+    pub fn reload_outside_change(&mut self) {
+        let Some(theirs) = self.outside_change.take() else { return };
+        self.synced_games = theirs.clone();
+        self.reload_journal(); // Before the reload is added to it
+
+        if let Err(error) = self.apply_change("Reload the game log", |game_log| {
+            game_log.game_file_contents = theirs;
+            Ok(())
+        }) {
+            println!("{}", error);
+        }
+    }
+
+    /// Save this window's log over the outside change
+    pub fn keep_my_log(&mut self) {
+        let Some(theirs) = self.outside_change.take() else { return };
+        self.synced_games = theirs;
+        self.reload_journal();
+
+        if let Err(error) = self.save_log(false) {
+            println!("Error Saving to File: {}", error);
+        }
+    }
+
+    /// Merge both logs, using the picked side for games both changed. Can be undone
+    pub fn merge_outside_change(&mut self) {
+        let Some(theirs) = self.outside_change.take() else { return };
+        let merged = merge_logs(&self.synced_games, &self.game_file_contents, &theirs, &self.merge_conflicts);
+        self.synced_games = theirs;
+        self.reload_journal();
+
+        if let Err(error) = self.apply_change("Merge outside changes", |game_log| {
+            game_log.game_file_contents = merged;
+            Ok(())
+        }) {
+            println!("{}", error);
+        }
+
+        // Keeping every one of my changes leaves the log as it was, so there was nothing for apply_change to save
+        if self.has_unsaved_changes() && self.save_log(false).is_err() {
+            println!("Error Saving to File");
+        }
+    }
+
+    /// Asks what to do about an outside change: reload it, or merge it with changes made here
+    pub fn outside_change_window(&mut self, ctx: &Context) {
+        if self.outside_change.is_none() {
+            return;
+        }
+
+        let unsaved = self.has_unsaved_changes();
+        let mut choice = None;
+
+        egui::Window::new("Game Log Changed")
+            .anchor(Align2::CENTER_CENTER, [0.0, 0.0])
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.set_width(450.0);
+
+                if !unsaved {
+                    ui.label(format!("The game log ({}) was changed outside the app. Reload it to see the changes?", self.settings.storage_backend));
+                    ui.add_space(5.0);
+
+                    ui.horizontal(|ui| {
+                        if ui.button("Reload").clicked() {
+                            choice = Some(OutsideChoice::Reload);
+                        }
+                        if ui.button("Keep Mine").on_hover_text("Save this window's log over the outside changes").clicked() {
+                            choice = Some(OutsideChoice::KeepMine);
+                        }
+                    });
+                    return;
+                }
+
+                ui.label(format!("The game log ({}) was changed outside the app while this window had changes that haven't been saved yet.", self.settings.storage_backend));
+                ui.label("Games only one side changed are merged as they are.");
+
+                if !self.merge_conflicts.is_empty() {
+                    ui.add_space(5.0);
+                    ui.label(RichText::new("Changed on both sides, pick which to keep:").strong());
+
+                    let rating_scale = self.settings.rating_scale;
+                    ScrollArea::vertical().max_height(250.0).show(ui, |ui| {
+                        for conflict in &mut self.merge_conflicts {
+                            ui.label(RichText::new(&conflict.name).strong());
+                            ui.radio_value(&mut conflict.keep_mine, true, format!("Mine: {}", version_summary(conflict.mine.as_ref(), rating_scale)));
+                            ui.radio_value(&mut conflict.keep_mine, false, format!("Theirs: {}", version_summary(conflict.theirs.as_ref(), rating_scale)));
+                            ui.add_space(5.0);
+                        }
+                    });
+                }
+
+                ui.add_space(5.0);
+                ui.horizontal(|ui| {
+                    if ui.button("Merge").clicked() {
+                        choice = Some(OutsideChoice::Merge);
+                    }
+                    if ui.button("Keep Mine").on_hover_text("Save this window's log over the outside changes").clicked() {
+                        choice = Some(OutsideChoice::KeepMine);
+                    }
+                    if ui.button("Use Theirs").on_hover_text("Throw away this window's changes (Can be undone)").clicked() {
+                        choice = Some(OutsideChoice::Reload);
+                    }
+                });
+            });
+
+        match choice {
+            Some(OutsideChoice::Reload) => self.reload_outside_change(),
+            Some(OutsideChoice::KeepMine) => self.keep_my_log(),
+            Some(OutsideChoice::Merge) => self.merge_outside_change(),
+            _ => (),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enums::Rating;
+
+    fn game(name: &str, points: u8) -> Game {
+        serde_json::from_str(&format!(r#"{{"name": "{}", "rating": {}, "times_played": 1, "last_playthrough": "", "notes": ""}}"#, name, points)).unwrap()
+    }
+
+    #[test]
+    fn merges_changes_from_both_sides() {
+        let base = vec![game("Celeste", 80), game("Hades", 80), game("Tunic", 80)];
+        let mine = vec![game("Celeste", 100), game("Outer Wilds", 100), game("Hades", 60), game("Tunic", 80)];
+        let theirs = vec![game("Celeste", 80), game("Hades", 40), game("Inside", 60)]; // Tunic deleted
+
+        let mut conflicts = merge_conflicts(&base, &mine, &theirs);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].name, "Hades");
+
+        conflicts[0].keep_mine = false;
+        let merged = merge_logs(&base, &mine, &theirs, &conflicts);
+        let names: Vec<&str> = merged.iter().map(|game| game.name.as_str()).collect();
+
+        assert_eq!(names, ["Celeste", "Outer Wilds", "Hades", "Inside"]);
+        assert_eq!(merged[0].rating, Rating::from_points(100).unwrap()); // Only changed here
+        assert_eq!(merged[2].rating, Rating::from_points(40).unwrap()); // Picked theirs
+    }
+}
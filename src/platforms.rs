@@ -0,0 +1,135 @@
+// Crates/Imports
+use eframe::egui::{Button, ComboBox, RichText, TextEdit, Ui, Vec2};
+
+use crate::{app_setup::GameLog, json_file_operations::Game, settings::save_settings};
+
+/// Platforms a new settings file starts with. Users can add and remove their own in the settings window
+pub fn default_platforms() -> Vec<String>
+{
+    ["PC", "Steam Deck", "Switch", "PlayStation 5", "Xbox Series X|S"].iter().map(|platform| platform.to_string()).collect()
+}
+
+/// Whether a game was played on a platform, either as its main platform or on any of its playthroughs
+pub fn played_on(game: &Game, platform: &str) -> bool
+{
+    game.platform.eq_ignore_ascii_case(platform)
+        || game.playthroughs.iter().any(|playthrough| playthrough.platform.eq_ignore_ascii_case(platform))
+}
+
+/// The user's platform list followed by any other platforms games in the log were played on (Like ones since removed
+/// from the list or that came in from an import). Compared ignoring case, the first spelling found is the one shown
+pub fn all_platforms(platforms: &[String], game_log: &[Game]) -> Vec<String>
+{
+    let mut all: Vec<String> = Vec::new();
+
+    let in_log = game_log.iter().flat_map(|game| {
+        std::iter::once(&game.platform).chain(game.playthroughs.iter().map(|playthrough| &playthrough.platform))
+    });
+    for platform in platforms.iter().chain(in_log) {
+        if !platform.is_empty() && !all.iter().any(|existing| existing.eq_ignore_ascii_case(platform)) {
+            all.push(platform.clone());
+        }
+    }
+
+    all
+}
+
+/// Drop down for choosing a platform from the user's platform list. An empty string means no platform
+pub fn platform_picker(ui: &mut Ui, id: &str, platform: &mut String, platforms: &[String])
+{
+    ComboBox::from_id_salt(id)
+        .selected_text(if platform.is_empty() { "None" } else { platform.as_str() })
+        .show_ui(ui, |ui| {
+            ui.selectable_value(platform, String::new(), "None");
+            for option in platforms {
+                ui.selectable_value(platform, option.clone(), option);
+            }
+        });
+}
+
+// Platform sidebar and settings GUI Code
+impl GameLog {
+    /// Platform list for the main screen's sidebar. Works just like the tag list
+    pub fn platform_filter_list(&mut self, ui: &mut Ui)
+    {
+        ui.label(RichText::new("Platforms").strong().size(18.0));
+        ui.add_space(5.0);
+
+        if ui.selectable_label(self.platform_filter.is_none(), "All Platforms").clicked() {
+            self.platform_filter = None;
+        }
+
+        for platform in all_platforms(&self.settings.platforms, &self.game_file_contents) {
+            let count = self.game_file_contents.iter().filter(|game| played_on(game, &platform)).count();
+            let selected = self.platform_filter.as_ref() == Some(&platform);
+
+            if ui.selectable_label(selected, format!("{} ({})", platform, count)).clicked() {
+                self.platform_filter = if selected { None } else { Some(platform) };
+            }
+        }
+    }
+
+    /// Add/remove entries in the user's platform list (Shown in the settings window)
+    pub fn platform_list_editor(&mut self, ui: &mut Ui)
+    {
+        let mut changed = false;
+
+        ui.label(RichText::new("Platforms").strong());
+
+        let mut removed = None;
+        ui.horizontal_wrapped(|ui| {
+            for (i, platform) in self.settings.platforms.iter().enumerate() {
+                if ui.add(Button::new(format!("{} ✖", platform)).small()).on_hover_text("Remove Platform").clicked() {
+                    removed = Some(i);
+                }
+            }
+        });
+
+        if let Some(i) = removed {
+            let platform = self.settings.platforms.remove(i);
+            if self.platform_filter.as_ref() == Some(&platform) {
+                self.platform_filter = None;
+            }
+            changed = true;
+        }
+
+        ui.horizontal(|ui| {
+            ui.add_sized(Vec2::new(150.0, 20.0), TextEdit::singleline(&mut self.new_platform_name)
+                .hint_text("New Platform")
+                .char_limit(30));
+
+            let new_platform = self.new_platform_name.trim().to_string();
+
+            if ui.button("Add").clicked() && !new_platform.is_empty()
+                && !self.settings.platforms.iter().any(|platform| platform.eq_ignore_ascii_case(&new_platform))
+            {
+                self.settings.platforms.push(new_platform);
+                self.new_platform_name.clear();
+                changed = true;
+            }
+        });
+
+        // Games keep their platform even if it's removed from the list, it just can't be picked for new games
+        if changed && save_settings(&self.settings).is_err() {
+            println!("Error Saving Settings");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn game(platform: &str, playthrough_platforms: &[&str]) -> Game {
+        let playthroughs: Vec<_> = playthrough_platforms.iter().map(|platform| serde_json::json!({"date": "2025-01-01", "platform": platform})).collect();
+        serde_json::from_value(serde_json::json!({"name": "Celeste", "rating": 80, "times_played": 1, "last_playthrough": "", "notes": "", "platform": platform, "playthroughs": playthroughs})).unwrap()
+    }
+
+    #[test]
+    fn platforms_in_the_log_are_listed_after_the_users_list() {
+        let platforms = vec![String::from("PC"), String::from("Switch")];
+        let log = vec![game("pc", &["Game Boy", ""]), game("", &["game boy", "Switch"]), game("Wii U", &[])];
+
+        assert_eq!(all_platforms(&platforms, &log), vec!["PC", "Switch", "Game Boy", "Wii U"]);
+    }
+}
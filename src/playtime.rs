@@ -0,0 +1,243 @@
+// Crates/Imports
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufReader, Write};
+use std::time::Duration;
+use chrono::Local;
+use eframe::egui::{Button, Color32, ComboBox, Context, RichText, Ui, Vec2};
+use serde::{Deserialize, Serialize};
+
+use crate::{app_setup::GameLog, json_file_operations::{search_for_game, Game}};
+
+const SESSION_FILE: &str = "GameLogSession.Json"; // Only exists while a timer is running
+
+/// Durations are saved to the log as a whole number of seconds rather than serde's default {secs, nanos} layout
+pub mod duration_seconds {
+    use std::time::Duration;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(duration.as_secs())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        Ok(Duration::from_secs(u64::deserialize(deserializer)?))
+    }
+}
+
+/// A running play session. Saved to disk as soon as it starts so closing the app mid-session doesn't lose it
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Session {
+    pub game_name: String,
+    pub started_at: i64, // Unix timestamp (seconds)
+}
+
+impl Session {
+    pub fn start(game_name: &str) -> Self {
+        Self { game_name: game_name.to_string(), started_at: Local::now().timestamp() }
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        Duration::from_secs((Local::now().timestamp() - self.started_at).max(0) as u64) // max(0) in case the clock has gone backwards
+    }
+}
+
+pub fn load_session() -> Option<Session>
+{
+    let reader = BufReader::new(File::open(SESSION_FILE).ok()?);
+    serde_json::from_reader(reader).ok()
+}
+
+pub fn save_session(session: &Session) -> Result<(), Box<dyn std::error::Error>>
+{
+    let mut file = OpenOptions::new()
+        .create(true)
+        .write(true)
+        .truncate(true)
+        .open(SESSION_FILE)?;
+
+    file.write_all(serde_json::to_string_pretty(session)?.as_bytes())?;
+
+    Ok(())
+}
+
+pub fn clear_session()
+{
+    let _ = fs::remove_file(SESSION_FILE); // Nothing to do if it's already gone
+}
+
+/// Show a duration as hours and minutes, e.g. "12h 05m"
+pub fn format_duration(duration: Duration) -> String
+{
+    let minutes = duration.as_secs() / 60;
+    format!("{}h {:02}m", minutes / 60, minutes % 60)
+}
+
+/// Same as `format_duration` but with seconds, for the live timer
+pub fn format_timer(duration: Duration) -> String
+{
+    let seconds = duration.as_secs();
+    format!("{}:{:02}:{:02}", seconds / 3600, (seconds / 60) % 60, seconds % 60)
+}
+
+/// Average play time per run of a game (None if it hasn't been played or timed)
+pub fn average_per_playthrough(game: &Game) -> Option<Duration>
+{
+    if game.times_played == 0 || game.play_time.is_zero() {
+        return None
+    }
+    Some(game.play_time / game.times_played)
+}
+
+/// "12h 05m (avg 2h 01m)" for the game list
+pub fn play_time_summary(game: &Game) -> String
+{
+    match average_per_playthrough(game) {
+        Some(average) => format!("{} (avg {} per playthrough)", format_duration(game.play_time), format_duration(average)),
+        None => format_duration(game.play_time),
+    }
+}
+
+// Session Timer GUI Code
+impl GameLog {
+    /// Top bar timer. Pick a game and press Start, then Stop adds the time onto the game
+    pub fn session_timer_bar(&mut self, ui: &mut Ui, ctx: &Context)
+    {
+        let button_size = Vec2::new(50.0, 20.0);
+
+        match self.session.clone() {
+            Some(session) => {
+                ui.label(RichText::new(format!("⏱ {} {}", session.game_name, format_timer(session.elapsed()))).strong());
+
+                if ui.add_sized(button_size, Button::new("Stop")).clicked() {
+                    self.stop_session();
+                }
+
+                ctx.request_repaint_after(Duration::from_secs(1)); // Keep the clock ticking even when the mouse isn't moving
+            },
+            None => {
+                ComboBox::from_id_salt("timer_game")
+                    .width(120.0)
+                    .selected_text(if self.timer_game_name.is_empty() { "Pick a game" } else { self.timer_game_name.as_str() })
+                    .show_ui(ui, |ui| {
+                        for game in &self.game_file_contents {
+                            ui.selectable_value(&mut self.timer_game_name, game.name.clone(), &game.name);
+                        }
+                    });
+
+                if ui.add_enabled(!self.timer_game_name.is_empty(), Button::new("▶ Start").min_size(button_size)).clicked() {
+                    let session = Session::start(&self.timer_game_name);
+
+                    self.timer_feedback_message = match save_session(&session) {
+                        Ok(_) => String::new(),
+                        Err(_) => String::from("Timer couldn't be saved, it won't survive a restart"),
+                    };
+                    self.session = Some(session);
+                }
+            }
+        }
+
+        // Games the process watcher has spotted running
+        let detected = self.process_watcher.running_games();
+        if !detected.is_empty() {
+            let names: Vec<&str> = detected.iter().map(|name| name.as_str()).collect();
+            ui.label(RichText::new(format!("🎮 {}", names.join(", "))).italics()).on_hover_text("Play time is being recorded automatically");
+        }
+
+        if !self.timer_feedback_message.is_empty() {
+            ui.label(RichText::new(&self.timer_feedback_message).color(Color32::RED));
+        }
+    }
+
+    /// Point the timer and any detected sessions at a game's new name, called whenever a change renames a game
+    pub fn follow_rename(&mut self, from: &str, to: &str)
+    {
+        if let Some(session) = &mut self.session
+            && session.game_name == from
+        {
+            session.game_name = to.to_string();
+            if save_session(session).is_err() {
+                self.timer_feedback_message = String::from("Timer couldn't be saved, it won't survive a restart");
+            }
+        }
+
+        if self.timer_game_name == from {
+            self.timer_game_name = to.to_string();
+        }
+
+        self.process_watcher.rename(from, to);
+    }
+
+    /// Finish the running session and add its time onto the game it was started for
+    pub fn stop_session(&mut self)
+    {
+        let Some(session) = self.session.take() else { return };
+
+        self.timer_feedback_message = match search_for_game(&self.game_file_contents, &session.game_name) {
+            Ok(i) => {
+                let description = format!("Play session of {}", session.game_name);
+
+                match self.apply_change(&description, |game_log| {
+                    game_log.game_file_contents[i].play_time += session.elapsed();
+                    Ok(())
+                }) {
+                    Ok(_) => String::new(),
+                    Err(_) => String::from("Error Saving Play Time"),
+                }
+            },
+            Err(_) => format!("{} is no longer in the log, session discarded", session.game_name), // Removed while the timer was running
+        };
+
+        clear_session();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::enums::Rating;
+
+    fn game(times_played: u32, play_time: u64) -> Game {
+        Game {
+            name: String::from("Celeste"),
+            rating: Rating::from_points(80).unwrap(),
+            times_played,
+            last_playthrough: String::from("2025-01-01"),
+            notes: String::new(),
+            tags: Vec::new(),
+            platform: String::new(),
+            storefront: String::new(),
+            playthroughs: Vec::new(),
+            play_time: Duration::from_secs(play_time),
+            executables: Vec::new(),
+            status: None,
+            release_year: None,
+            developer: String::new(),
+            genres: Vec::new(),
+            cover: String::new(),
+        }
+    }
+
+    #[test]
+    fn durations_are_shown_as_hours_and_minutes() {
+        assert_eq!(format_duration(Duration::ZERO), "0h 00m");
+        assert_eq!(format_duration(Duration::from_secs(12 * 3600 + 5 * 60 + 59)), "12h 05m"); // Seconds are dropped, not rounded
+        assert_eq!(format_timer(Duration::from_secs(3600 + 2 * 60 + 3)), "1:02:03");
+    }
+
+    #[test]
+    fn play_time_is_averaged_over_runs() {
+        assert_eq!(average_per_playthrough(&game(4, 8 * 3600)), Some(Duration::from_secs(2 * 3600)));
+        assert_eq!(average_per_playthrough(&game(0, 3600)), None);
+        assert_eq!(average_per_playthrough(&game(2, 0)), None);
+
+        assert_eq!(play_time_summary(&game(2, 3 * 3600)), "3h 00m (avg 1h 30m per playthrough)");
+        assert_eq!(play_time_summary(&game(0, 0)), "0h 00m");
+    }
+
+    #[test]
+    fn session_time_never_goes_negative() {
+        let session = Session { game_name: String::from("Celeste"), started_at: Local::now().timestamp() + 600 }; // Clock went backwards
+        assert_eq!(session.elapsed(), Duration::ZERO);
+    }
+}
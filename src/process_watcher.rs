@@ -0,0 +1,425 @@
+// Crates/Imports
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+use std::time::Duration;
+use chrono::Local;
+use eframe::egui::{Button, ComboBox, Context, TextEdit, Ui, Vec2};
+
+use crate::{app_setup::GameLog, clock::get_date, json_file_operations::{search_for_game, Game}};
+
+pub const PROC_ROOT: &str = "/proc";
+const SCAN_INTERVAL: Duration = Duration::from_secs(5);
+const COMM_LENGTH: usize = 15; // The kernel cuts /proc/<pid>/comm down to 15 characters
+
+/// A process found while scanning procfs
+#[derive(Clone, Debug, PartialEq)]
+pub struct RunningProcess {
+    pub pid: u32,
+    pub name: String, // From /proc/<pid>/comm
+    pub exe: Option<PathBuf>, // Where /proc/<pid>/exe points (Only readable for our own processes)
+    pub command: Option<String>, // First argument of /proc/<pid>/cmdline (Wine/Proton games show up here rather than in exe)
+}
+
+/// What changed between two scans
+#[derive(Clone, Debug, PartialEq)]
+pub enum WatchEvent {
+    Started(String), // Game name
+    Stopped(String, Duration),
+}
+
+/// Read every process out of a procfs style directory.
+///
+/// `proc_root` is normally `/proc` but can point at any folder laid out the same way (numbered folders holding
+/// `comm`, `cmdline` and an `exe` link), which is how the scanner is tested. Processes that vanish mid-scan are skipped
+pub fn scan_processes(proc_root: &Path) -> Vec<RunningProcess>
+{
+    let Ok(entries) = fs::read_dir(proc_root) else { return Vec::new() };
+
+    entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let pid = entry.file_name().to_str()?.parse::<u32>().ok()?; // Only the numbered folders are processes
+            let path = entry.path();
+
+            let name = fs::read_to_string(path.join("comm")).ok()?.trim_end().to_string();
+            let exe = fs::read_link(path.join("exe")).ok();
+            let command = fs::read(path.join("cmdline")).ok()
+                .and_then(|bytes| {
+                    let first = bytes.split(|byte| *byte == 0).next()?; // Arguments are separated by null bytes
+                    (!first.is_empty()).then(|| String::from_utf8_lossy(first).into_owned())
+                });
+
+            Some(RunningProcess { pid, name, exe, command })
+        })
+        .collect()
+}
+
+// Last part of a path, coping with Windows style paths from Wine command lines
+fn file_name(path: &str) -> &str
+{
+    path.rsplit(['/', '\\']).next().unwrap_or(path)
+}
+
+/// Whether a process is one of the executables linked to a game.
+///
+/// Entries with a slash in them are treated as full paths, anything else is matched against the executable's file name
+pub fn process_matches(process: &RunningProcess, executable: &str) -> bool
+{
+    let executable = executable.trim();
+    if executable.is_empty() {
+        return false
+    }
+
+    let mut candidates: Vec<&str> = Vec::new();
+    if let Some(exe) = process.exe.as_ref().and_then(|exe| exe.to_str()) {
+        candidates.push(exe);
+    }
+    if let Some(command) = &process.command {
+        candidates.push(command);
+    }
+
+    if executable.contains(['/', '\\']) {
+        candidates.contains(&executable)
+    }
+    else {
+        candidates.iter().any(|candidate| file_name(candidate) == executable)
+            || (process.name == executable)
+            || (executable.len() > COMM_LENGTH && executable.starts_with(&process.name) && process.name.len() == COMM_LENGTH) // comm was cut short
+    }
+}
+
+/// Keeps track of which games are running between scans and turns scans into start/stop events
+#[derive(Default)]
+pub struct ProcessWatcher {
+    running: HashMap<String, i64>, // Game name -> when it was first seen (Unix seconds)
+}
+
+impl ProcessWatcher {
+    /// Compare the latest scan against the last one. `now` is passed in (Unix seconds) so it can be tested without waiting
+    pub fn update(&mut self, processes: &[RunningProcess], game_log: &[Game], now: i64) -> Vec<WatchEvent>
+    {
+        let mut events = Vec::new();
+
+        let running_now: Vec<&Game> = game_log.iter()
+            .filter(|game| game.executables.iter().any(|executable| processes.iter().any(|process| process_matches(process, executable))))
+            .collect();
+
+        for game in &running_now {
+            if !self.running.contains_key(&game.name) {
+                self.running.insert(game.name.clone(), now);
+                events.push(WatchEvent::Started(game.name.clone()));
+            }
+        }
+
+        let stopped: Vec<String> = self.running.keys()
+            .filter(|name| !running_now.iter().any(|game| &game.name == *name))
+            .cloned()
+            .collect();
+
+        for name in stopped {
+            if let Some(started_at) = self.running.remove(&name) {
+                events.push(WatchEvent::Stopped(name, Duration::from_secs((now - started_at).max(0) as u64)));
+            }
+        }
+
+        events
+    }
+
+    /// End every running session, used when the app closes so the time isn't lost
+    pub fn stop_all(&mut self, now: i64) -> Vec<WatchEvent>
+    {
+        self.running.drain()
+            .map(|(name, started_at)| WatchEvent::Stopped(name, Duration::from_secs((now - started_at).max(0) as u64)))
+            .collect()
+    }
+
+    /// Carry a running game over to its new name so the time isn't lost when it's renamed mid-session
+    pub fn rename(&mut self, from: &str, to: &str)
+    {
+        if let Some(started_at) = self.running.remove(from) {
+            self.running.insert(to.to_string(), started_at);
+        }
+    }
+
+    pub fn running_games(&self) -> Vec<&String>
+    {
+        self.running.keys().collect()
+    }
+}
+
+/// Add a finished session's time onto a game and the playthrough it belongs to.
+///
+/// Time goes onto today's playthrough if there is one, otherwise onto the latest one. Games with no playthroughs only get
+/// the time on the game (A session isn't a new run, so it doesn't make a playthrough that times played wouldn't count)
+pub fn record_session(game: &mut Game, elapsed: Duration)
+{
+    game.play_time += elapsed;
+
+    let today = get_date().to_string();
+
+    let index = game.playthroughs.iter().rposition(|playthrough| playthrough.date == today).or(game.playthroughs.len().checked_sub(1));
+    if let Some(index) = index {
+        game.playthroughs[index].play_time += elapsed;
+    }
+}
+
+/// Scan procfs in the background every few seconds, sending each scan back to the app
+pub fn spawn_scanner(proc_root: PathBuf) -> Receiver<Vec<RunningProcess>>
+{
+    let (sender, receiver) = mpsc::channel();
+
+    thread::spawn(move || {
+        // Stops once the app drops the receiver
+        while sender.send(scan_processes(&proc_root)).is_ok() {
+            thread::sleep(SCAN_INTERVAL);
+        }
+    });
+
+    receiver
+}
+
+/// List editor for the executables linked to a game, with a drop down of what's running right now to make finding the name easier
+pub fn executable_editor(ui: &mut Ui, executables: &mut Vec<String>, input: &mut String, running: &[RunningProcess])
+{
+    ui.vertical(|ui| {
+        if !executables.is_empty() {
+            let mut removed = None;
+            ui.horizontal_wrapped(|ui| {
+                for (i, executable) in executables.iter().enumerate() {
+                    if ui.add(Button::new(format!("{} ✖", executable)).small()).on_hover_text("Unlink Executable").clicked() {
+                        removed = Some(i);
+                    }
+                }
+            });
+            if let Some(i) = removed {
+                executables.remove(i);
+            }
+        }
+
+        ui.horizontal(|ui| {
+            ui.add_sized(Vec2::new(120.0, 20.0), TextEdit::singleline(input)
+                .hint_text("Name or full path"));
+
+            if !running.is_empty() {
+                let mut names: Vec<&String> = running.iter().map(|process| &process.name).collect();
+                names.sort();
+                names.dedup();
+
+                ComboBox::from_id_salt("running_processes")
+                    .width(20.0)
+                    .selected_text("")
+                    .show_ui(ui, |ui| {
+                        for name in names {
+                            if ui.selectable_label(false, name).clicked() {
+                                *input = name.clone();
+                            }
+                        }
+                    });
+            }
+
+            if ui.add(Button::new("+").small()).clicked() {
+                let executable = input.trim().to_string();
+                if !executable.is_empty() && !executables.contains(&executable) {
+                    executables.push(executable);
+                }
+                input.clear();
+            }
+        });
+    });
+}
+
+// Process Watcher GUI Glue
+impl GameLog {
+    /// Turn the background scanner on or off (Linux only, other systems don't have procfs)
+    pub fn set_auto_detect(&mut self, enabled: bool)
+    {
+        if enabled && cfg!(target_os = "linux") {
+            if self.process_scans.is_none() {
+                self.process_scans = Some(spawn_scanner(PathBuf::from(PROC_ROOT)));
+            }
+        }
+        else {
+            self.process_scans = None; // Dropping the receiver stops the thread
+            self.running_processes.clear();
+            let events = self.process_watcher.stop_all(Local::now().timestamp());
+            self.apply_watch_events(events);
+        }
+    }
+
+    /// Check for a new scan and start/stop sessions to match. Called every frame
+    pub fn poll_process_watcher(&mut self, ctx: &Context)
+    {
+        let Some(scans) = &self.process_scans else { return };
+
+        // Only the newest scan matters if a few have piled up
+        if let Some(processes) = scans.try_iter().last() {
+            let events = self.process_watcher.update(&processes, &self.game_file_contents, Local::now().timestamp());
+            self.running_processes = processes;
+            self.apply_watch_events(events);
+        }
+
+        ctx.request_repaint_after(SCAN_INTERVAL); // Keep polling even when nobody is touching the app
+    }
+
+    /// Add the time from any finished sessions onto their games and save
+    pub fn apply_watch_events(&mut self, events: Vec<WatchEvent>)
+    {
+        // Games starting don't change anything, so don't save (Or add an undo step) for them
+        if !events.iter().any(|event| matches!(event, WatchEvent::Stopped(..))) {
+            return
+        }
+
+        let saved = self.apply_change("Detected play time", |game_log| {
+            for event in events {
+                if let WatchEvent::Stopped(name, elapsed) = event
+                    && let Ok(i) = search_for_game(&game_log.game_file_contents, &name)
+                {
+                    record_session(&mut game_log.game_file_contents[i], elapsed);
+                }
+            }
+            Ok(())
+        });
+
+        if saved.is_err() {
+            println!("Error Saving Detected Play Time");
+        }
+    }
+}
+
+#[cfg(all(test, unix))] // Fake procfs uses unix symlinks
+mod tests {
+    use super::*;
+    use std::os::unix::fs::symlink;
+
+    use crate::{enums::Rating, json_file_operations::Playthrough};
+
+    // Build a throwaway procfs lookalike in the temp folder
+    fn fake_proc(test_name: &str, processes: &[(u32, &str, Option<&str>, &str)]) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("gamelog_fake_proc_{}_{}", test_name, std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("self")).unwrap(); // Non-numbered entries should be ignored
+
+        for (pid, comm, exe, cmdline) in processes {
+            let dir = root.join(pid.to_string());
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(dir.join("comm"), format!("{}\n", comm)).unwrap();
+            fs::write(dir.join("cmdline"), cmdline.replace('|', "\0")).unwrap(); // '|' stands in for the null bytes between arguments
+            if let Some(exe) = exe {
+                symlink(exe, dir.join("exe")).unwrap(); // Dangling links are fine, only the target is read
+            }
+        }
+
+        root
+    }
+
+    fn game(name: &str, executables: &[&str]) -> Game {
+        Game {
+            name: name.to_string(),
+            rating: Rating::from_points(80).unwrap(),
+            times_played: 1,
+            last_playthrough: String::from("2025-01-01"),
+            notes: String::new(),
+            tags: Vec::new(),
+            platform: String::new(),
+            storefront: String::new(),
+            playthroughs: Vec::new(),
+            play_time: Duration::ZERO,
+            executables: executables.iter().map(|executable| executable.to_string()).collect(),
+            status: None,
+            release_year: None,
+            developer: String::new(),
+            genres: Vec::new(),
+            cover: String::new(),
+        }
+    }
+
+    #[test]
+    fn scans_numbered_process_folders() {
+        let root = fake_proc("scan", &[
+            (100, "bash", Some("/usr/bin/bash"), "bash"),
+            (200, "Celeste", Some("/games/Celeste/Celeste"), "/games/Celeste/Celeste|--fullscreen"),
+        ]);
+
+        let mut processes = scan_processes(&root);
+        processes.sort_by_key(|process| process.pid);
+
+        assert_eq!(processes.len(), 2);
+        assert_eq!(processes[1].name, "Celeste");
+        assert_eq!(processes[1].exe, Some(PathBuf::from("/games/Celeste/Celeste")));
+        assert_eq!(processes[1].command.as_deref(), Some("/games/Celeste/Celeste"));
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn matches_names_paths_and_wine_commands() {
+        let root = fake_proc("match", &[
+            (300, "HollowKnight.ex", None, "Z:\\games\\Hollow Knight\\HollowKnight.exe"),
+            (301, "factorio", Some("/opt/factorio/bin/x64/factorio"), "factorio"),
+        ]);
+        let processes = scan_processes(&root);
+        let find = |pid| processes.iter().find(|process| process.pid == pid).unwrap();
+
+        assert!(process_matches(find(300), "HollowKnight.exe"));
+        assert!(process_matches(find(301), "factorio"));
+        assert!(process_matches(find(301), "/opt/factorio/bin/x64/factorio"));
+        assert!(!process_matches(find(301), "/usr/bin/factorio"));
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn watcher_reports_start_and_stop_with_elapsed_time() {
+        let game_log = vec![game("Celeste", &["Celeste"]), game("Minecraft", &["java"])];
+        let root = fake_proc("watch", &[(400, "Celeste", Some("/games/Celeste/Celeste"), "Celeste")]);
+        let mut watcher = ProcessWatcher::default();
+
+        assert_eq!(watcher.update(&scan_processes(&root), &game_log, 1000), vec![WatchEvent::Started(String::from("Celeste"))]);
+        assert!(watcher.update(&scan_processes(&root), &game_log, 1005).is_empty()); // Still running, nothing new
+
+        fs::remove_dir_all(root.join("400")).unwrap(); // Game closed
+        assert_eq!(watcher.update(&scan_processes(&root), &game_log, 1600), vec![WatchEvent::Stopped(String::from("Celeste"), Duration::from_secs(600))]);
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn renaming_a_running_game_keeps_its_session() {
+        let mut game_log = vec![game("Celeste", &["Celeste"])];
+        let root = fake_proc("rename", &[(500, "Celeste", Some("/games/Celeste/Celeste"), "Celeste")]);
+        let mut watcher = ProcessWatcher::default();
+
+        watcher.update(&scan_processes(&root), &game_log, 1000);
+        game_log[0].name = String::from("Celeste (2018)");
+        watcher.rename("Celeste", "Celeste (2018)");
+        assert!(watcher.update(&scan_processes(&root), &game_log, 1005).is_empty()); // Same session, not a new one
+
+        fs::remove_dir_all(root.join("500")).unwrap();
+        assert_eq!(watcher.update(&scan_processes(&root), &game_log, 1300), vec![WatchEvent::Stopped(String::from("Celeste (2018)"), Duration::from_secs(300))]);
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn sessions_are_recorded_on_the_latest_playthrough() {
+        let mut celeste = game("Celeste", &["Celeste"]);
+
+        record_session(&mut celeste, Duration::from_secs(60));
+        assert_eq!(celeste.play_time, Duration::from_secs(60));
+        assert!(celeste.playthroughs.is_empty()); // Only on the game, runs are still 1 with no playthroughs
+
+        let playthrough = |date: &str| Playthrough { date: date.to_string(), platform: String::new(), storefront: String::new(), play_time: Duration::ZERO, note: String::new() };
+        celeste.playthroughs = vec![playthrough("2025-01-01"), playthrough("2025-07-14")];
+
+        record_session(&mut celeste, Duration::from_secs(90));
+        record_session(&mut celeste, Duration::from_secs(30));
+
+        assert_eq!(celeste.play_time, Duration::from_secs(180));
+        assert_eq!(celeste.playthroughs.len(), 2);
+        assert_eq!(celeste.playthroughs[0].play_time, Duration::ZERO);
+        assert_eq!(celeste.playthroughs[1].play_time, Duration::from_secs(120));
+    }
+}
@@ -1,6 +1,6 @@
-use eframe::egui::{Align, Button, Checkbox, Color32, Label, RichText, TextEdit, Vec2};
+use eframe::egui::{self, Align, Button, Checkbox, Color32, Label, RichText, TextEdit, Vec2};
 
-use crate::{app_setup::GameLog, egui::Ui, json_file_operations::{save_to_file, search_for_game}};
+use crate::{app_setup::GameLog, egui::Ui, json_file_operations::search_for_game};
 
 // 'Removing' Window GUI Code
 impl GameLog {
@@ -75,24 +75,60 @@ impl GameLog {
 
                         self.removing_feedback_message = match search_for_game(&self.game_file_contents, &self.remove_game_name) {
                             Ok(i) => {
-                                self.game_file_contents.remove(i);
-                                // Save Edits
-                                match save_to_file(&mut self.game_file_contents) {
+                                let description = format!("Remove {}", self.game_file_contents[i].name);
+
+                                // Save Edits (Can still be undone)
+                                match self.apply_change(&description, |game_log| {
+                                    game_log.game_file_contents.remove(i);
+                                    Ok(())
+                                }) {
                                     Ok(_) => { 
                                         self.error_confirmation = false;
                                         self.remove_game_name.clear(); // Clear Input Box
-                                        format!("Game Removed Successfully")
+                                        String::from("Game Removed Successfully")
                                     },
-                                    Err(_) => format!("Error Removing Game"),
+                                    Err(_) => String::from("Error Removing Game"),
                                 }
                             },
-                            Err(_) => format!("Invalid Game Entered") // If user enters an invalid Game, Let them Know
+                            Err(_) => String::from("Invalid Game Entered") // If user enters an invalid Game, Let them Know
                         };
                     }
             });
 
             ui.add_space(5.0);
 
+            // Games moved to the trash from the right click menu
+            if !self.trash.is_empty() {
+                ui.separator();
+                ui.label(RichText::new(format!("Trash ({})", self.trash.len())).size(20.0));
+                ui.add_space(5.0);
+
+                let mut result = None;
+
+                egui::ScrollArea::vertical().max_height(150.0).show(ui, |ui| {
+                    for i in 0..self.trash.len() {
+                        ui.horizontal(|ui| {
+                            ui.label(&self.trash[i].name);
+
+                            if ui.small_button("Restore").clicked() {
+                                result = Some(self.restore_from_trash(i));
+                            }
+                            else if ui.small_button("Delete Forever").clicked() {
+                                result = Some(self.delete_from_trash(i));
+                            }
+                        });
+
+                        if result.is_some() {
+                            break; // Indexes have moved
+                        }
+                    }
+                });
+
+                if let Some(result) = result {
+                    self.error_confirmation = result.is_err();
+                    self.removing_feedback_message = result.err().unwrap_or_default();
+                }
+            }
         });
     }
 }
\ No newline at end of file
@@ -0,0 +1,150 @@
+// Crates/Imports
+use std::fs::{File, OpenOptions};
+use std::io::{BufReader, Write};
+use eframe::egui::{ComboBox, Label, RichText, Ui};
+use serde::{Deserialize, Serialize};
+
+use crate::{app_setup::GameLog, enums::{RatingScale, ViewMode, WindowOpened}, platforms::default_platforms, storage::StorageBackend};
+
+const SETTINGS_FILE: &str = "GameLogSettings.Json"; // Lives next to GameLog.Json
+
+/// User preferences that are kept between runs of the app
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)] // Anything missing from an older settings file just uses its default
+pub struct Settings {
+    pub rating_scale: RatingScale,
+    pub platforms: Vec<String>, // User managed list shown in the platform drop downs
+    pub auto_detect_playtime: bool, // Watch running processes for linked games (Linux only)
+    pub metadata_url: String, // IGDB style API that game details are looked up from
+    pub metadata_client_id: String, // Lookups are off while this is empty
+    pub metadata_token: String,
+    pub view_mode: ViewMode, // List, table or grid in the main window
+    pub storage_backend: StorageBackend, // JSON file or SQLite database
+    pub backup_on_startup: bool,
+    pub backup_daily: bool,
+    pub backup_every_saves: u32, // 0 for never
+    pub backups_kept: usize, // Newest ones kept (0 for all)
+    pub backup_max_age_days: u32, // Older ones are deleted (0 for never)
+    pub export_spoilers: bool, // Otherwise ||spoilers|| in notes are redacted from exports
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            rating_scale: RatingScale::default(),
+            platforms: default_platforms(),
+            auto_detect_playtime: false,
+            metadata_url: String::from("https://api.igdb.com/v4"),
+            metadata_client_id: String::new(),
+            metadata_token: String::new(),
+            view_mode: ViewMode::default(),
+            storage_backend: StorageBackend::default(),
+            backup_on_startup: true,
+            backup_daily: true,
+            backup_every_saves: 25,
+            backups_kept: 30,
+            backup_max_age_days: 0,
+            export_spoilers: false,
+        }
+    }
+}
+
+// Read the settings file, falling back to the defaults if it doesn't exist yet (or can't be read)
+pub fn load_settings() -> Settings
+{
+    let reader = match File::open(SETTINGS_FILE) {
+        Ok(file) => BufReader::new(file),
+        Err(_) => return Settings::default()
+    };
+
+    serde_json::from_reader(reader).unwrap_or_default()
+}
+
+pub fn save_settings(settings: &Settings) -> Result<(), Box<dyn std::error::Error>>
+{
+    let new_json = serde_json::to_string_pretty(settings)?;
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .write(true)
+        .truncate(true)
+        .open(SETTINGS_FILE)?;
+
+    file.write_all(new_json.as_bytes())?;
+
+    Ok(())
+}
+
+// 'Settings' Window GUI Code
+impl GameLog {
+    pub fn settings_gui (&mut self, ui: &mut Ui)
+    {
+        ui.add_space(5.0);
+
+        ui.horizontal(|ui| {
+            ui.add(Label::new(RichText::new("Rating Scale").strong()));
+
+            let previous_scale = self.settings.rating_scale;
+
+            ComboBox::from_id_salt("rating_scale")
+                .selected_text(self.settings.rating_scale.to_string())
+                .show_ui(ui, |ui| {
+                    for scale in RatingScale::ALL {
+                        ui.selectable_value(&mut self.settings.rating_scale, scale, scale.to_string());
+                    }
+                });
+
+            // Ratings are stored the same way whatever the scale, so changing it only needs the settings file saving
+            if previous_scale != self.settings.rating_scale {
+                // Half-typed ratings were written for the old scale, so they're cleared rather than misread
+                self.add_game_rating.clear();
+                self.edit_game_rating.clear();
+
+                if save_settings(&self.settings).is_err() {
+                    println!("Error Saving Settings");
+                }
+            }
+        });
+
+        ui.add_space(10.0);
+
+        // Only Linux has /proc to scan
+        ui.add_enabled_ui(cfg!(target_os = "linux"), |ui| {
+            let mut auto_detect = self.settings.auto_detect_playtime;
+
+            if ui.checkbox(&mut auto_detect, "Detect play time from running games").on_hover_text("Link executables to games in the edit window").changed() {
+                self.settings.auto_detect_playtime = auto_detect;
+                self.set_auto_detect(auto_detect);
+
+                if save_settings(&self.settings).is_err() {
+                    println!("Error Saving Settings");
+                }
+            }
+        });
+
+        ui.add_space(10.0);
+
+        self.platform_list_editor(ui);
+
+        ui.add_space(10.0);
+
+        self.metadata_settings(ui);
+
+        ui.add_space(10.0);
+
+        self.storage_settings(ui);
+
+        ui.add_space(10.0);
+
+        if ui.checkbox(&mut self.settings.export_spoilers, "Include spoilers in exports").on_hover_text("Otherwise spoilers in notes are swapped for [Spoiler]").changed()
+            && save_settings(&self.settings).is_err() {
+            println!("Error Saving Settings");
+        }
+
+        ui.add_space(10.0);
+
+        if ui.button("Backups").clicked() {
+            self.current_window_opened = WindowOpened::Backups;
+        }
+    }
+}
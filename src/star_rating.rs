@@ -0,0 +1,81 @@
+// Crates/Imports
+use eframe::egui::{Align2, Button, Color32, DragValue, FontId, Sense, Ui, Vec2};
+
+use crate::enums::RatingScale;
+
+const STAR_SIZE: f32 = 20.0;
+const STAR_COLOUR: Color32 = Color32::from_rgb(255, 190, 0); // Gold
+
+/// Clickable stars for entering a rating on whichever scale is chosen.
+///
+/// The rating is kept as text in the scale's own format (the same thing `RatingScale::parse` reads), so an empty
+/// string means "no rating picked yet". The 100 point scale uses a number box next to the stars as 100 stars would be silly.
+pub fn star_rating_input(ui: &mut Ui, rating_text: &mut String, scale: RatingScale)
+{
+    let current_steps = scale.parse(rating_text).map(|rating| scale.steps(rating)).unwrap_or(0);
+
+    ui.horizontal(|ui| {
+        ui.spacing_mut().item_spacing.x = 2.0;
+
+        match scale {
+            RatingScale::HundredPoints => {
+                draw_stars(ui, 5, current_steps as f32 / 20.0); // Preview only
+
+                let mut points = current_steps;
+                if ui.add(DragValue::new(&mut points).range(0..=100).suffix("/100")).changed() {
+                    *rating_text = if points == 0 { String::new() } else { points.to_string() };
+                }
+            },
+            _ => {
+                let half_stars = scale == RatingScale::HalfStars;
+                let steps_per_star = if half_stars { 2 } else { 1 };
+                let star_count = scale.max_steps() / steps_per_star;
+
+                for star in 0..star_count {
+                    let filled_steps = current_steps.saturating_sub(star * steps_per_star).min(steps_per_star);
+                    let response = draw_star(ui, filled_steps as f32 / steps_per_star as f32);
+
+                    if response.clicked() {
+                        // Clicking the left half of a star gives half a star on the half star scale
+                        let left_half = response.interact_pointer_pos().is_some_and(|pos| pos.x < response.rect.center().x);
+                        let steps = if half_stars && left_half { star * 2 + 1 } else { (star + 1) * steps_per_star };
+
+                        if let Some(rating) = scale.from_steps(steps) {
+                            *rating_text = scale.format_value(rating);
+                        }
+                    }
+                }
+            }
+        }
+
+        // Let users take their choice back
+        if !rating_text.is_empty() && ui.add(Button::new("✖").small()).on_hover_text("Clear Rating").clicked() {
+            rating_text.clear();
+        }
+    });
+}
+
+// Non-clickable row of stars, `filled` is how many stars to colour in (fractions are partly coloured)
+fn draw_stars(ui: &mut Ui, star_count: u8, filled: f32)
+{
+    for star in 0..star_count {
+        draw_star(ui, (filled - star as f32).clamp(0.0, 1.0));
+    }
+}
+
+// Paints one star, filling in `fill` (0.0 to 1.0) of it from the left
+fn draw_star(ui: &mut Ui, fill: f32) -> eframe::egui::Response
+{
+    let (rect, response) = ui.allocate_exact_size(Vec2::splat(STAR_SIZE), Sense::click());
+    let font = FontId::proportional(STAR_SIZE);
+
+    ui.painter().text(rect.center(), Align2::CENTER_CENTER, "☆", font.clone(), ui.visuals().text_color());
+
+    if fill > 0.0 {
+        let mut filled_area = rect;
+        filled_area.set_right(rect.left() + rect.width() * fill);
+        ui.painter().with_clip_rect(filled_area).text(rect.center(), Align2::CENTER_CENTER, "★", font, STAR_COLOUR);
+    }
+
+    response
+}
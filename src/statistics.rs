@@ -0,0 +1,120 @@
+// Crates/Imports
+use std::time::Duration;
+use eframe::egui::{Grid, RichText, Ui};
+
+use crate::{app_setup::GameLog, json_file_operations::Game, playtime::format_duration};
+
+const UNKNOWN_PLATFORM: &str = "Unknown";
+
+/// How many games and playthroughs belong to one platform
+pub struct PlatformStats {
+    pub platform: String,
+    pub games: usize,
+    pub playthroughs: u64,
+}
+
+// Runs counted in times played that have no playthrough recorded for them (Logged before playthroughs were added)
+fn unrecorded_runs(game: &Game) -> u64
+{
+    (game.times_played as u64).saturating_sub(game.playthroughs.len() as u64)
+}
+
+/// Break the log down by platform.
+///
+/// Games are counted under their main platform. Playthroughs are counted under the platform they were played on,
+/// falling back to the game's platform for runs that didn't record one (Or weren't recorded at all, only counted in times played)
+pub fn platform_breakdown(game_log: &[Game]) -> Vec<PlatformStats>
+{
+    let mut stats: Vec<PlatformStats> = Vec::new();
+
+    fn entry<'a>(stats: &'a mut Vec<PlatformStats>, platform: &str) -> &'a mut PlatformStats {
+        let platform = if platform.is_empty() { UNKNOWN_PLATFORM } else { platform };
+
+        match stats.iter().position(|stat| stat.platform.eq_ignore_ascii_case(platform)) {
+            Some(i) => &mut stats[i],
+            None => {
+                stats.push(PlatformStats { platform: platform.to_string(), games: 0, playthroughs: 0 });
+                stats.last_mut().unwrap()
+            }
+        }
+    }
+
+    for game in game_log {
+        entry(&mut stats, &game.platform).games += 1;
+
+        for playthrough in &game.playthroughs {
+            let platform = if playthrough.platform.is_empty() { &game.platform } else { &playthrough.platform };
+            entry(&mut stats, platform).playthroughs += 1;
+        }
+        entry(&mut stats, &game.platform).playthroughs += unrecorded_runs(game);
+    }
+
+    stats.sort_by(|a, b| b.games.cmp(&a.games).then_with(|| a.platform.cmp(&b.platform))); // Most played platforms first
+    stats
+}
+
+// 'Statistics' Window GUI Code
+impl GameLog {
+    pub fn statistics_gui (&mut self, ui: &mut Ui)
+    {
+        let recorded_playthroughs: usize = self.game_file_contents.iter().map(|game| game.playthroughs.len()).sum();
+        let total_playthroughs: u64 = self.game_file_contents.iter().map(|game| game.playthroughs.len() as u64 + unrecorded_runs(game)).sum();
+
+        ui.add_space(5.0);
+
+        ui.label(RichText::new(format!("Games: {}", self.game_file_contents.len())).strong());
+        ui.label(RichText::new(format!("Playthroughs: {}", total_playthroughs)).strong())
+            .on_hover_text(format!("{} with a date and platform recorded, the rest are only counted in times played", recorded_playthroughs));
+
+        // Added up as whole seconds in u64s, so even huge (or hand edited) logs can't overflow
+        let total_play_time = self.game_file_contents.iter().map(|game| game.play_time.as_secs()).fold(0, u64::saturating_add);
+        // Only runs of games with time on them count, untimed games would drag the average down (Same as the per game average)
+        let total_times_played: u64 = self.game_file_contents.iter()
+            .filter(|game| !game.play_time.is_zero())
+            .map(|game| game.times_played as u64)
+            .sum();
+
+        ui.label(RichText::new(format!("Total Play Time: {}", format_duration(Duration::from_secs(total_play_time)))).strong());
+        if let Some(average) = total_play_time.checked_div(total_times_played) {
+            ui.label(RichText::new(format!("Average Per Playthrough: {}", format_duration(Duration::from_secs(average)))).strong());
+        }
+
+        ui.add_space(10.0);
+        ui.label(RichText::new("By Platform").size(18.0));
+        ui.add_space(5.0);
+
+        Grid::new("platform_statistics").striped(true).num_columns(3).show(ui, |ui| {
+            ui.label(RichText::new("Platform").strong());
+            ui.label(RichText::new("Games").strong());
+            ui.label(RichText::new("Playthroughs").strong());
+            ui.end_row();
+
+            for stat in platform_breakdown(&self.game_file_contents) {
+                ui.label(stat.platform);
+                ui.label(stat.games.to_string());
+                ui.label(stat.playthroughs.to_string());
+                ui.end_row();
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn playthroughs_are_counted_where_they_were_played() {
+        let games: Vec<Game> = serde_json::from_str(r#"[
+            {"name": "Hades", "rating": 100, "times_played": 3, "last_playthrough": "", "notes": "", "platform": "PC",
+             "playthroughs": [{"date": "2025-01-01", "platform": "Switch"}, {"date": "2025-02-01"}]},
+            {"name": "Celeste", "rating": 80, "times_played": 1, "last_playthrough": "", "notes": "", "platform": "switch"},
+            {"name": "Tunic", "rating": 80, "times_played": 0, "last_playthrough": "", "notes": ""}
+        ]"#).unwrap();
+
+        let stats: Vec<(String, usize, u64)> = platform_breakdown(&games).into_iter().map(|stat| (stat.platform, stat.games, stat.playthroughs)).collect();
+
+        // Hades' run without a platform and the one only in times played both count for PC
+        assert_eq!(stats, [(String::from("PC"), 1, 2), (String::from("Switch"), 1, 2), (String::from(UNKNOWN_PLATFORM), 1, 0)]);
+    }
+}
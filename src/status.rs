@@ -0,0 +1,17 @@
+// Crates/Imports
+use eframe::egui::{ComboBox, Ui};
+
+use crate::enums::GameStatus;
+
+/// Drop down for choosing a game's status. None means the game has no status
+pub fn status_picker(ui: &mut Ui, id: &str, status: &mut Option<GameStatus>)
+{
+    ComboBox::from_id_salt(id)
+        .selected_text(status.map(|status| status.to_string()).unwrap_or_else(|| String::from("None")))
+        .show_ui(ui, |ui| {
+            ui.selectable_value(status, None, "None");
+            for option in GameStatus::ALL {
+                ui.selectable_value(status, Some(option), option.to_string());
+            }
+        });
+}
@@ -0,0 +1,157 @@
+// Crates/Imports
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufReader, ErrorKind, Write};
+use std::path::PathBuf;
+use std::time::SystemTime;
+use anyhow::anyhow;
+
+use crate::{json_file_operations::{search_for_game, Game, SearchQuery}, storage::{Storage, StorageChange}};
+
+/// The log as one pretty printed JSON file. Any change rewrites the whole file
+pub struct JsonStorage {
+    path: PathBuf,
+    seen: Option<(SystemTime, u64)>, // Modified time and size of the file when it was last loaded or saved (None if there wasn't one)
+}
+
+impl JsonStorage {
+    pub fn new(path: impl Into<PathBuf>) -> JsonStorage
+    {
+        let mut storage = JsonStorage { path: path.into(), seen: None };
+        storage.seen = storage.stamp();
+        storage
+    }
+
+    // Enough to tell the file has been written to, without reading it
+    fn stamp(&self) -> Option<(SystemTime, u64)>
+    {
+        let metadata = fs::metadata(&self.path).ok()?;
+        Some((metadata.modified().ok()?, metadata.len()))
+    }
+
+    fn write(&mut self, games: &[Game]) -> Result<(), anyhow::Error>
+    {
+        let new_json = serde_json::to_string_pretty(games)?;
+
+        // Write back to file (overwrite)
+        let mut file = OpenOptions::new()
+            .create(true) // If file doesn't exist, Create it
+            .write(true)
+            .truncate(true)
+            .open(&self.path)?;
+
+        file.write_all(new_json.as_bytes())?;
+        drop(file);
+
+        self.seen = self.stamp();
+        Ok(())
+    }
+
+    // No file yet is just an empty log, but a file that can't be read is an error so it doesn't get saved over
+    fn read(&self) -> Result<Vec<Game>, anyhow::Error>
+    {
+        let file = match File::open(&self.path) {
+            Ok(file) => file,
+            Err(error) if error.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(error) => return Err(error.into()),
+        };
+
+        Ok(serde_json::from_reader(BufReader::new(file))?)
+    }
+}
+
+// Make one change to a loaded log
+fn apply_to(games: &mut Vec<Game>, change: &StorageChange) -> Result<(), anyhow::Error>
+{
+    match change {
+        StorageChange::Insert(position, game) => games.insert((*position).min(games.len()), game.clone()),
+        StorageChange::Update(name, game) => {
+            let index = search_for_game(games, name).map_err(|_| anyhow!("{} isn't in the game log", name))?;
+            games[index] = game.clone();
+        },
+        StorageChange::Delete(name) => {
+            let index = search_for_game(games, name).map_err(|_| anyhow!("{} isn't in the game log", name))?;
+            games.remove(index);
+        },
+    }
+    Ok(())
+}
+
+impl Storage for JsonStorage {
+    fn load(&mut self) -> Result<Vec<Game>, anyhow::Error>
+    {
+        self.seen = self.stamp(); // Before reading, so a write that lands part way through still shows up as a change
+        self.read()
+    }
+
+    fn insert(&mut self, position: usize, game: &Game) -> Result<(), anyhow::Error>
+    {
+        self.apply(&[StorageChange::Insert(position, game.clone())])
+    }
+
+    fn update(&mut self, name: &str, game: &Game) -> Result<(), anyhow::Error>
+    {
+        self.apply(&[StorageChange::Update(name.to_string(), game.clone())])
+    }
+
+    fn delete(&mut self, name: &str) -> Result<(), anyhow::Error>
+    {
+        self.apply(&[StorageChange::Delete(name.to_string())])
+    }
+
+    fn query(&mut self, query: &str) -> Result<Vec<Game>, anyhow::Error>
+    {
+        let search = SearchQuery::parse(query);
+        Ok(self.read()?.into_iter().filter(|game| search.matches(game)).collect())
+    }
+
+    fn replace_all(&mut self, games: &[Game]) -> Result<(), anyhow::Error>
+    {
+        self.write(games)
+    }
+
+    fn changed_elsewhere(&mut self) -> Result<bool, anyhow::Error>
+    {
+        Ok(self.stamp() != self.seen)
+    }
+
+    // Every change is made before anything is written, so the file is only rewritten once
+    fn apply(&mut self, changes: &[StorageChange]) -> Result<(), anyhow::Error>
+    {
+        let mut games = self.read()?;
+        for change in changes {
+            apply_to(&mut games, change)?;
+        }
+        self.write(&games)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::tests::sample_games;
+
+    #[test]
+    fn json_round_trip() {
+        let path = std::env::temp_dir().join(format!("gamelog-json-test-{}.json", std::process::id()));
+        let mut storage = JsonStorage::new(&path);
+        let games = sample_games();
+
+        assert_eq!(storage.load().unwrap(), Vec::new()); // No file yet
+        storage.replace_all(&games[..2]).unwrap();
+        storage.insert(0, &games[2]).unwrap();
+        storage.delete("celeste").unwrap();
+
+        let loaded = storage.load().unwrap();
+        assert_eq!(loaded, vec![games[2].clone(), games[1].clone()]);
+        assert_eq!(storage.query("tag:roguelike").unwrap(), vec![games[1].clone()]);
+
+        // Written by something else (A text editor, the command line...)
+        assert!(!storage.changed_elsewhere().unwrap());
+        JsonStorage::new(&path).replace_all(&games).unwrap();
+        assert!(storage.changed_elsewhere().unwrap());
+        storage.load().unwrap();
+        assert!(!storage.changed_elsewhere().unwrap());
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,323 @@
+// Crates/Imports
+pub mod json;
+pub mod sqlite;
+
+use std::collections::HashMap;
+use std::fmt;
+use eframe::egui::{self, RichText, Ui};
+use serde::{Deserialize, Serialize};
+
+use crate::{app_setup::GameLog, instance_lock::{InstanceLock, SAVE_LOCK_TIMEOUT, WRITE_LOCK_FILE}, json_file_operations::Game, settings::{save_settings, Settings}, storage::{json::JsonStorage, sqlite::SqliteStorage}};
+
+pub const JSON_FILE: &str = "GameLog.Json";
+pub const SQLITE_FILE: &str = "GameLog.sqlite3";
+
+/// Where the log is kept. Chosen in the settings window (or with `my_gui_app convert`)
+#[derive(Copy, Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StorageBackend {
+    #[default]
+    Json, // One pretty printed file, easy to read and back up by hand
+    Sqlite, // Only what changed is written, so it stays quick with big logs
+}
+
+impl StorageBackend {
+    pub const ALL: [StorageBackend; 2] = [StorageBackend::Json, StorageBackend::Sqlite];
+
+    /// Read a backend name given on the command line
+    pub fn parse(text: &str) -> Option<StorageBackend>
+    {
+        match text.trim().to_lowercase().as_str() {
+            "json" => Some(StorageBackend::Json),
+            "sqlite" | "sqlite3" | "db" => Some(StorageBackend::Sqlite),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for StorageBackend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            StorageBackend::Json => "JSON",
+            StorageBackend::Sqlite => "SQLite",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// One change to the stored log. Games are found by their name (as it was before the change)
+#[derive(Clone, Debug, PartialEq)]
+pub enum StorageChange {
+    Insert(usize, Game), // Position in the log, and the game
+    Update(String, Game),
+    Delete(String),
+}
+
+/// Somewhere the game log can be kept
+pub trait Storage {
+    /// Every game, in log order
+    fn load(&mut self) -> Result<Vec<Game>, anyhow::Error>;
+
+    fn insert(&mut self, position: usize, game: &Game) -> Result<(), anyhow::Error>;
+
+    /// Replace the game called `name` (It may have been renamed)
+    fn update(&mut self, name: &str, game: &Game) -> Result<(), anyhow::Error>;
+
+    fn delete(&mut self, name: &str) -> Result<(), anyhow::Error>;
+
+    /// Games matching a main window search (See `SearchQuery`)
+    fn query(&mut self, query: &str) -> Result<Vec<Game>, anyhow::Error>;
+
+    /// Swap everything stored for `games`
+    fn replace_all(&mut self, games: &[Game]) -> Result<(), anyhow::Error>;
+
+    /// Whether something else (A text editor, another copy of the app...) has changed the log since this last loaded or saved it
+    fn changed_elsewhere(&mut self) -> Result<bool, anyhow::Error>;
+
+    /// Make several changes as one, so a batch is saved (or fails) together
+    fn apply(&mut self, changes: &[StorageChange]) -> Result<(), anyhow::Error>
+    {
+        for change in changes {
+            match change {
+                StorageChange::Insert(position, game) => self.insert(*position, game)?,
+                StorageChange::Update(name, game) => self.update(name, game)?,
+                StorageChange::Delete(name) => self.delete(name)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Stands in for a backend that couldn't be opened, so the app can still start (read only) without touching another backend's log.
+///
+/// Everything fails with the reason it couldn't be opened
+pub struct UnavailableStorage {
+    pub error: String,
+}
+
+impl Storage for UnavailableStorage {
+    fn load(&mut self) -> Result<Vec<Game>, anyhow::Error>
+    {
+        Err(anyhow::anyhow!("{}", self.error))
+    }
+
+    fn insert(&mut self, _position: usize, _game: &Game) -> Result<(), anyhow::Error>
+    {
+        self.load().map(|_| ())
+    }
+
+    fn update(&mut self, _name: &str, _game: &Game) -> Result<(), anyhow::Error>
+    {
+        self.load().map(|_| ())
+    }
+
+    fn delete(&mut self, _name: &str) -> Result<(), anyhow::Error>
+    {
+        self.load().map(|_| ())
+    }
+
+    fn query(&mut self, _query: &str) -> Result<Vec<Game>, anyhow::Error>
+    {
+        self.load()
+    }
+
+    fn replace_all(&mut self, _games: &[Game]) -> Result<(), anyhow::Error>
+    {
+        self.load().map(|_| ())
+    }
+
+    fn changed_elsewhere(&mut self) -> Result<bool, anyhow::Error>
+    {
+        Ok(false) // Nothing to watch
+    }
+}
+
+/// Work out the changes that turn `before` into `after`, so only what changed needs writing.
+///
+/// Names are matched ignoring case like `search_for_game` does, so changing the capitals of a name is an update of the game.
+/// Returns None if games were moved around, which is simpler to save by replacing everything
+pub fn plan_changes(before: &[Game], after: &[Game]) -> Option<Vec<StorageChange>>
+{
+    let before_names: Vec<String> = before.iter().map(|game| game.name.to_lowercase()).collect();
+    let after_names: Vec<String> = after.iter().map(|game| game.name.to_lowercase()).collect();
+    let before_by_name: HashMap<&str, &Game> = before_names.iter().map(String::as_str).zip(before).collect();
+
+    let mut changes: Vec<StorageChange> = before.iter().zip(&before_names)
+        .filter(|(_, name)| !after_names.contains(name))
+        .map(|(game, _)| StorageChange::Delete(game.name.clone()))
+        .collect();
+
+    // Games in both have to still be in the same order, as only inserts and deletes move things
+    let kept_before: Vec<&String> = before_names.iter().filter(|name| after_names.contains(name)).collect();
+    let kept_after: Vec<&String> = after_names.iter().filter(|name| before_by_name.contains_key(name.as_str())).collect();
+    if kept_before != kept_after {
+        return None;
+    }
+
+    for (position, (game, name)) in after.iter().zip(&after_names).enumerate() {
+        match before_by_name.get(name.as_str()) {
+            None => changes.push(StorageChange::Insert(position, game.clone())),
+            Some(old) if *old != game => changes.push(StorageChange::Update(old.name.clone(), game.clone())), // Found by the name it's stored under
+            Some(_) => (),
+        }
+    }
+
+    Some(changes)
+}
+
+/// Save the log after a change, writing as little as the backend allows
+pub fn save_changes(storage: &mut dyn Storage, before: &[Game], after: &[Game]) -> Result<(), anyhow::Error>
+{
+    match plan_changes(before, after) {
+        Some(changes) if changes.is_empty() => Ok(()),
+        Some(changes) => storage.apply(&changes),
+        None => storage.replace_all(after),
+    }
+}
+
+/// Open the backend picked in the settings
+pub fn open_storage(backend: StorageBackend) -> Result<Box<dyn Storage>, anyhow::Error>
+{
+    Ok(match backend {
+        StorageBackend::Json => Box::new(JsonStorage::new(JSON_FILE)),
+        StorageBackend::Sqlite => Box::new(SqliteStorage::open(SQLITE_FILE)?),
+    })
+}
+
+/// Copy the whole log from one backend into another (Anything already in `to` is replaced). Returns how many games were copied
+pub fn convert_storage(from: &mut dyn Storage, to: &mut dyn Storage) -> Result<usize, anyhow::Error>
+{
+    let games = from.load()?;
+    to.replace_all(&games)?;
+
+    // Read it back so a conversion never quietly loses anything
+    if to.load()? != games {
+        return Err(anyhow::anyhow!("The converted log doesn't match the original"));
+    }
+
+    Ok(games.len())
+}
+
+/// Move the log to another backend and make it the one used from now on. Returns the new storage and how many games were moved.
+///
+/// Won't replace a log that's already there unless `overwrite` is set
+pub fn switch_backend(settings: &mut Settings, to: StorageBackend, overwrite: bool) -> Result<(Box<dyn Storage>, usize), String>
+{
+    if settings.storage_backend == to {
+        return Err(format!("The log is already stored as {}", to));
+    }
+
+    let mut from_storage = open_storage(settings.storage_backend).map_err(|error| format!("Error Opening the {} Log: {}", settings.storage_backend, error))?;
+    let mut to_storage = open_storage(to).map_err(|error| format!("Error Opening the {} Log: {}", to, error))?;
+
+    let existing = to_storage.load().map_err(|error| format!("Error Reading the {} Log: {}", to, error))?;
+    if !existing.is_empty() && !overwrite {
+        return Err(format!("There's already a {} log with {} game(s) in it. Overwrite it to switch anyway", to, existing.len()));
+    }
+
+    let count = convert_storage(from_storage.as_mut(), to_storage.as_mut()).map_err(|error| format!("Error Converting the Log: {}", error))?;
+
+    settings.storage_backend = to;
+    save_settings(settings).map_err(|_| String::from("Error Saving Settings"))?;
+
+    Ok((to_storage, count))
+}
+
+// Storage part of the 'Settings' window
+impl GameLog {
+    pub fn storage_settings(&mut self, ui: &mut Ui)
+    {
+        ui.label(RichText::new("Storage").strong());
+
+        ui.horizontal(|ui| {
+            let current = self.settings.storage_backend;
+            ui.label(format!("The log is stored as {}", current));
+
+            for backend in StorageBackend::ALL.into_iter().filter(|backend| *backend != current) {
+                if ui.add_enabled(!self.read_only(), egui::Button::new(format!("Switch to {}", backend))).clicked() {
+                    let switched = InstanceLock::wait_for(WRITE_LOCK_FILE, SAVE_LOCK_TIMEOUT).map_err(|error| error.to_string())
+                        .and_then(|_write_lock| switch_backend(&mut self.settings, backend, self.storage_overwrite));
+
+                    match switched {
+                        Ok((storage, count)) => {
+                            self.storage = storage;
+                            self.storage_overwrite = false;
+                            self.storage_feedback = format!("Moved {} game(s) to {}", count, backend);
+                        },
+                        Err(error) => self.storage_feedback = error,
+                    }
+                }
+            }
+        });
+
+        ui.checkbox(&mut self.storage_overwrite, "Overwrite a log that's already there");
+
+        if !self.storage_feedback.is_empty() {
+            let color = if self.storage_feedback.starts_with("Moved") {
+                if self.dark_mode { egui::Color32::GREEN } else { egui::Color32::DARK_GREEN }
+            }
+            else {
+                egui::Color32::RED
+            };
+            ui.label(RichText::new(&self.storage_feedback).color(color));
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    /// A few games with everything filled in, shared by the backend tests
+    pub fn sample_games() -> Vec<Game> {
+        serde_json::from_str(r#"[
+            {"name": "Celeste", "rating": 90, "times_played": 2, "last_playthrough": "2025-03-01", "notes": "B-sides next",
+             "tags": ["platformer", "indie"], "platform": "PC", "storefront": "Steam", "play_time": 36000, "status": "completed",
+             "playthroughs": [{"date": "2024-12-24", "platform": "Switch", "note": "Christmas run"}, {"date": "2025-03-01", "platform": "PC", "play_time": 7200}],
+             "executables": ["Celeste.exe"], "release_year": 2018, "developer": "Maddy Makes Games", "genres": ["Platformer"], "cover": "celeste-1.png"},
+            {"name": "Hades", "rating": 100, "times_played": 1, "last_playthrough": "2024-06-01", "notes": "", "tags": ["roguelike"], "platform": "Switch"},
+            {"name": "Tunic", "rating": 80, "times_played": 0, "last_playthrough": "", "notes": "", "status": "backlog"}
+        ]"#).unwrap()
+    }
+
+    #[test]
+    fn plans_only_what_changed() {
+        let before = sample_games();
+        let mut after = before.clone();
+        after[1].rating = crate::enums::Rating::from_points(90).unwrap();
+        after.remove(2);
+        let mut copy = after[0].clone();
+        copy.name = String::from("Celeste (Copy)");
+        after.insert(1, copy.clone());
+
+        let changes = plan_changes(&before, &after).unwrap();
+        assert_eq!(changes, vec![
+            StorageChange::Delete(String::from("Tunic")),
+            StorageChange::Insert(1, copy),
+            StorageChange::Update(String::from("Hades"), after[2].clone()),
+        ]);
+
+        after.swap(0, 2); // Moving games around means replacing everything
+        assert_eq!(plan_changes(&before, &after), None);
+        assert_eq!(plan_changes(&before, &before), Some(Vec::new()));
+    }
+
+    #[test]
+    fn unopened_storage_never_reads_or_writes() {
+        let mut storage = UnavailableStorage { error: String::from("database is locked") };
+        let games = sample_games();
+
+        assert_eq!(storage.load().unwrap_err().to_string(), "database is locked");
+        assert!(save_changes(&mut storage, &[], &games).is_err());
+        assert!(!storage.changed_elsewhere().unwrap());
+    }
+
+    #[test]
+    fn changing_the_capitals_of_a_name_is_an_update() {
+        let before = sample_games();
+        let mut after = before.clone();
+        after[1].name = String::from("HADES");
+
+        assert_eq!(plan_changes(&before, &after), Some(vec![StorageChange::Update(String::from("Hades"), after[1].clone())]));
+    }
+}
@@ -0,0 +1,366 @@
+// Crates/Imports
+use std::path::Path;
+use std::time::Duration;
+use anyhow::anyhow;
+use rusqlite::{params, params_from_iter, Connection, OptionalExtension};
+
+use crate::{enums::{GameStatus, Rating}, json_file_operations::{Game, Playthrough, SearchQuery}, storage::{Storage, StorageChange}};
+
+// Each entry moves the database up one version (Kept in `PRAGMA user_version`). Only ever add to the end of this list
+const MIGRATIONS: &[&str] = &[
+    // 1: Games and everything that hangs off them
+    "CREATE TABLE games (
+        id INTEGER PRIMARY KEY,
+        position INTEGER NOT NULL,
+        name TEXT NOT NULL UNIQUE COLLATE NOCASE,
+        rating INTEGER NOT NULL,
+        times_played INTEGER NOT NULL,
+        last_playthrough TEXT NOT NULL,
+        notes TEXT NOT NULL,
+        platform TEXT NOT NULL COLLATE NOCASE,
+        storefront TEXT NOT NULL,
+        play_time_seconds INTEGER NOT NULL,
+        status TEXT,
+        release_year INTEGER,
+        developer TEXT NOT NULL,
+        cover TEXT NOT NULL
+    );
+    CREATE INDEX games_position ON games (position);
+    CREATE TABLE game_tags (
+        game_id INTEGER NOT NULL REFERENCES games (id) ON DELETE CASCADE,
+        position INTEGER NOT NULL,
+        tag TEXT NOT NULL COLLATE NOCASE
+    );
+    CREATE TABLE game_genres (
+        game_id INTEGER NOT NULL REFERENCES games (id) ON DELETE CASCADE,
+        position INTEGER NOT NULL,
+        genre TEXT NOT NULL
+    );
+    CREATE TABLE game_executables (
+        game_id INTEGER NOT NULL REFERENCES games (id) ON DELETE CASCADE,
+        position INTEGER NOT NULL,
+        executable TEXT NOT NULL
+    );
+    CREATE TABLE playthroughs (
+        game_id INTEGER NOT NULL REFERENCES games (id) ON DELETE CASCADE,
+        position INTEGER NOT NULL,
+        date TEXT NOT NULL,
+        platform TEXT NOT NULL COLLATE NOCASE,
+        storefront TEXT NOT NULL,
+        play_time_seconds INTEGER NOT NULL,
+        note TEXT NOT NULL
+    );
+    CREATE INDEX game_tags_game ON game_tags (game_id);
+    CREATE INDEX game_genres_game ON game_genres (game_id);
+    CREATE INDEX game_executables_game ON game_executables (game_id);
+    CREATE INDEX playthroughs_game ON playthroughs (game_id);",
+    // 2: Times played went from a u8 to a u32. The column was always wide enough, this just keeps it in range
+    "CREATE TRIGGER games_times_played_insert BEFORE INSERT ON games
+        WHEN NEW.times_played NOT BETWEEN 0 AND 4294967295
+        BEGIN SELECT RAISE(ABORT, 'times_played is out of range'); END;
+    CREATE TRIGGER games_times_played_update BEFORE UPDATE OF times_played ON games
+        WHEN NEW.times_played NOT BETWEEN 0 AND 4294967295
+        BEGIN SELECT RAISE(ABORT, 'times_played is out of range'); END;",
+];
+
+/// The log in a SQLite database, so a change only writes the games it touched
+pub struct SqliteStorage {
+    connection: Connection,
+    data_version: i64, // Only moves when another connection commits, so it shows changes from other copies of the app
+}
+
+impl SqliteStorage {
+    /// Open (or create) the database and bring its tables up to date
+    pub fn open(path: impl AsRef<Path>) -> Result<SqliteStorage, anyhow::Error>
+    {
+        let mut connection = Connection::open(path)?;
+        connection.pragma_update(None, "foreign_keys", true)?;
+        migrate(&mut connection)?;
+        let data_version = data_version(&connection)?;
+
+        Ok(SqliteStorage { connection, data_version })
+    }
+}
+
+fn data_version(connection: &Connection) -> Result<i64, anyhow::Error>
+{
+    Ok(connection.pragma_query_value(None, "data_version", |row| row.get(0))?)
+}
+
+// Run any migrations the database hasn't had yet
+fn migrate(connection: &mut Connection) -> Result<(), anyhow::Error>
+{
+    let version: usize = connection.pragma_query_value(None, "user_version", |row| row.get(0))?;
+    if version > MIGRATIONS.len() {
+        return Err(anyhow!("The game log database is from a newer version of the app"));
+    }
+
+    for (i, migration) in MIGRATIONS.iter().enumerate().skip(version) {
+        let transaction = connection.transaction()?;
+        transaction.execute_batch(migration)?;
+        transaction.pragma_update(None, "user_version", i + 1)?;
+        transaction.commit()?;
+    }
+
+    Ok(())
+}
+
+fn game_id(connection: &Connection, name: &str) -> Result<i64, anyhow::Error>
+{
+    connection.query_row("SELECT id FROM games WHERE name = ?1", [name], |row| row.get(0))
+        .optional()?
+        .ok_or_else(|| anyhow!("{} isn't in the game log", name))
+}
+
+// Tags, genres, executables and playthroughs are all rewritten whenever a game is
+fn write_children(connection: &Connection, id: i64, game: &Game) -> Result<(), anyhow::Error>
+{
+    for table in ["game_tags", "game_genres", "game_executables", "playthroughs"] {
+        connection.execute(&format!("DELETE FROM {} WHERE game_id = ?1", table), [id])?;
+    }
+
+    let lists = [("game_tags", "tag", &game.tags), ("game_genres", "genre", &game.genres), ("game_executables", "executable", &game.executables)];
+    for (table, column, values) in lists {
+        let mut statement = connection.prepare_cached(&format!("INSERT INTO {} (game_id, position, {}) VALUES (?1, ?2, ?3)", table, column))?;
+        for (position, value) in values.iter().enumerate() {
+            statement.execute(params![id, position, value])?;
+        }
+    }
+
+    let mut statement = connection.prepare_cached(
+        "INSERT INTO playthroughs (game_id, position, date, platform, storefront, play_time_seconds, note) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)")?;
+    for (position, playthrough) in game.playthroughs.iter().enumerate() {
+        statement.execute(params![id, position, playthrough.date, playthrough.platform, playthrough.storefront,
+            playthrough.play_time.as_secs() as i64, playthrough.note])?;
+    }
+
+    Ok(())
+}
+
+fn insert_game(connection: &Connection, position: usize, game: &Game) -> Result<(), anyhow::Error>
+{
+    // Make room at the position
+    connection.execute("UPDATE games SET position = position + 1 WHERE position >= ?1", [position])?;
+
+    connection.execute(
+        "INSERT INTO games (position, name, rating, times_played, last_playthrough, notes, platform, storefront,
+            play_time_seconds, status, release_year, developer, cover)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+        params![position, game.name, game.rating.points(), game.times_played, game.last_playthrough, game.notes, game.platform,
+            game.storefront, game.play_time.as_secs() as i64, game.status.map(|status| status.to_string()), game.release_year,
+            game.developer, game.cover])?;
+
+    write_children(connection, connection.last_insert_rowid(), game)
+}
+
+fn update_game(connection: &Connection, name: &str, game: &Game) -> Result<(), anyhow::Error>
+{
+    let id = game_id(connection, name)?;
+
+    connection.execute(
+        "UPDATE games SET name = ?2, rating = ?3, times_played = ?4, last_playthrough = ?5, notes = ?6, platform = ?7, storefront = ?8,
+            play_time_seconds = ?9, status = ?10, release_year = ?11, developer = ?12, cover = ?13
+         WHERE id = ?1",
+        params![id, game.name, game.rating.points(), game.times_played, game.last_playthrough, game.notes, game.platform,
+            game.storefront, game.play_time.as_secs() as i64, game.status.map(|status| status.to_string()), game.release_year,
+            game.developer, game.cover])?;
+
+    write_children(connection, id, game)
+}
+
+fn delete_game(connection: &Connection, name: &str) -> Result<(), anyhow::Error>
+{
+    let id = game_id(connection, name)?;
+    let position: i64 = connection.query_row("SELECT position FROM games WHERE id = ?1", [id], |row| row.get(0))?;
+
+    connection.execute("DELETE FROM games WHERE id = ?1", [id])?; // Everything else goes with it (ON DELETE CASCADE)
+    connection.execute("UPDATE games SET position = position - 1 WHERE position > ?1", [position])?;
+
+    Ok(())
+}
+
+// One list column of a game, in order
+fn read_list(connection: &Connection, table: &str, column: &str, id: i64) -> Result<Vec<String>, anyhow::Error>
+{
+    let mut statement = connection.prepare_cached(&format!("SELECT {} FROM {} WHERE game_id = ?1 ORDER BY position", column, table))?;
+    let values = statement.query_map([id], |row| row.get(0))?.collect::<Result<Vec<String>, _>>()?;
+    Ok(values)
+}
+
+// Read every game matching `condition` (An SQL expression over the games table), in log order
+fn read_games(connection: &Connection, condition: &str, values: &[String]) -> Result<Vec<Game>, anyhow::Error>
+{
+    let mut statement = connection.prepare(&format!(
+        "SELECT id, name, rating, times_played, last_playthrough, notes, platform, storefront, play_time_seconds, status,
+            release_year, developer, cover
+         FROM games WHERE {} ORDER BY position", condition))?;
+
+    let rows = statement.query_map(params_from_iter(values), |row| {
+        let points: u8 = row.get(2)?;
+        let game = Game {
+            name: row.get(1)?,
+            rating: Rating::from_points(points).ok_or(rusqlite::Error::IntegralValueOutOfRange(2, points.into()))?,
+            times_played: row.get(3)?,
+            last_playthrough: row.get(4)?,
+            notes: row.get(5)?,
+            tags: Vec::new(),
+            platform: row.get(6)?,
+            storefront: row.get(7)?,
+            playthroughs: Vec::new(),
+            play_time: Duration::from_secs(row.get::<_, i64>(8)?.max(0) as u64),
+            executables: Vec::new(),
+            status: row.get::<_, Option<String>>(9)?.as_deref().and_then(GameStatus::parse),
+            release_year: row.get(10)?,
+            developer: row.get(11)?,
+            genres: Vec::new(),
+            cover: row.get(12)?,
+        };
+        Ok((row.get::<_, i64>(0)?, game))
+    })?.collect::<Result<Vec<(i64, Game)>, _>>()?;
+
+    let mut playthrough_statement = connection.prepare_cached(
+        "SELECT date, platform, storefront, play_time_seconds, note FROM playthroughs WHERE game_id = ?1 ORDER BY position")?;
+
+    let mut games = Vec::with_capacity(rows.len());
+    for (id, mut game) in rows {
+        game.tags = read_list(connection, "game_tags", "tag", id)?;
+        game.genres = read_list(connection, "game_genres", "genre", id)?;
+        game.executables = read_list(connection, "game_executables", "executable", id)?;
+        game.playthroughs = playthrough_statement.query_map([id], |row| Ok(Playthrough {
+            date: row.get(0)?,
+            platform: row.get(1)?,
+            storefront: row.get(2)?,
+            play_time: Duration::from_secs(row.get::<_, i64>(3)?.max(0) as u64),
+            note: row.get(4)?,
+        }))?.collect::<Result<Vec<Playthrough>, _>>()?;
+
+        games.push(game);
+    }
+
+    Ok(games)
+}
+
+impl Storage for SqliteStorage {
+    fn load(&mut self) -> Result<Vec<Game>, anyhow::Error>
+    {
+        self.data_version = data_version(&self.connection)?;
+        read_games(&self.connection, "1", &[])
+    }
+
+    fn insert(&mut self, position: usize, game: &Game) -> Result<(), anyhow::Error>
+    {
+        self.apply(&[StorageChange::Insert(position, game.clone())])
+    }
+
+    fn update(&mut self, name: &str, game: &Game) -> Result<(), anyhow::Error>
+    {
+        self.apply(&[StorageChange::Update(name.to_string(), game.clone())])
+    }
+
+    fn delete(&mut self, name: &str) -> Result<(), anyhow::Error>
+    {
+        self.apply(&[StorageChange::Delete(name.to_string())])
+    }
+
+    // The filters are done in SQL so only matching games are read
+    fn query(&mut self, query: &str) -> Result<Vec<Game>, anyhow::Error>
+    {
+        let search = SearchQuery::parse(query);
+        let mut conditions = vec![String::from("1")];
+        let mut values = Vec::new();
+
+        // NOCASE only ignores the case of plain ASCII letters, so other names are left to the check below
+        if !search.name.is_empty() && search.name.is_ascii() {
+            conditions.push(String::from("name = ?"));
+            values.push(search.name.clone());
+        }
+        for tag in search.tags.iter().filter(|tag| tag.is_ascii()) { // Same goes for tags
+            conditions.push(String::from("EXISTS (SELECT 1 FROM game_tags WHERE game_id = games.id AND tag = ?)"));
+            values.push(tag.clone());
+        }
+        for platform in &search.platforms {
+            // Numbered so the one platform can be used twice. Plain `?`s after it carry on from the highest number used
+            let number = values.len() + 1;
+            conditions.push(format!("(platform = ?{0} OR EXISTS (SELECT 1 FROM playthroughs WHERE game_id = games.id AND platform = ?{0}))", number));
+            values.push(platform.clone());
+        }
+        for status in &search.statuses {
+            conditions.push(String::from("status = ?"));
+            values.push(status.to_string());
+        }
+
+        let games = read_games(&self.connection, &conditions.join(" AND "), &values)?;
+        Ok(games.into_iter().filter(|game| search.matches(game)).collect())
+    }
+
+    fn replace_all(&mut self, games: &[Game]) -> Result<(), anyhow::Error>
+    {
+        let transaction = self.connection.transaction()?;
+        transaction.execute("DELETE FROM games", [])?;
+        for (position, game) in games.iter().enumerate() {
+            insert_game(&transaction, position, game)?;
+        }
+        transaction.commit()?;
+
+        Ok(())
+    }
+
+    fn changed_elsewhere(&mut self) -> Result<bool, anyhow::Error>
+    {
+        Ok(data_version(&self.connection)? != self.data_version)
+    }
+
+    // One transaction for the lot, so a failed change leaves the database as it was
+    fn apply(&mut self, changes: &[StorageChange]) -> Result<(), anyhow::Error>
+    {
+        let transaction = self.connection.transaction()?;
+        for change in changes {
+            match change {
+                StorageChange::Insert(position, game) => insert_game(&transaction, *position, game)?,
+                StorageChange::Update(name, game) => update_game(&transaction, name, game)?,
+                StorageChange::Delete(name) => delete_game(&transaction, name)?,
+            }
+        }
+        transaction.commit()?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::tests::sample_games;
+
+    #[test]
+    fn sqlite_round_trip_and_query() {
+        let mut storage = SqliteStorage::open(":memory:").unwrap();
+        let games = sample_games();
+
+        storage.replace_all(&games).unwrap();
+        assert_eq!(storage.load().unwrap(), games);
+
+        let mut celeste = games[0].clone();
+        celeste.name = String::from("Celeste Classic");
+        celeste.tags.push(String::from("pico-8"));
+        storage.update("celeste", &celeste).unwrap();
+        storage.delete("Hades").unwrap();
+        storage.insert(1, &games[1]).unwrap();
+        assert_eq!(storage.load().unwrap(), vec![celeste.clone(), games[1].clone(), games[2].clone()]);
+
+        assert_eq!(storage.query("tag:PICO-8 platform:switch").unwrap(), vec![celeste.clone()]); // Switch from a playthrough
+        assert_eq!(storage.query("status:backlog").unwrap(), vec![games[2].clone()]);
+        assert_eq!(storage.query("hades").unwrap(), vec![games[1].clone()]);
+        assert!(storage.query("tag:roguelike status:completed").unwrap().is_empty());
+
+        assert!(storage.update("Minecraft", &celeste).is_err());
+        assert!(storage.insert(0, &games[1]).is_err()); // Names are unique
+        assert_eq!(storage.load().unwrap().len(), 3); // And the failed insert left nothing behind
+
+        // Past what the old u8 could hold, but nothing the triggers from migration 2 let through
+        celeste.times_played = 300;
+        storage.update("Celeste Classic", &celeste).unwrap();
+        assert_eq!(storage.load().unwrap()[0].times_played, 300);
+        assert!(storage.connection.execute("UPDATE games SET times_played = -1", []).is_err());
+    }
+}
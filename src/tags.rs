@@ -0,0 +1,291 @@
+// Crates/Imports
+use eframe::egui::{self, Button, Color32, ComboBox, Label, RichText, TextEdit, Ui, Vec2};
+
+use crate::{app_setup::GameLog, export::write_csv_export, json_file_operations::Game};
+
+pub const MAX_TAG_LENGTH: usize = 30;
+const MAX_SUGGESTIONS: usize = 5; // Stops the autocomplete list taking over the window
+
+/// Every tag used in the log along with how many games have it, sorted alphabetically.
+///
+/// Tags are compared without caring about case, the first spelling found is the one shown.
+pub fn all_tags(game_log: &[Game]) -> Vec<(String, usize)>
+{
+    let mut tags: Vec<(String, usize)> = Vec::new();
+
+    for tag in game_log.iter().flat_map(|game| game.tags.iter()) {
+        match tags.iter_mut().find(|(existing, _)| same_tag(existing, tag)) {
+            Some((_, count)) => *count += 1,
+            None => tags.push((tag.clone(), 1)),
+        }
+    }
+
+    tags.sort_by_key(|(tag, _)| tag.to_lowercase());
+    tags
+}
+
+/// Whether two tags are the same ignoring case. Folds the whole of Unicode (Not just ASCII) so it agrees with how tags are sorted
+pub fn same_tag(a: &str, b: &str) -> bool
+{
+    a == b || a.to_lowercase() == b.to_lowercase()
+}
+
+pub fn has_tag(game: &Game, tag: &str) -> bool
+{
+    game.tags.iter().any(|game_tag| same_tag(game_tag, tag))
+}
+
+/// Tidy up a list of tags: trims spaces, drops empty ones and removes duplicates (ignoring case)
+pub fn normalise_tags(tags: &[String]) -> Vec<String>
+{
+    let mut cleaned: Vec<String> = Vec::new();
+
+    for tag in tags {
+        let tag = tag.trim();
+        if !tag.is_empty() && !cleaned.iter().any(|existing| same_tag(existing, tag)) {
+            cleaned.push(tag.to_string());
+        }
+    }
+
+    cleaned
+}
+
+/// Rename a tag on every game in the log. If a game already has the new tag the two are merged into one.
+///
+/// Returns how many games were changed
+pub fn rename_tag(game_log: &mut [Game], from: &str, to: &str) -> usize
+{
+    let mut changed = 0;
+
+    for game in game_log.iter_mut().filter(|game| has_tag(game, from)) {
+        for tag in game.tags.iter_mut() {
+            if same_tag(tag, from) || same_tag(tag, to) { // The new spelling wins when merging, so it's the same on every game
+                *tag = to.to_string();
+            }
+        }
+        game.tags = normalise_tags(&game.tags); // Merges the duplicate if the game had both tags
+        changed += 1;
+    }
+
+    changed
+}
+
+/// Tag picker used by the adding and editing windows.
+///
+/// Shows the chosen tags (click one to remove it), a box to type a new one and, while typing, buttons for matching tags that are already in the log
+pub fn tag_editor(ui: &mut Ui, tags: &mut Vec<String>, input: &mut String, known_tags: &[(String, usize)])
+{
+    ui.vertical(|ui| {
+        // Current Tags
+        if !tags.is_empty() {
+            ui.horizontal_wrapped(|ui| {
+                let mut removed = None;
+                for (i, tag) in tags.iter().enumerate() {
+                    if ui.add(Button::new(format!("{} ✖", tag)).small()).on_hover_text("Remove Tag").clicked() {
+                        removed = Some(i);
+                    }
+                }
+                if let Some(i) = removed {
+                    tags.remove(i);
+                }
+            });
+        }
+
+        // New Tag
+        ui.horizontal(|ui| {
+            let response = ui.add_sized(Vec2::new(120.0, 20.0), TextEdit::singleline(input)
+                .hint_text("Add a tag")
+                .char_limit(MAX_TAG_LENGTH));
+
+            let entered = response.lost_focus() && ui.input(|input| input.key_pressed(egui::Key::Enter));
+
+            if (ui.add(Button::new("+").small()).clicked() || entered) && !input.trim().is_empty() {
+                tags.push(input.trim().to_string());
+                *tags = normalise_tags(tags);
+                input.clear();
+            }
+        });
+
+        // Autocomplete from tags already in the log
+        if !input.trim().is_empty() {
+            let typed = input.trim().to_lowercase();
+            let suggestions: Vec<&String> = known_tags.iter()
+                .map(|(tag, _)| tag)
+                .filter(|tag| tag.to_lowercase().starts_with(&typed) && !tags.iter().any(|chosen| same_tag(chosen, tag)))
+                .take(MAX_SUGGESTIONS)
+                .collect();
+
+            ui.horizontal_wrapped(|ui| {
+                for suggestion in suggestions {
+                    if ui.add(Button::new(RichText::new(suggestion).italics()).small()).clicked() {
+                        tags.push(suggestion.clone());
+                        input.clear();
+                    }
+                }
+            });
+        }
+    });
+}
+
+// Tag sidebar and 'Tags' Window GUI Code
+impl GameLog {
+    /// Tag list for the main screen's sidebar. Clicking a tag filters the game list down to games with that tag
+    pub fn tag_filter_list(&mut self, ui: &mut Ui)
+    {
+        ui.label(RichText::new("Tags").strong().size(18.0));
+        ui.add_space(5.0);
+
+        let tags = all_tags(&self.game_file_contents);
+
+        if tags.is_empty() {
+            ui.label(RichText::new("No tags yet").italics());
+        }
+
+        if ui.selectable_label(self.tag_filter.is_none(), "All Tags").clicked() {
+            self.tag_filter = None;
+        }
+
+        for (tag, count) in tags {
+            let selected = self.tag_filter.as_ref().is_some_and(|filter| same_tag(filter, &tag));
+
+            if ui.selectable_label(selected, format!("{} ({})", tag, count)).clicked() {
+                self.tag_filter = if selected { None } else { Some(tag) }; // Clicking the selected tag again turns the filter off
+            }
+        }
+    }
+
+    pub fn tags_gui (&mut self, ui: &mut Ui)
+    {
+        let tags = all_tags(&self.game_file_contents);
+        let label_size = Vec2::new(100.0, 20.0);
+
+        ui.add_space(5.0);
+
+        ui.vertical(|ui| {
+            ui.label(RichText::new("Rename or merge a tag across the whole log.\nRenaming to a tag that already exists merges the two.").italics());
+
+            ui.add_space(5.0);
+
+            ui.horizontal(|ui| {
+                ui.add_sized(label_size, Label::new(RichText::new("Tag").strong()));
+
+                ComboBox::from_id_salt("rename_tag_from")
+                    .selected_text(if self.rename_tag_from.is_empty() { "Pick a tag" } else { &self.rename_tag_from })
+                    .show_ui(ui, |ui| {
+                        for (tag, count) in &tags {
+                            ui.selectable_value(&mut self.rename_tag_from, tag.clone(), format!("{} ({})", tag, count));
+                        }
+                    });
+            });
+
+            ui.horizontal(|ui| {
+                ui.add_sized(label_size, Label::new(RichText::new("New Name").strong()));
+
+                ui.add_sized(Vec2::new(150.0, 20.0), TextEdit::singleline(&mut self.rename_tag_to)
+                    .hint_text("New or existing tag")
+                    .char_limit(MAX_TAG_LENGTH));
+            });
+
+            ui.add_space(5.0);
+
+            if !self.tags_feedback_message.is_empty() {
+                ui.label(RichText::new(&self.tags_feedback_message).color(
+                    if self.error_confirmation {
+                        Color32::RED
+                    }
+                    else if self.dark_mode {
+                        Color32::GREEN
+                    }
+                    else {
+                        Color32::DARK_GREEN
+                    }
+                ));
+            }
+
+            ui.horizontal(|ui| {
+                if ui.add_sized(Vec2::new(150.0, 20.0), Button::new("Rename / Merge")).clicked() {
+                    self.error_confirmation = true;
+
+                    let new_name = self.rename_tag_to.trim().to_string();
+
+                    if self.rename_tag_from.is_empty() || new_name.is_empty() {
+                        self.tags_feedback_message = String::from("Pick a tag and enter its new name");
+                    }
+                    else {
+                        let description = format!("Rename tag {} to {}", self.rename_tag_from, new_name);
+                        let old_name = self.rename_tag_from.clone();
+
+                        self.tags_feedback_message = match self.apply_change(&description, |game_log| Ok(rename_tag(&mut game_log.game_file_contents, &old_name, &new_name))) {
+                            Ok(changed) => {
+                                // Keep the sidebar filter pointing at the same tag
+                                if self.tag_filter.as_ref().is_some_and(|filter| same_tag(filter, &old_name)) {
+                                    self.tag_filter = Some(new_name.clone());
+                                }
+
+                                self.error_confirmation = false;
+                                self.rename_tag_from = new_name;
+                                self.rename_tag_to.clear();
+                                format!("Updated {} game(s)", changed)
+                            },
+                            Err(error) => error,
+                        };
+                    }
+                }
+
+                // Export whatever the sidebar is currently showing, so a tag can be exported on its own
+                if ui.add_sized(Vec2::new(150.0, 20.0), Button::new("Export Shown Games")).clicked() {
+                    let shown: Vec<Game> = self.game_file_contents.iter()
+                        .filter(|game| self.is_game_shown(game))
+                        .cloned()
+                        .collect();
+
+                    self.error_confirmation = true;
+                    self.tags_feedback_message = match write_csv_export(&shown, self.settings.export_spoilers) {
+                        Ok(path) => {
+                            self.error_confirmation = false;
+                            format!("Exported {} game(s) to {}", shown.len(), path)
+                        },
+                        Err(_) => String::from("Error Exporting Games"),
+                    };
+                }
+            });
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::json_file_operations::SearchQuery;
+
+    fn game(name: &str, tags: &[&str]) -> Game {
+        serde_json::from_value(serde_json::json!({"name": name, "rating": 80, "times_played": 1, "last_playthrough": "", "notes": "", "tags": tags})).unwrap()
+    }
+
+    fn tags(list: &[&str]) -> Vec<String> {
+        list.iter().map(|tag| tag.to_string()).collect()
+    }
+
+    #[test]
+    fn tags_are_tidied_ignoring_case() {
+        assert_eq!(normalise_tags(&tags(&[" RPG ", "", "rpg", "Émulation", "ÉMULATION", "Open World"])), tags(&["RPG", "Émulation", "Open World"]));
+        assert!(same_tag("Ökonomie", "öKONOMIE"));
+    }
+
+    #[test]
+    fn renaming_a_tag_merges_it_into_an_existing_one() {
+        let mut log = vec![game("Hades", &["roguelike", "Rogue-lite"]), game("Celeste", &["platformer"]), game("Dead Cells", &["ROGUE-LITE"])];
+
+        assert_eq!(rename_tag(&mut log, "rogue-lite", "Roguelike"), 2);
+        assert_eq!(log[0].tags, tags(&["Roguelike"])); // Both tags became one
+        assert_eq!(log[2].tags, tags(&["Roguelike"]));
+        assert_eq!(all_tags(&log), vec![(String::from("platformer"), 1), (String::from("Roguelike"), 2)]);
+    }
+
+    #[test]
+    fn tags_with_spaces_can_be_searched() {
+        let tagged = game("Elden Ring", &["Open World"]);
+        assert!(SearchQuery::parse("tag:open_world").matches(&tagged));
+        assert!(!SearchQuery::parse("tag:open").matches(&tagged));
+    }
+}
@@ -0,0 +1,162 @@
+// Crates/Imports
+use std::time::Duration;
+use eframe::egui::{Color32, Label, RichText, Ui};
+
+use crate::{enums::{GameStatus, RatingScale}, json_file_operations::{Game, Playthrough}, tags::{normalise_tags, MAX_TAG_LENGTH}};
+
+pub const MAX_NAME_LENGTH: usize = 50; // Same limit the input boxes enforce with char_limit
+
+/// The raw, unchecked text a user (or the CLI/an importer) typed in for a game.
+///
+/// Nothing here is trusted until it has gone through `validate`, which is the one place the field rules live.
+#[derive(Clone, Default)]
+pub struct GameDraft {
+    pub name: String,
+    pub rating: String,
+    pub rating_scale: RatingScale, // How the rating text should be read
+    pub times_played: u32,
+    pub last_playthrough: String,
+    pub notes: String,
+    pub tags: Vec<String>,
+    pub platform: String,
+    pub storefront: String,
+    pub playthroughs: Vec<Playthrough>,
+    pub play_time: Duration,
+    pub executables: Vec<String>,
+    pub status: Option<GameStatus>,
+    pub release_year: Option<i32>,
+    pub developer: String,
+    pub genres: Vec<String>,
+    pub cover: String,
+}
+
+/// Which field of a game an error belongs to, so the GUI can put the message next to the right input box
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum GameField {
+    Name,
+    Rating,
+    Tags,
+}
+
+#[derive(Clone, Debug)]
+pub struct FieldError {
+    pub field: GameField,
+    pub message: String,
+}
+
+impl GameDraft {
+    /// Start a draft from a game that's already in the log (Used when editing so blank boxes keep their old values).
+    ///
+    /// The rating goes in as exact points, so it isn't rounded to the user's scale just because something else was edited
+    pub fn from_game(game: &Game) -> Self {
+        Self {
+            name: game.name.clone(),
+            rating: game.rating.points().to_string(),
+            rating_scale: RatingScale::HundredPoints,
+            times_played: game.times_played,
+            last_playthrough: game.last_playthrough.clone(),
+            notes: game.notes.clone(),
+            tags: game.tags.clone(),
+            platform: game.platform.clone(),
+            storefront: game.storefront.clone(),
+            playthroughs: game.playthroughs.clone(),
+            play_time: game.play_time,
+            executables: game.executables.clone(),
+            status: game.status,
+            release_year: game.release_year,
+            developer: game.developer.clone(),
+            genres: game.genres.clone(),
+            cover: game.cover.clone(),
+        }
+    }
+}
+
+/// Check every field of a draft and turn it into a `Game`.
+///
+/// All errors are collected rather than stopping at the first one, so every bad field can be flagged at once.
+pub fn validate(draft: &GameDraft) -> Result<Game, Vec<FieldError>> {
+    let mut errors = Vec::new();
+
+    let name = draft.name.trim();
+    if name.is_empty() {
+        errors.push(FieldError { field: GameField::Name, message: String::from("A game name is required") });
+    }
+    else if name.chars().count() > MAX_NAME_LENGTH { // Count chars not bytes, so names with accents etc. aren't punished
+        errors.push(FieldError { field: GameField::Name, message: format!("Names must be {} characters or less", MAX_NAME_LENGTH) });
+    }
+
+    let rating = draft.rating_scale.parse(&draft.rating);
+    if rating.is_none() {
+        let message = if draft.rating.trim().is_empty() { "A rating is required" } else { draft.rating_scale.hint() };
+        errors.push(FieldError { field: GameField::Rating, message: String::from(message) });
+    }
+
+    let tags = normalise_tags(&draft.tags);
+    if let Some(tag) = tags.iter().find(|tag| tag.chars().count() > MAX_TAG_LENGTH) {
+        errors.push(FieldError { field: GameField::Tags, message: format!("\"{}\" is too long, tags must be {} characters or less", tag, MAX_TAG_LENGTH) });
+    }
+
+    match rating {
+        Some(rating) if errors.is_empty() => Ok(Game {
+            name: name.to_string(),
+            rating,
+            times_played: draft.times_played,
+            last_playthrough: draft.last_playthrough.clone(),
+            notes: draft.notes.clone(),
+            tags,
+            platform: draft.platform.trim().to_string(),
+            storefront: draft.storefront.trim().to_string(),
+            playthroughs: draft.playthroughs.clone(),
+            play_time: draft.play_time,
+            executables: draft.executables.clone(),
+            status: draft.status,
+            release_year: draft.release_year,
+            developer: draft.developer.trim().to_string(),
+            genres: draft.genres.clone(),
+            cover: draft.cover.clone(),
+        }),
+        _ => Err(errors),
+    }
+}
+
+/// Find the message (if any) for a particular field
+pub fn error_for(errors: &[FieldError], field: GameField) -> Option<&str> {
+    errors.iter().find(|error| error.field == field).map(|error| error.message.as_str())
+}
+
+/// Show a field's error in red underneath its input box. Draws nothing if the field is fine
+pub fn field_error_label(ui: &mut Ui, errors: &[FieldError], field: GameField) {
+    if let Some(message) = error_for(errors, field) {
+        ui.add(Label::new(RichText::new(message).color(Color32::RED).small()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn draft(name: &str, rating: &str) -> GameDraft {
+        GameDraft { name: name.to_string(), rating: rating.to_string(), ..GameDraft::default() }
+    }
+
+    #[test]
+    fn every_bad_field_is_reported() {
+        let game = validate(&GameDraft { tags: vec![String::from(" rpg "), String::from("RPG")], ..draft("  Celeste  ", "4") }).unwrap();
+        assert_eq!((game.name.as_str(), game.rating.points(), game.tags), ("Celeste", 80, vec![String::from("rpg")]));
+
+        // Accented names are counted by characters, not bytes
+        assert!(validate(&draft(&"é".repeat(MAX_NAME_LENGTH), "4")).is_ok());
+
+        let errors = validate(&GameDraft { tags: vec!["x".repeat(MAX_TAG_LENGTH + 1)], ..draft(&"a".repeat(MAX_NAME_LENGTH + 1), "6") }).unwrap_err();
+        let fields: Vec<GameField> = errors.iter().map(|error| error.field).collect();
+        assert_eq!(fields, [GameField::Name, GameField::Rating, GameField::Tags]);
+
+        assert_eq!(error_for(&validate(&draft(" ", "")).unwrap_err(), GameField::Rating), Some("A rating is required"));
+    }
+
+    #[test]
+    fn unchanged_ratings_are_not_rounded() {
+        let game = validate(&GameDraft { rating_scale: RatingScale::HundredPoints, ..draft("Hades", "90") }).unwrap();
+        assert_eq!(validate(&GameDraft::from_game(&game)).unwrap().rating.points(), 90); // Not 100 on five stars
+    }
+}